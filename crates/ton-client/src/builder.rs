@@ -1,6 +1,11 @@
+#[cfg(feature = "emulator")]
+use crate::emulator::{EmulatorLayer, EmulatorService};
 use crate::{
     Client, RoutedClient, TonService,
-    pool::{Balance, LiteServerDiscoverError, LiteServerDiscoverHandle},
+    pool::{
+        Balance, HedgePolicy, LiteServerDiscoverError, LiteServerDiscoverHandle, OutlierConfig,
+        OutlierDetection,
+    },
 };
 use futures::{Stream, StreamExt, TryStreamExt, stream};
 use std::{future::ready, path::PathBuf, pin::Pin, time::Duration};
@@ -12,11 +17,13 @@ use ton_config::{
 use ton_tower::{
     request::GetMasterchainInfo,
     service::{
+        cache::{Cache, CacheLayer},
         error::{ErrorLayer, ErrorService},
         metric::ConcurrencyMetric,
         reconnect::Reconnect,
         retry::RetryPolicy,
         shared::{SharedLayer, SharedService},
+        singleflight::{Singleflight, SingleflightLayer},
         timeout::{Timeout, TimeoutLayer},
     },
 };
@@ -32,9 +39,11 @@ use url::Url;
 
 pub type ReconnectingClient<F> = Reconnect<RateLimit<F>, TonConfig>;
 
-pub type WrappedCursor<F> = RoutedClient<
-    ConcurrencyMetric<
-        ConcurrencyLimit<SharedService<ErrorService<Timeout<PeakEwma<ReconnectingClient<F>>>>>>,
+pub type WrappedCursor<F> = OutlierDetection<
+    RoutedClient<
+        ConcurrencyMetric<
+            ConcurrencyLimit<SharedService<ErrorService<Timeout<PeakEwma<ReconnectingClient<F>>>>>>,
+        >,
     >,
 >;
 
@@ -45,9 +54,18 @@ pub type BoxClientDiscover<F> = Pin<
     >,
 >;
 
-pub type SharedBalance<F> = SharedService<Balance<WrappedCursor<F>, BoxClientDiscover<F>>>;
+pub type SharedBalance<F> =
+    Singleflight<SharedService<Balance<WrappedCursor<F>, BoxClientDiscover<F>>>>;
+
+#[cfg(feature = "emulator")]
+pub type PoolTransport<F> = Either<EmulatorService<CachedTransport<F>>, CachedTransport<F>>;
+
+#[cfg(not(feature = "emulator"))]
+pub type PoolTransport<F> = CachedTransport<F>;
 
-pub type PoolTransport<F> =
+pub type CachedTransport<F> = Either<Cache<UncachedTransport<F>>, UncachedTransport<F>>;
+
+pub type UncachedTransport<F> =
     ErrorService<Timeout<Either<Retry<RetryPolicy, SharedBalance<F>>, SharedBalance<F>>>>;
 
 #[derive(Debug)]
@@ -69,6 +87,21 @@ pub struct TonClientBuilder<F> {
     retry_percent: f32,
     retry_first_delay: Duration,
     retry_max_delay: Duration,
+    hedge_enabled: bool,
+    hedge_budget_ttl: Duration,
+    hedge_min_per_sec: u32,
+    hedge_percent: f32,
+    hedge_latency_percentile: f64,
+    hedge_min_data_points: u64,
+    hedge_period: Duration,
+    cache_capacity: usize,
+    cache_ttl: Option<Duration>,
+    outlier_enabled: bool,
+    outlier: OutlierConfig,
+    #[cfg(feature = "emulator")]
+    emulator_enabled: bool,
+    #[cfg(feature = "emulator")]
+    emulator_gas_limit: Option<i64>,
 }
 
 impl<F: Default> Default for TonClientBuilder<F> {
@@ -114,6 +147,21 @@ impl<F> TonClientBuilder<F> {
             retry_percent: 0.1,
             retry_first_delay: Duration::from_millis(128),
             retry_max_delay: Duration::from_millis(4096),
+            hedge_enabled: false,
+            hedge_budget_ttl: Duration::from_secs(10),
+            hedge_min_per_sec: 10,
+            hedge_percent: 0.1,
+            hedge_latency_percentile: 0.95,
+            hedge_min_data_points: 100,
+            hedge_period: Duration::from_secs(10),
+            cache_capacity: 0,
+            cache_ttl: None,
+            outlier_enabled: false,
+            outlier: OutlierConfig::default(),
+            #[cfg(feature = "emulator")]
+            emulator_enabled: false,
+            #[cfg(feature = "emulator")]
+            emulator_gas_limit: None,
         }
     }
 
@@ -157,11 +205,81 @@ impl<F> TonClientBuilder<F> {
         self
     }
 
+    pub fn enable_hedge(mut self) -> Self {
+        self.hedge_enabled = true;
+        self
+    }
+
+    pub fn set_hedge_budget_ttl(mut self, budget_ttl: Duration) -> Self {
+        self.hedge_budget_ttl = budget_ttl;
+        self
+    }
+
+    pub fn set_hedge_min_per_sec(mut self, hedge_min_per_sec: u32) -> Self {
+        self.hedge_min_per_sec = hedge_min_per_sec;
+        self
+    }
+
+    pub fn set_hedge_percent(mut self, hedge_percent: f32) -> Self {
+        self.hedge_percent = hedge_percent;
+        self
+    }
+
+    /// A duplicate is sent when a request is slower than this percentile of its request type.
+    pub fn set_hedge_latency_percentile(mut self, latency_percentile: f64) -> Self {
+        self.hedge_latency_percentile = latency_percentile;
+        self
+    }
+
+    pub fn set_hedge_min_data_points(mut self, min_data_points: u64) -> Self {
+        self.hedge_min_data_points = min_data_points;
+        self
+    }
+
+    pub fn set_hedge_period(mut self, period: Duration) -> Self {
+        self.hedge_period = period;
+        self
+    }
+
+    pub fn enable_outlier_detection(mut self) -> Self {
+        self.outlier_enabled = true;
+        self
+    }
+
+    pub fn set_outlier_detection(mut self, outlier: OutlierConfig) -> Self {
+        self.outlier = outlier;
+        self
+    }
+
+    /// Runs get-methods in a local TVM emulator instead of on the lite-servers.
+    #[cfg(feature = "emulator")]
+    pub fn enable_emulator(mut self) -> Self {
+        self.emulator_enabled = true;
+        self
+    }
+
+    #[cfg(feature = "emulator")]
+    pub fn set_emulator_gas_limit(mut self, gas_limit: Option<i64>) -> Self {
+        self.emulator_gas_limit = gas_limit;
+        self
+    }
+
     pub fn set_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
         self
     }
 
+    /// Caches up to `capacity` responses of immutable block-scoped requests, `0` disables the cache.
+    pub fn set_cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache_capacity = capacity;
+        self
+    }
+
+    pub fn set_cache_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
     pub fn build(self) -> anyhow::Result<Client<PoolTransport<F>>>
     where
         F: Service<TonConfig, Response: TonService, Error: Send + Sync, Future: Send + Unpin>
@@ -205,10 +323,15 @@ impl<F> TonClientBuilder<F> {
             CompleteOnResponse::default(),
         );
 
+        let outlier = self.outlier_enabled.then_some(self.outlier);
         let cursor_client_discover = ewma_discover
-            .map_ok(|s| match s {
+            .map_ok(move |s| match s {
                 Change::Insert(k, v) => {
                     let svc = ServiceBuilder::new()
+                        .layer_fn(|svc| match outlier {
+                            Some(config) => OutlierDetection::new(svc, k.to_string(), config),
+                            None => OutlierDetection::disabled(svc),
+                        })
                         .layer_fn(|svc| RoutedClient::new(k.to_string(), svc))
                         .layer_fn(|svc| ConcurrencyMetric::new(svc, k.to_string()))
                         .layer(ConcurrencyLimitLayer::new(256))
@@ -223,8 +346,25 @@ impl<F> TonClientBuilder<F> {
             })
             .boxed();
 
+        let mut balance = Balance::new(cursor_client_discover);
+        if self.hedge_enabled {
+            balance = balance.with_hedge(HedgePolicy::new(
+                TpsBudget::new(
+                    self.hedge_budget_ttl,
+                    self.hedge_min_per_sec,
+                    self.hedge_percent,
+                ),
+                self.hedge_latency_percentile,
+                self.hedge_min_data_points,
+                self.hedge_period,
+            ));
+        }
+
         let svc = ServiceBuilder::new()
-            .layer_fn(Client::new)
+            .option_layer(
+                (self.cache_capacity > 0)
+                    .then(|| CacheLayer::new(self.cache_capacity, self.cache_ttl)),
+            )
             .layer(ErrorLayer)
             .layer(TimeoutLayer::new(self.timeout))
             .option_layer(self.retry_enabled.then(|| {
@@ -238,10 +378,22 @@ impl<F> TonClientBuilder<F> {
                     self.retry_max_delay,
                 ))
             }))
+            .layer(SingleflightLayer::new())
             .layer(SharedLayer)
-            .service(Balance::new(cursor_client_discover));
+            .service(balance);
+
+        #[cfg(feature = "emulator")]
+        let svc = ServiceBuilder::new()
+            .option_layer(
+                self.emulator_enabled
+                    .then(|| match self.emulator_gas_limit {
+                        Some(gas_limit) => EmulatorLayer::new().set_gas_limit(gas_limit),
+                        None => EmulatorLayer::new(),
+                    }),
+            )
+            .service(svc);
 
-        Ok(svc)
+        Ok(Client::new(svc))
     }
 }
 
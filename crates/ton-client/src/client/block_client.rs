@@ -1,10 +1,11 @@
 use crate::{Client, RequestHandler};
 use ton_tower::request::{
-    GetBlockHeader, GetMasterchainInfo, GetShards, GetTransactionIds, GetTransactions,
-    LookUpBlockByLt, LookUpBlockBySeqno, Sync,
+    GetBlockHeader, GetMasterchainInfo, GetShardBlockProof, GetShards, GetTransactionIds,
+    GetTransactions, LookUpBlockByLt, LookUpBlockBySeqno, Sync,
 };
 use ton_tower::response::{
-    BlockHeader, BlockIdExt, BlockTransactions, BlockTransactionsExt, MasterchainInfo, ShortTxId,
+    BlockHeader, BlockIdExt, BlockTransactions, BlockTransactionsExt, MasterchainInfo,
+    ShardBlockProof, ShortTxId,
 };
 use tower::ServiceExt;
 
@@ -80,6 +81,19 @@ where
     }
 }
 
+impl<S> Client<S>
+where
+    S: RequestHandler<GetShardBlockProof>,
+{
+    /// Proves that the shard block `id` was committed by a masterchain block.
+    pub async fn get_shard_block_proof(
+        &mut self,
+        id: BlockIdExt,
+    ) -> anyhow::Result<ShardBlockProof> {
+        self.oneshot(GetShardBlockProof { id }).await
+    }
+}
+
 impl<S> Client<S>
 where
     S: RequestHandler<GetTransactionIds>,
@@ -1,22 +1,32 @@
 use crate::RequestHandler;
 use crate::algo::binary_search::{AccountTxAvailability, BinarySearch};
 use crate::client::Client;
+use crate::hash::normalize_hash;
+use crate::route::shard_prefix::ShardPrefix;
 use anyhow::anyhow;
 use async_stream::try_stream;
 use futures::stream::BoxStream;
 use futures::{Stream, StreamExt, TryStreamExt, stream_select};
 use itertools::Itertools;
 use std::cmp::min;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::{Bound, RangeBounds};
+use std::time::Duration;
+use tokio_retry::Retry;
+use tokio_retry::strategy::{FibonacciBackoff, jitter};
 use ton_address::SmartContractAddress;
 use ton_tower::request::{
     GetAccountState, GetAccountStateByTransaction, GetAccountStateOnBlock, GetAccountTransactions,
-    GetMasterchainInfo, GetShards, GetTransactionIds, GetTransactions, LookUpBlockBySeqno,
+    GetBlockHeader, GetMasterchainInfo, GetShards, GetTransactionIds, GetTransactions,
+    LookUpBlockByLt, LookUpBlockBySeqno, Sync,
 };
 use ton_tower::response::{
     AccountState, BlockIdExt, Shards, ShortTxId, Transaction, TransactionId,
 };
+use ton_tower::service::error::RequestError;
+
+const MASTERCHAIN_ID: i32 = -1;
+const MASTERCHAIN_SHARD: i64 = i64::MIN;
 
 impl<S> Client<S> {
     pub async fn get_shards(&mut self, master_seqno: i32) -> anyhow::Result<Shards>
@@ -29,12 +39,173 @@ impl<S> Client<S> {
         let shards = self.get_shards_by_block_id(block).await?;
         Ok(Shards { shards })
     }
+
+    /// Returns the shard holding `address` in the layout of the masterchain block at `lt`,
+    /// shards split and merge so the layout is taken at the time of the lookup.
+    pub async fn find_account_shard(
+        &mut self,
+        address: &SmartContractAddress,
+        lt: i64,
+    ) -> anyhow::Result<i64>
+    where
+        S: RequestHandler<LookUpBlockByLt> + RequestHandler<GetShards>,
+    {
+        if address.workchain_id() == MASTERCHAIN_ID {
+            return Ok(MASTERCHAIN_SHARD);
+        }
+
+        let masterchain = self
+            .look_up_block_by_lt(MASTERCHAIN_ID, MASTERCHAIN_SHARD, lt)
+            .await?;
+
+        Ok(self
+            .find_account_shard_block(address, masterchain)
+            .await?
+            .shard)
+    }
+
+    /// Returns the block of the shard holding `address` committed by the `masterchain` block.
+    pub async fn find_account_shard_block(
+        &mut self,
+        address: &SmartContractAddress,
+        masterchain: BlockIdExt,
+    ) -> anyhow::Result<BlockIdExt>
+    where
+        S: RequestHandler<GetShards>,
+    {
+        if address.workchain_id() == MASTERCHAIN_ID {
+            return Ok(masterchain);
+        }
+
+        let data = address.data_as_bytes();
+
+        self.get_shards_by_block_id(masterchain)
+            .await?
+            .into_iter()
+            .find(|shard| {
+                shard.workchain == address.workchain_id()
+                    && ShardPrefix::from_shard_id(shard.shard as u64).matches(&data)
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "no shard of workchain {} holds the account",
+                    address.workchain_id()
+                )
+            })
+    }
 }
 
 impl<S> Client<S>
 where
-    S: Clone + Send + Sync + 'static,
+    S: Clone + Send + std::marker::Sync + 'static,
 {
+    /// Streams masterchain blocks starting from `from_seqno` (or the last known block) together
+    /// with every shard block committed by them, waiting for new blocks once the head is reached.
+    pub fn get_masterchain_block_stream_from(
+        &self,
+        from_seqno: Option<i32>,
+    ) -> impl Stream<Item = anyhow::Result<(BlockIdExt, Vec<BlockIdExt>)>> + Send + 'static
+    where
+        S: RequestHandler<Sync>
+            + RequestHandler<LookUpBlockBySeqno>
+            + RequestHandler<GetShards>
+            + RequestHandler<GetBlockHeader>,
+    {
+        let mut client = self.clone();
+
+        try_stream! {
+            let mut last_seqno = client.sync().await?.seqno;
+            let mut next_seqno = from_seqno.unwrap_or(last_seqno);
+            let mut prev_shards: Option<HashSet<BlockIdExt>> = if next_seqno > 1 {
+                Some(client.get_shards(next_seqno - 1).await?.shards.into_iter().collect())
+            } else {
+                None
+            };
+
+            loop {
+                if next_seqno > last_seqno {
+                    last_seqno = client.sync_past(last_seqno).await?.seqno;
+                }
+
+                let block = client
+                    .look_up_block_by_seqno(MASTERCHAIN_ID, MASTERCHAIN_SHARD, next_seqno)
+                    .await?;
+                let shards = client.get_shards_by_block_id(block.clone()).await?;
+
+                let shard_blocks = match prev_shards.as_ref() {
+                    Some(prev) => client.get_shard_blocks_between(prev, &shards).await?,
+                    None => shards.clone(),
+                };
+
+                yield (block, shard_blocks);
+
+                prev_shards = Some(shards.into_iter().collect());
+                next_seqno += 1;
+            }
+        }
+    }
+
+    /// Syncs until the masterchain moves past `seqno`, backing off while no new block is committed.
+    async fn sync_past(&mut self, seqno: i32) -> anyhow::Result<BlockIdExt>
+    where
+        S: RequestHandler<Sync>,
+    {
+        let retry = FibonacciBackoff::from_millis(512)
+            .max_delay(Duration::from_millis(4096))
+            .map(jitter);
+
+        Retry::start(retry, || {
+            let mut client = self.clone();
+
+            async move {
+                let last = client.sync().await?;
+                if last.seqno <= seqno {
+                    return Err(anyhow!(
+                        "masterchain block {} is not committed yet",
+                        seqno + 1
+                    ));
+                }
+
+                Ok(last)
+            }
+        })
+        .await
+    }
+
+    /// Walks back from the current shard tops until the previous ones are reached,
+    /// returning all shard blocks in between ordered by seqno.
+    async fn get_shard_blocks_between(
+        &mut self,
+        prev: &HashSet<BlockIdExt>,
+        current: &[BlockIdExt],
+    ) -> anyhow::Result<Vec<BlockIdExt>>
+    where
+        S: RequestHandler<GetBlockHeader>,
+    {
+        let min_seqno = prev.iter().map(|b| b.seqno).min().unwrap_or_default();
+
+        let mut visited: HashSet<BlockIdExt> = HashSet::new();
+        let mut queue: Vec<BlockIdExt> = current
+            .iter()
+            .filter(|b| !prev.contains(b))
+            .cloned()
+            .collect();
+
+        while let Some(block) = queue.pop() {
+            if prev.contains(&block) || block.seqno <= min_seqno || !visited.insert(block.clone()) {
+                continue;
+            }
+
+            let header = self.get_block_header(block).await?;
+            queue.extend(header.prev_blocks);
+        }
+
+        Ok(visited
+            .into_iter()
+            .sorted_by_key(|b| (b.seqno, b.workchain, b.shard))
+            .collect())
+    }
+
     pub fn get_block_tx_id_stream(
         &self,
         block: &BlockIdExt,
@@ -278,6 +449,135 @@ where
         .try_flatten()
     }
 
+    /// Finds the block of the transaction `tx` of one of `addresses`.
+    /// Returns `None` when none of the accounts has such a transaction.
+    pub async fn find_account_transaction_block(
+        &mut self,
+        addresses: &[SmartContractAddress],
+        tx: &TransactionId,
+    ) -> anyhow::Result<Option<BlockIdExt>>
+    where
+        S: RequestHandler<GetAccountTransactions>
+            + RequestHandler<LookUpBlockByLt>
+            + RequestHandler<GetShards>
+            + RequestHandler<GetBlockHeader>
+            + RequestHandler<GetTransactionIds>,
+    {
+        for address in addresses {
+            // lite-servers reject a transaction id unknown to the account
+            match self.get_transaction(address, tx).await {
+                Ok(Some(_)) => {}
+                Ok(None) => continue,
+                Err(e) if RequestError::is_cause_of(&*e) => continue,
+                Err(e) => return Err(e),
+            }
+
+            let shard = self.find_account_shard(address, tx.lt).await?;
+            let block = self
+                .look_up_block_by_lt(address.workchain_id(), shard, tx.lt)
+                .await?;
+
+            return Ok(Some(block));
+        }
+
+        Ok(None)
+    }
+
+    /// Streams new transactions of the given accounts in block order. When `after` is set to
+    /// a transaction and its block, see [`Self::find_account_transaction_block`],
+    /// the stream resumes right after that transaction.
+    pub fn get_accounts_tx_subscription_stream(
+        &self,
+        addresses: &[SmartContractAddress],
+        after: Option<(TransactionId, BlockIdExt)>,
+    ) -> impl Stream<Item = anyhow::Result<Transaction>> + Send + 'static
+    where
+        S: RequestHandler<Sync>
+            + RequestHandler<LookUpBlockBySeqno>
+            + RequestHandler<GetShards>
+            + RequestHandler<GetBlockHeader>
+            + RequestHandler<GetTransactionIds>
+            + RequestHandler<GetTransactions>,
+    {
+        let mut client = self.clone();
+        let key = |a: &SmartContractAddress| (a.workchain_id(), a.data_as_bytes());
+        let addresses: HashSet<_> = addresses.iter().map(key).collect();
+
+        try_stream! {
+            let (from_seqno, mut skip_until) = match after {
+                Some((tx, block)) => {
+                    // the block is committed by a masterchain block after the one it refers to,
+                    // which is not older than `min_ref_mc_seqno`
+                    let header = client.get_block_header(block.clone()).await?;
+                    let tx = TransactionId {
+                        lt: tx.lt,
+                        hash: normalize_hash(&tx.hash),
+                    };
+
+                    (Some(header.min_ref_mc_seqno), Some((tx, block)))
+                }
+                None => (None, None),
+            };
+
+            let blocks = client.get_masterchain_block_stream_from(from_seqno);
+            for await item in blocks {
+                let (master, shards) = item?;
+
+                for block in std::iter::once(master).chain(shards) {
+                    let is_cursor_block = skip_until.as_ref().is_some_and(|(_, cursor_block)| {
+                        (cursor_block.workchain, cursor_block.shard, cursor_block.seqno)
+                            == (block.workchain, block.shard, block.seqno)
+                    });
+
+                    let accounts = client.get_accounts_in_block_stream(&block);
+                    futures::pin_mut!(accounts);
+
+                    let mut touched = false;
+                    while let Some(account) = accounts.try_next().await? {
+                        if addresses.contains(&key(&account)) {
+                            touched = true;
+                            break;
+                        }
+                    }
+
+                    if touched {
+                        let txs = client.get_block_tx_stream(&block, false);
+                        for await tx in txs {
+                            let tx: Transaction = tx?;
+                            if !addresses.contains(&key(&tx.address)) {
+                                continue;
+                            }
+
+                            if let Some((cursor, _)) = skip_until.as_ref() {
+                                if cursor.lt == tx.transaction_id.lt
+                                    && cursor.hash == normalize_hash(&tx.transaction_id.hash)
+                                {
+                                    skip_until = None;
+                                }
+                                continue;
+                            }
+
+                            yield tx;
+                        }
+                    }
+
+                    // the cursor block is scanned, skipping further would never end
+                    if let Some((cursor, _)) = skip_until.as_ref()
+                        && is_cursor_block
+                    {
+                        Err::<(), _>(anyhow!(
+                            "transaction {} is not found in block {}:{:x}:{}",
+                            cursor.lt,
+                            block.workchain,
+                            block.shard,
+                            block.seqno
+                        ))?;
+                    }
+                }
+            }
+        }
+    }
+
     pub fn get_account_tx_stream(
         &self,
         address: &SmartContractAddress,
@@ -402,6 +702,122 @@ where
         )
     }
 
+    /// Streams the account transactions within `range` from old to new. The range is walked
+    /// forward in masterchain block windows, so only one window is buffered at a time.
+    pub fn get_account_tx_range_asc(
+        &self,
+        address: &SmartContractAddress,
+        range: (Bound<TransactionId>, Bound<TransactionId>),
+    ) -> impl Stream<Item = anyhow::Result<Transaction>> + Send + 'static
+    where
+        S: RequestHandler<GetMasterchainInfo>
+            + RequestHandler<LookUpBlockBySeqno>
+            + RequestHandler<LookUpBlockByLt>
+            + RequestHandler<GetShards>
+            + RequestHandler<GetAccountState>
+            + RequestHandler<GetAccountStateOnBlock>
+            + RequestHandler<GetAccountTransactions>,
+    {
+        const MIN_STEP: i32 = 1;
+        const MAX_STEP: i32 = 28800;
+        const TARGET_CHUNK: usize = 256;
+
+        let mut client = self.clone();
+        let address = address.to_owned();
+
+        try_stream! {
+            let last_tx = match range.start_bound().cloned() {
+                Bound::Included(tx) | Bound::Excluded(tx) => tx,
+                Bound::Unbounded => {
+                    let state: AccountState = client.get_account_state(&address).await?;
+                    let Some(tx) = state.last_transaction_id else {
+                        return;
+                    };
+
+                    tx
+                }
+            };
+            let first_tx = match range.end_bound().cloned() {
+                Bound::Included(tx) | Bound::Excluded(tx) => tx,
+                Bound::Unbounded => {
+                    AccountTxAvailability::new(&mut client, &address)
+                        .find()
+                        .await?
+                }
+            };
+
+            // windows are measured in masterchain seqnos, the account shard may split or merge
+            // along the range so it is resolved again at the end of every window
+            let last_seqno = client
+                .look_up_block_by_lt(MASTERCHAIN_ID, MASTERCHAIN_SHARD, last_tx.lt)
+                .await?
+                .seqno;
+            let first_seqno = client
+                .look_up_block_by_lt(MASTERCHAIN_ID, MASTERCHAIN_SHARD, first_tx.lt)
+                .await?
+                .seqno;
+
+            let mut lower = match range.end_bound().cloned() {
+                Bound::Unbounded => Bound::Included(first_tx),
+                bound => bound,
+            };
+            let mut seqno = first_seqno;
+            let mut step = 1024;
+
+            loop {
+                let next_seqno = seqno.saturating_add(step);
+
+                let window_end = if next_seqno < last_seqno {
+                    let masterchain = client
+                        .look_up_block_by_seqno(MASTERCHAIN_ID, MASTERCHAIN_SHARD, next_seqno)
+                        .await?;
+                    let block = client.find_account_shard_block(&address, masterchain).await?;
+                    let state = client.get_account_state_on_block(&address, block).await?;
+                    let tx = state
+                        .last_transaction_id
+                        .ok_or_else(|| anyhow!("invalid last tx"))?;
+
+                    Some(tx).filter(|tx| tx.lt < last_tx.lt)
+                } else {
+                    None
+                };
+                let done = window_end.is_none();
+
+                let upper = match window_end {
+                    Some(tx) => Bound::Included(tx),
+                    None => match range.start_bound().cloned() {
+                        Bound::Unbounded => Bound::Included(last_tx.clone()),
+                        bound => bound,
+                    },
+                };
+
+                let chunk: Vec<Transaction> = client
+                    .get_account_tx_range(&address, (upper, lower.clone()))
+                    .try_collect()
+                    .await?;
+
+                if let Some(newest) = chunk.first() {
+                    lower = Bound::Excluded(newest.transaction_id.clone());
+                }
+
+                if chunk.len() > TARGET_CHUNK {
+                    step = (step / 2).max(MIN_STEP);
+                } else if chunk.len() < TARGET_CHUNK / 4 {
+                    step = (step * 2).min(MAX_STEP);
+                }
+
+                for tx in chunk.into_iter().rev() {
+                    yield tx;
+                }
+
+                if done {
+                    break;
+                }
+                seqno = next_seqno;
+            }
+        }
+    }
+
     pub fn get_account_tx_range(
         &self,
         address: &SmartContractAddress,
@@ -450,3 +866,203 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::{Ready, ready};
+    use std::task::{Context, Poll};
+    use ton_tower::response::{MasterchainInfo, Transactions};
+    use tower::Service;
+
+    const WORKCHAIN: i32 = 0;
+    const ROOT_SHARD: i64 = i64::MIN;
+    const LEFT_SHARD: i64 = 0x4000_0000_0000_0000;
+    const RIGHT_SHARD: i64 = 0xC000_0000_0000_0000_u64 as i64;
+    const LTS_PER_BLOCK: i64 = 100;
+    const BLOCKS: i32 = 3000;
+    const SPLIT_AT: i32 = 500;
+    const TX_BATCH: usize = 4;
+
+    #[tokio::test]
+    async fn should_stream_transactions_ascending_across_shard_split() {
+        let chain = SplitChain {
+            account: SmartContractAddress::raw(WORKCHAIN, [0x11; 32]),
+            tx_blocks: vec![1, 2, 400, 499, 500, 501, 1200, 2600, 2999],
+        };
+        let expected = chain.transaction_ids();
+        let client = Client::new(chain.clone());
+
+        let txs: Vec<TransactionId> = client
+            .get_account_tx_range_asc(&chain.account, (Bound::Unbounded, Bound::Unbounded))
+            .map_ok(|tx| tx.transaction_id)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(txs, expected);
+    }
+
+    /// The account holds one transaction in each of `tx_blocks`. Workchain blocks share the
+    /// seqno and the lts of their masterchain block, the root shard splits at `SPLIT_AT`.
+    #[derive(Clone)]
+    struct SplitChain {
+        account: SmartContractAddress,
+        tx_blocks: Vec<i32>,
+    }
+
+    impl SplitChain {
+        fn block_id(workchain: i32, shard: i64, seqno: i32) -> BlockIdExt {
+            BlockIdExt {
+                workchain,
+                shard,
+                seqno,
+                root_hash: format!("root{workchain}:{shard:x}:{seqno}"),
+                file_hash: format!("file{workchain}:{shard:x}:{seqno}"),
+            }
+        }
+
+        fn shards(seqno: i32) -> Vec<i64> {
+            if seqno < SPLIT_AT {
+                vec![ROOT_SHARD]
+            } else {
+                vec![LEFT_SHARD, RIGHT_SHARD]
+            }
+        }
+
+        fn look_up(chain: i32, shard: i64, seqno: i32) -> anyhow::Result<BlockIdExt> {
+            let known = (1..=BLOCKS).contains(&seqno)
+                && match chain {
+                    MASTERCHAIN_ID => shard == MASTERCHAIN_SHARD,
+                    WORKCHAIN => Self::shards(seqno).contains(&shard),
+                    _ => false,
+                };
+            if !known {
+                return Err(anyhow!("block {chain}:{shard:x}:{seqno} not found"));
+            }
+
+            Ok(Self::block_id(chain, shard, seqno))
+        }
+
+        fn transaction_ids(&self) -> Vec<TransactionId> {
+            self.tx_blocks
+                .iter()
+                .map(|seqno| {
+                    let lt = i64::from(*seqno) * LTS_PER_BLOCK + 10;
+
+                    TransactionId {
+                        lt,
+                        hash: format!("tx{lt}"),
+                    }
+                })
+                .collect()
+        }
+
+        /// Answers on masterchain blocks as a lite-server does, a workchain block must be the
+        /// one of the account shard.
+        fn state_on(&self, block_id: BlockIdExt) -> anyhow::Result<AccountState> {
+            let shard = if block_id.seqno < SPLIT_AT {
+                ROOT_SHARD
+            } else {
+                LEFT_SHARD
+            };
+            if block_id.workchain == WORKCHAIN && block_id.shard != shard {
+                return Err(anyhow!("account is not in shard {:x}", block_id.shard));
+            }
+
+            let end_lt = i64::from(block_id.seqno + 1) * LTS_PER_BLOCK;
+
+            Ok(AccountState {
+                balance: Some(0),
+                code: String::new(),
+                data: String::new(),
+                frozen_hash: String::new(),
+                last_transaction_id: self
+                    .transaction_ids()
+                    .into_iter()
+                    .rfind(|tx| tx.lt < end_lt),
+                block_id,
+                sync_utime: 0,
+            })
+        }
+    }
+
+    macro_rules! impl_split_chain_service {
+        ($req:ty, $resp:ty, $call:expr) => {
+            impl Service<$req> for SplitChain {
+                type Response = $resp;
+                type Error = anyhow::Error;
+                type Future = Ready<anyhow::Result<$resp>>;
+
+                fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                    Poll::Ready(Ok(()))
+                }
+
+                fn call(&mut self, req: $req) -> Self::Future {
+                    let call: fn(&SplitChain, $req) -> anyhow::Result<$resp> = $call;
+
+                    ready(call(self, req))
+                }
+            }
+        };
+    }
+
+    impl_split_chain_service!(GetMasterchainInfo, MasterchainInfo, |_, _| {
+        Ok(MasterchainInfo {
+            last: SplitChain::block_id(MASTERCHAIN_ID, MASTERCHAIN_SHARD, BLOCKS),
+            state_root_hash: String::new(),
+            init: SplitChain::block_id(MASTERCHAIN_ID, MASTERCHAIN_SHARD, 1),
+        })
+    });
+
+    impl_split_chain_service!(LookUpBlockBySeqno, BlockIdExt, |_, req| {
+        SplitChain::look_up(req.chain, req.shard, req.seqno)
+    });
+
+    impl_split_chain_service!(LookUpBlockByLt, BlockIdExt, |_, req| {
+        SplitChain::look_up(req.chain, req.shard, (req.lt / LTS_PER_BLOCK) as i32)
+    });
+
+    impl_split_chain_service!(GetShards, Vec<BlockIdExt>, |_, req| {
+        Ok(SplitChain::shards(req.block_id.seqno)
+            .into_iter()
+            .map(|shard| SplitChain::block_id(WORKCHAIN, shard, req.block_id.seqno))
+            .collect())
+    });
+
+    impl_split_chain_service!(GetAccountState, AccountState, |chain, _| {
+        chain.state_on(SplitChain::block_id(WORKCHAIN, LEFT_SHARD, BLOCKS))
+    });
+
+    impl_split_chain_service!(GetAccountStateOnBlock, AccountState, |chain, req| {
+        chain.state_on(req.block_id)
+    });
+
+    impl_split_chain_service!(GetAccountTransactions, Transactions, |chain, req| {
+        let mut older = chain
+            .transaction_ids()
+            .into_iter()
+            .rev()
+            .skip_while(|tx| tx.lt > req.from.lt);
+        let transactions = older
+            .by_ref()
+            .take(TX_BATCH)
+            .map(|transaction_id| Transaction {
+                address: chain.account.clone(),
+                utime: 0,
+                data: String::new(),
+                transaction_id,
+                fee: 0,
+                storage_fee: 0,
+                other_fee: 0,
+                in_msg: None,
+                out_msgs: vec![],
+            })
+            .collect();
+
+        Ok(Transactions {
+            transactions,
+            previous_transaction_id: older.next(),
+        })
+    });
+}
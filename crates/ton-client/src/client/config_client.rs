@@ -0,0 +1,28 @@
+use crate::{Client, RequestHandler};
+use ton_tower::request::{GetConfigAll, GetConfigParam};
+use ton_tower::response::{BlockIdExt, Cell};
+use tower::ServiceExt;
+
+impl<S> Client<S>
+where
+    S: RequestHandler<GetConfigAll>,
+{
+    pub async fn get_config_all(&mut self, block_id: &BlockIdExt) -> anyhow::Result<Cell> {
+        let block_id = block_id.clone();
+        self.oneshot(GetConfigAll { block_id }).await
+    }
+}
+
+impl<S> Client<S>
+where
+    S: RequestHandler<GetConfigParam>,
+{
+    pub async fn get_config_param(
+        &mut self,
+        block_id: &BlockIdExt,
+        param: i32,
+    ) -> anyhow::Result<Cell> {
+        let block_id = block_id.clone();
+        self.oneshot(GetConfigParam { block_id, param }).await
+    }
+}
@@ -0,0 +1,282 @@
+use crate::Client;
+use crate::RequestHandler;
+use crate::client::message_client::parse_external_message;
+use anyhow::{anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as base64_standard;
+use futures::try_join;
+use num_bigint::BigUint;
+use serde::Deserialize;
+use ton_emulator::TransactionEmulator;
+use ton_tlb::transaction::Transaction;
+use ton_tlb::transaction_descr::{TrComputePhase, TransactionDescr};
+use ton_tower::request::{GetConfigAll, GetMasterchainInfo, GetShardAccountCellOnBlock};
+use ton_tower::response::{BlockIdExt, Cell};
+use ton_tower::service::error::RequestError;
+use toner::tlb::BoC;
+use tower::ServiceExt;
+
+/// Fees of the transaction that an external message would produce, in nanotons.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeEstimate {
+    /// `false` when the account rejects the message, the fees are zero then
+    /// and `compute_exit_code` tells why.
+    pub accepted: bool,
+    pub storage_fee: i64,
+    pub gas_fee: i64,
+    pub fwd_fee: i64,
+    /// `total_fees` of the transaction, includes the import fee of the message.
+    pub total_fee: i64,
+    /// `None` when the compute phase is skipped.
+    pub compute_exit_code: Option<i32>,
+    /// `None` when there is no action phase.
+    pub action_result_code: Option<i32>,
+}
+
+impl<S> Client<S>
+where
+    S: RequestHandler<GetMasterchainInfo>
+        + RequestHandler<GetShardAccountCellOnBlock>
+        + RequestHandler<GetConfigAll>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    /// Emulates an inbound external message on top of the last masterchain block.
+    /// Signature checks are disabled, so the message may be left unsigned.
+    pub async fn estimate_fee<M>(&mut self, message: M) -> anyhow::Result<FeeEstimate>
+    where
+        M: ToString,
+    {
+        let body = message.to_string();
+        let (address, _) = parse_external_message(&body).map_err(RequestError)?;
+
+        let block_id = self.get_masterchain_info().await?.last;
+        let (shard_account, config) = try_join!(
+            self.clone().oneshot(GetShardAccountCellOnBlock {
+                address,
+                block_id: block_id.clone(),
+            }),
+            self.clone().oneshot(GetConfigAll {
+                block_id: block_id.clone(),
+            }),
+        )?;
+
+        tokio::task::spawn_blocking(move || emulate_fee(&block_id, &shard_account, &config, &body))
+            .await?
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EmulationResult {
+    success: bool,
+    error: Option<String>,
+    transaction: Option<String>,
+    vm_exit_code: Option<i32>,
+}
+
+fn emulate_fee(
+    block_id: &BlockIdExt,
+    shard_account: &Cell,
+    config: &Cell,
+    message: &str,
+) -> anyhow::Result<FeeEstimate> {
+    let emulator = TransactionEmulator::new(&config.bytes, 0)?;
+    emulator.set_ignore_chksig(true);
+    // Same as for get-methods: the seed only has to be deterministic per block.
+    emulator.set_rand_seed(&hex::encode(base64_standard.decode(&block_id.root_hash)?))?;
+
+    let response = emulator.emulate(&shard_account.bytes, message)?;
+    let response: EmulationResult = serde_json::from_str(response.as_ref())?;
+    let transaction = match response {
+        EmulationResult {
+            success: true,
+            transaction: Some(transaction),
+            ..
+        } => transaction,
+        EmulationResult {
+            vm_exit_code: Some(exit_code),
+            ..
+        } => {
+            return Ok(FeeEstimate {
+                accepted: false,
+                storage_fee: 0,
+                gas_fee: 0,
+                fwd_fee: 0,
+                total_fee: 0,
+                compute_exit_code: Some(exit_code),
+                action_result_code: None,
+            });
+        }
+        EmulationResult { error, .. } => {
+            return Err(anyhow!(
+                error.unwrap_or_else(|| "ambiguous response".to_owned())
+            ));
+        }
+    };
+
+    let boc = BoC::parse_base64(&transaction).map_err(|e| anyhow!("transaction: {e}"))?;
+    let root = boc
+        .single_root()
+        .ok_or_else(|| anyhow!("transaction: single root expected"))?;
+    let transaction: Transaction = root
+        .parse_fully(())
+        .map_err(|e| anyhow!("failed to parse transaction: {e}"))?;
+
+    let TransactionDescr::Ordinary {
+        storage_ph,
+        compute_ph,
+        action,
+        ..
+    } = transaction.description
+    else {
+        bail!("ordinary transaction expected");
+    };
+
+    let (gas_fee, compute_exit_code) = match compute_ph {
+        TrComputePhase::Skipped { .. } => (0, None),
+        TrComputePhase::Vm {
+            gas_fees,
+            exit_code,
+            ..
+        } => (to_nanotons(&gas_fees)?, Some(exit_code)),
+    };
+
+    Ok(FeeEstimate {
+        accepted: true,
+        storage_fee: storage_ph
+            .map(|phase| to_nanotons(&phase.storage_fees_collected))
+            .transpose()?
+            .unwrap_or_default(),
+        gas_fee,
+        fwd_fee: action
+            .as_ref()
+            .and_then(|phase| phase.total_fwd_fees.as_ref())
+            .map(to_nanotons)
+            .transpose()?
+            .unwrap_or_default(),
+        total_fee: to_nanotons(&transaction.total_fees.grams)?,
+        compute_exit_code,
+        action_result_code: action.map(|phase| phase.result_code),
+    })
+}
+
+fn to_nanotons(value: &BigUint) -> anyhow::Result<i64> {
+    i64::try_from(value).map_err(|e| anyhow!("fee is out of range: {e}"))
+}
+
+#[cfg(test)]
+mod integration {
+    use super::*;
+    use std::str::FromStr;
+    use testcontainers_ton::LocalLiteServer;
+    use ton_address::SmartContractAddress;
+    use ton_liteserver_client::{LiteServerAdapter, LiteServerClient};
+    use ton_tower::request::GetAccountState;
+    use toner::tlb::bits::ser::BitWriterExt;
+    use toner::tlb::{BagOfCellsArgs, Cell as TlbCell, Ref};
+
+    const FAUCET_WALLET_ADDR: &str =
+        "-1:22f53b7d9aba2cef44755f7078b01614cd4dde2388a1729c2c386cf8f9898afe";
+
+    #[tokio::test]
+    async fn should_estimate_fee_of_wallet_message() -> anyhow::Result<()> {
+        let server = LocalLiteServer::shared().await?;
+        let inner = LiteServerClient::connect(server.addr(), server.server_key()).await?;
+        let mut client = Client::new(LiteServerAdapter::new(inner));
+        let address = SmartContractAddress::from_str(FAUCET_WALLET_ADDR)?;
+        let message = given_wallet_message(&mut client, &address).await?;
+
+        let fee = client.estimate_fee(message).await?;
+
+        assert!(fee.accepted);
+        assert_eq!(fee.compute_exit_code, Some(0));
+        assert_eq!(fee.action_result_code, Some(0));
+        assert!(fee.gas_fee > 0);
+        assert!(fee.total_fee >= fee.gas_fee);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_exit_code_of_rejected_message() -> anyhow::Result<()> {
+        let server = LocalLiteServer::shared().await?;
+        let inner = LiteServerClient::connect(server.addr(), server.server_key()).await?;
+        let mut client = Client::new(LiteServerAdapter::new(inner));
+        let address = SmartContractAddress::from_str(FAUCET_WALLET_ADDR)?;
+        let message = external_message(&address, TlbCell::builder().into_cell())?;
+
+        let fee = client.estimate_fee(message).await?;
+
+        assert!(!fee.accepted);
+        assert!(fee.compute_exit_code.is_some_and(|code| code != 0));
+        assert_eq!(fee.total_fee, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_invalid_message() -> anyhow::Result<()> {
+        let server = LocalLiteServer::shared().await?;
+        let inner = LiteServerClient::connect(server.addr(), server.server_key()).await?;
+        let mut client = Client::new(LiteServerAdapter::new(inner));
+
+        let error = client.estimate_fee("invalid_boc").await.unwrap_err();
+
+        assert!(RequestError::is_cause_of(&*error));
+        Ok(())
+    }
+
+    /// An unsigned message of the wallet sending nothing, signatures are not checked.
+    async fn given_wallet_message<S>(
+        client: &mut Client<S>,
+        address: &SmartContractAddress,
+    ) -> anyhow::Result<String>
+    where
+        S: RequestHandler<GetAccountState>,
+    {
+        let state = client.get_account_state(address).await?;
+        let boc = BoC::parse_base64(&state.data).map_err(|e| anyhow!("data: {e}"))?;
+        let data = boc
+            .single_root()
+            .ok_or_else(|| anyhow!("data: single root expected"))?;
+        // seqno:uint32 subwallet_id:uint32 public_key:bits256
+        let raw = data.data.as_raw_slice();
+        let seqno = u32::from_be_bytes(raw[0..4].try_into()?);
+        let subwallet_id = u32::from_be_bytes(raw[4..8].try_into()?);
+
+        let mut body = TlbCell::builder();
+        body.pack([0u8; 32], ())?;
+        body.pack([0u8; 32], ())?;
+        body.pack(subwallet_id, ())?;
+        body.pack(u32::MAX, ())?;
+        body.pack(seqno, ())?;
+        // simple send of wallet v4
+        body.pack(0u8, ())?;
+
+        external_message(address, body.into_cell())
+    }
+
+    fn external_message(address: &SmartContractAddress, body: TlbCell) -> anyhow::Result<String> {
+        let mut message = TlbCell::builder();
+        // ext_in_msg_info$10 src:addr_none$00 dest:(addr_std$10 anycast:nothing$0)
+        for bit in [true, false, false, false, true, false, false] {
+            message.pack(bit, ())?;
+        }
+        message.pack(address.workchain_id() as u8, ())?;
+        message.pack(address.data_as_bytes(), ())?;
+        // import_fee:(VarUInteger 16) of zero, init:nothing$0 body:right$1
+        for bit in [false, false, false, false, false, true] {
+            message.pack(bit, ())?;
+        }
+        message.store_as::<_, Ref>(&body, ())?;
+
+        let bytes = BoC::from_root(message.into_cell())
+            .serialize(BagOfCellsArgs {
+                has_crc32c: true,
+                ..BagOfCellsArgs::default()
+            })
+            .map_err(|e| anyhow!("BoC serialize failed: {e}"))?;
+
+        Ok(base64_standard.encode(bytes))
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{Client, RequestHandler};
+use anyhow::anyhow;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as base64_standard;
+use std::collections::BTreeSet;
+use ton_tower::request::GetLibraries;
+use ton_tower::response::{AccountState, Library};
+use ton_tower::service::error::RequestError;
+use toner::tlb::BoC;
+use tower::ServiceExt;
+
+// library cell: 8-bit cell type followed by the 256-bit hash of the library root
+const LIBRARY_CELL_TYPE: u8 = 2;
+const LIBRARY_CELL_BITS: usize = 8 + 256;
+const LIBRARY_HASH_BYTES: usize = 32;
+
+impl<S> Client<S>
+where
+    S: RequestHandler<GetLibraries>,
+{
+    pub async fn get_libraries(&mut self, hashes: Vec<String>) -> anyhow::Result<Vec<Library>> {
+        if let Some(hash) = hashes.iter().find(|hash| !is_library_hash(hash)) {
+            return Err(RequestError(anyhow!(
+                "library hash {hash} must be base64 of {LIBRARY_HASH_BYTES} bytes"
+            ))
+            .into());
+        }
+
+        self.oneshot(GetLibraries { hashes }).await
+    }
+
+    /// Resolves the library cells referenced from the account code.
+    pub async fn get_account_libraries(
+        &mut self,
+        state: &AccountState,
+    ) -> anyhow::Result<Vec<Library>> {
+        if state.code.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let hashes = find_library_hashes(&state.code)?;
+        if hashes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        self.get_libraries(hashes).await
+    }
+}
+
+fn is_library_hash(hash: &str) -> bool {
+    base64_standard
+        .decode(hash)
+        .is_ok_and(|bytes| bytes.len() == LIBRARY_HASH_BYTES)
+}
+
+/// Returns base64 hashes of every library cell in the base64 encoded `code` BoC.
+pub fn find_library_hashes(code: &str) -> anyhow::Result<Vec<String>> {
+    let boc = BoC::parse_base64(code).map_err(|e| anyhow!("code: invalid BoC: {e}"))?;
+    let root = boc
+        .single_root()
+        .ok_or_else(|| anyhow!("code: BoC must have exactly one root cell"))?;
+
+    let mut hashes = BTreeSet::new();
+    let mut stack = vec![root];
+    while let Some(cell) = stack.pop() {
+        if cell.is_exotic && cell.data.len() == LIBRARY_CELL_BITS {
+            let bytes = cell.data.as_raw_slice();
+            if bytes[0] == LIBRARY_CELL_TYPE {
+                hashes.insert(base64_standard.encode(&bytes[1..]));
+            }
+        }
+
+        stack.extend(cell.references.iter());
+    }
+
+    Ok(hashes.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use toner::tlb::bits::bitvec::order::Msb0;
+    use toner::tlb::bits::bitvec::vec::BitVec;
+    use toner::tlb::{BagOfCellsArgs, Cell};
+
+    fn serialize(root: Cell) -> String {
+        let bytes = BoC::from_root(root)
+            .serialize(BagOfCellsArgs::default())
+            .unwrap();
+
+        base64_standard.encode(bytes)
+    }
+
+    #[test]
+    fn should_find_library_hashes() {
+        let mut data = BitVec::<u8, Msb0>::from_slice(&[LIBRARY_CELL_TYPE]);
+        data.extend_from_raw_slice(&[7; 32]);
+        let library = Arc::new(Cell {
+            is_exotic: true,
+            data,
+            references: vec![],
+        });
+        let code = Cell {
+            is_exotic: false,
+            data: BitVec::from_slice(&[0xff]),
+            references: vec![library.clone(), library],
+        };
+
+        let hashes = find_library_hashes(&serialize(code)).unwrap();
+
+        assert_eq!(hashes, vec![base64_standard.encode([7; 32])]);
+    }
+
+    #[test]
+    fn should_accept_only_base64_hashes_of_32_bytes() {
+        assert!(is_library_hash(&base64_standard.encode([7; 32])));
+        assert!(!is_library_hash(&base64_standard.encode([7; 31])));
+        assert!(!is_library_hash("invalid"));
+    }
+
+    #[test]
+    fn should_find_no_library_hashes_in_ordinary_code() {
+        let code = Cell {
+            is_exotic: false,
+            data: BitVec::from_slice(&[0xff]),
+            references: vec![],
+        };
+
+        let hashes = find_library_hashes(&serialize(code)).unwrap();
+
+        assert!(hashes.is_empty());
+    }
+}
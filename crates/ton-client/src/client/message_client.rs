@@ -1,8 +1,22 @@
 use crate::Client;
 use crate::RequestHandler;
-use ton_tower::request::{SendMessage, SendMessageReturningHash};
+use anyhow::{Context, anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as base64_standard;
+use futures::TryStreamExt;
+use std::time::Duration;
+use ton_address::SmartContractAddress;
+use ton_tower::request::{
+    GetAccountState, GetAccountTransactions, SendMessage, SendMessageReturningHash,
+};
+use ton_tower::response::Transaction;
+use ton_tower::service::error::RequestError;
+use toner::tlb::BoC;
+use toner::ton::message::{CommonMsgInfo, Message};
 use tower::ServiceExt;
 
+const SEND_MESSAGE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 impl<S> Client<S>
 where
     S: RequestHandler<SendMessage>,
@@ -28,3 +42,267 @@ where
         self.oneshot(SendMessageReturningHash { body }).await
     }
 }
+
+impl<S> Client<S>
+where
+    S: RequestHandler<SendMessage>
+        + RequestHandler<GetAccountState>
+        + RequestHandler<GetAccountTransactions>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    /// Sends an external message and waits for the transaction of the destination account
+    /// that consumes it. Fails with [`tokio::time::error::Elapsed`] in the error chain
+    /// if no such transaction appears within `timeout`.
+    pub async fn send_message_and_wait<M>(
+        &mut self,
+        message: M,
+        timeout: Duration,
+    ) -> anyhow::Result<Transaction>
+    where
+        M: ToString,
+    {
+        let body = message.to_string();
+        let (address, hash) = parse_external_message(&body).map_err(RequestError)?;
+
+        let last_tx = self.get_account_state(&address).await?.last_transaction_id;
+        self.send_message(body).await?;
+
+        let wait = async {
+            loop {
+                let txs = self.get_account_tx_stream_from(&address, None);
+                futures::pin_mut!(txs);
+
+                while let Some(tx) = txs.try_next().await? {
+                    if last_tx
+                        .as_ref()
+                        .is_some_and(|last| tx.transaction_id.lt <= last.lt)
+                    {
+                        break;
+                    }
+                    if tx.in_msg.as_ref().is_some_and(|msg| msg.hash == hash) {
+                        return anyhow::Ok(tx);
+                    }
+                }
+
+                tokio::time::sleep(SEND_MESSAGE_POLL_INTERVAL).await;
+            }
+        };
+
+        tokio::time::timeout(timeout, wait)
+            .await
+            .with_context(|| format!("message {hash} was not processed within {timeout:?}"))?
+    }
+}
+
+pub(crate) fn parse_external_message(body: &str) -> anyhow::Result<(SmartContractAddress, String)> {
+    let boc = BoC::parse_base64(body).map_err(|e| anyhow!("message: invalid BoC: {e}"))?;
+    let root = boc
+        .single_root()
+        .ok_or_else(|| anyhow!("message: BoC must have exactly one root cell"))?;
+    let message: Message = root
+        .parse_fully(())
+        .map_err(|e| anyhow!("failed to parse message: {e}"))?;
+
+    let CommonMsgInfo::ExternalIn(info) = message.info else {
+        bail!("message is not an inbound external message");
+    };
+    let address = SmartContractAddress::raw(info.dst.workchain_id, info.dst.address);
+
+    Ok((address, base64_standard.encode(root.hash())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::{Ready, ready};
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+    use ton_tower::response::{
+        AccountState, BlockIdExt, Message as InMessage, MessageData, TransactionId, Transactions,
+    };
+    use toner::tlb::bits::ser::BitWriterExt;
+    use toner::tlb::{BagOfCellsArgs, Cell as TlbCell, Ref};
+    use tower::Service;
+
+    const WALLET_ADDR: &str = "-1:22f53b7d9aba2cef44755f7078b01614cd4dde2388a1729c2c386cf8f9898afe";
+
+    #[tokio::test]
+    async fn should_wait_for_transaction_of_sent_message() {
+        let address: SmartContractAddress = WALLET_ADDR.parse().unwrap();
+        let wallet = Wallet::new(&address);
+        let mut client = Client::new(wallet.clone());
+        let message = external_message(&address);
+        let (_, hash) = parse_external_message(&message).unwrap();
+
+        let tx = client
+            .send_message_and_wait(message, Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(tx.address, address);
+        assert_eq!(tx.in_msg.unwrap().hash, hash);
+        assert_eq!(tx.transaction_id, wallet.last_transaction_id());
+    }
+
+    #[tokio::test]
+    async fn should_reject_invalid_message() {
+        let address: SmartContractAddress = WALLET_ADDR.parse().unwrap();
+        let mut client = Client::new(Wallet::new(&address));
+
+        let error = client
+            .send_message_and_wait("invalid_boc", Duration::from_secs(5))
+            .await
+            .unwrap_err();
+
+        assert!(RequestError::is_cause_of(&*error));
+    }
+
+    fn external_message(address: &SmartContractAddress) -> String {
+        let mut message = TlbCell::builder();
+        // ext_in_msg_info$10 src:addr_none$00 dest:(addr_std$10 anycast:nothing$0)
+        for bit in [true, false, false, false, true, false, false] {
+            message.pack(bit, ()).unwrap();
+        }
+        message.pack(address.workchain_id() as u8, ()).unwrap();
+        message.pack(address.data_as_bytes(), ()).unwrap();
+        // import_fee:(VarUInteger 16) of zero, init:nothing$0 body:right$1
+        for bit in [false, false, false, false, false, true] {
+            message.pack(bit, ()).unwrap();
+        }
+        message
+            .store_as::<_, Ref>(&TlbCell::builder().into_cell(), ())
+            .unwrap();
+
+        let bytes = BoC::from_root(message.into_cell())
+            .serialize(BagOfCellsArgs {
+                has_crc32c: true,
+                ..BagOfCellsArgs::default()
+            })
+            .unwrap();
+
+        base64_standard.encode(bytes)
+    }
+
+    /// Single account which processes every sent message in a new transaction,
+    /// transactions are kept from the oldest to the newest.
+    #[derive(Clone)]
+    struct Wallet {
+        address: SmartContractAddress,
+        transactions: Arc<Mutex<Vec<Transaction>>>,
+    }
+
+    impl Wallet {
+        fn new(address: &SmartContractAddress) -> Self {
+            let wallet = Self {
+                address: address.clone(),
+                transactions: Arc::default(),
+            };
+            wallet.push(None);
+
+            wallet
+        }
+
+        fn push(&self, in_msg: Option<InMessage>) {
+            let mut transactions = self.transactions.lock().unwrap();
+            let lt = (transactions.len() as i64 + 1) * 10;
+
+            transactions.push(Transaction {
+                address: self.address.clone(),
+                utime: 0,
+                data: String::new(),
+                transaction_id: TransactionId {
+                    lt,
+                    hash: format!("tx{lt}"),
+                },
+                fee: 0,
+                storage_fee: 0,
+                other_fee: 0,
+                in_msg,
+                out_msgs: vec![],
+            });
+        }
+
+        fn last_transaction_id(&self) -> TransactionId {
+            let transactions = self.transactions.lock().unwrap();
+
+            transactions.last().unwrap().transaction_id.clone()
+        }
+    }
+
+    macro_rules! impl_wallet_service {
+        ($req:ty, $resp:ty, $call:expr) => {
+            impl Service<$req> for Wallet {
+                type Response = $resp;
+                type Error = anyhow::Error;
+                type Future = Ready<anyhow::Result<$resp>>;
+
+                fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                    Poll::Ready(Ok(()))
+                }
+
+                fn call(&mut self, req: $req) -> Self::Future {
+                    let call: fn(&Wallet, $req) -> anyhow::Result<$resp> = $call;
+
+                    ready(call(self, req))
+                }
+            }
+        };
+    }
+
+    impl_wallet_service!(SendMessage, (), |wallet, req| {
+        let (address, hash) = parse_external_message(&req.body)?;
+        wallet.push(Some(InMessage {
+            hash,
+            source: None,
+            destination: Some(address),
+            value: 0,
+            fwd_fee: 0,
+            ihr_fee: 0,
+            created_lt: 0,
+            body_hash: String::new(),
+            msg_data: MessageData::Raw {
+                body: String::new(),
+                init_state: String::new(),
+            },
+            decoded: None,
+        }));
+
+        Ok(())
+    });
+
+    impl_wallet_service!(GetAccountState, AccountState, |wallet, _| {
+        Ok(AccountState {
+            balance: Some(0),
+            code: String::new(),
+            data: String::new(),
+            frozen_hash: String::new(),
+            last_transaction_id: Some(wallet.last_transaction_id()),
+            block_id: BlockIdExt {
+                workchain: -1,
+                shard: i64::MIN,
+                seqno: 1,
+                root_hash: String::new(),
+                file_hash: String::new(),
+            },
+            sync_utime: 0,
+        })
+    });
+
+    impl_wallet_service!(GetAccountTransactions, Transactions, |wallet, req| {
+        let transactions = wallet.transactions.lock().unwrap();
+        let position = transactions
+            .iter()
+            .position(|tx| tx.address == req.address && tx.transaction_id == req.from)
+            .ok_or_else(|| anyhow!("transaction {} not found", req.from.lt))?;
+
+        Ok(Transactions {
+            transactions: vec![transactions[position].clone()],
+            previous_transaction_id: position
+                .checked_sub(1)
+                .map(|prev| transactions[prev].transaction_id.clone()),
+        })
+    });
+}
@@ -7,8 +7,14 @@ use tower::load::Load;
 pub mod account_client;
 pub mod block_client;
 pub mod client_ext;
+pub mod config_client;
+#[cfg(feature = "emulator")]
+pub mod fee_client;
+pub mod library_client;
 pub mod message_client;
 pub mod smc_client;
+pub mod snapshot;
+pub mod trace_client;
 
 #[derive(Debug, Clone)]
 pub struct Client<S> {
@@ -1,8 +1,9 @@
-use crate::Client;
-use crate::RequestHandler;
+use crate::pool::Forward;
+use crate::route::{BlockCriteria, Route};
+use crate::{Client, ForwardHandler, RequestHandler};
 use ton_address::SmartContractAddress;
-use ton_tower::request::RunGetMethod;
-use ton_tower::response::{SmcRunResult, StackEntry};
+use ton_tower::request::{RunGetMethod, RunGetMethodOnBlock};
+use ton_tower::response::{BlockIdExt, SmcRunResult, StackEntry};
 use tower::ServiceExt;
 
 impl<S> Client<S>
@@ -25,3 +26,59 @@ where
         .await
     }
 }
+
+impl<S> Client<S>
+where
+    S: ForwardHandler<RunGetMethod>,
+{
+    pub async fn run_get_method_at_least_block(
+        &mut self,
+        address: &SmartContractAddress,
+        method: &str,
+        stack: Vec<StackEntry>,
+        block_id: &BlockIdExt,
+    ) -> anyhow::Result<SmcRunResult> {
+        let address = address.clone();
+        let method = method.to_string();
+        let route = Route::Block {
+            chain: block_id.workchain,
+            criteria: BlockCriteria::Seqno {
+                shard: block_id.shard,
+                seqno: block_id.seqno,
+            },
+        };
+        self.oneshot(Forward::new(
+            route,
+            RunGetMethod {
+                address,
+                method,
+                stack,
+            },
+        ))
+        .await
+    }
+}
+
+impl<S> Client<S>
+where
+    S: RequestHandler<RunGetMethodOnBlock>,
+{
+    pub async fn run_get_method_on_block(
+        &mut self,
+        address: &SmartContractAddress,
+        method: &str,
+        stack: Vec<StackEntry>,
+        block_id: &BlockIdExt,
+    ) -> anyhow::Result<SmcRunResult> {
+        let address = address.clone();
+        let method = method.to_string();
+        let block_id = block_id.clone();
+        self.oneshot(RunGetMethodOnBlock {
+            address,
+            block_id,
+            method,
+            stack,
+        })
+        .await
+    }
+}
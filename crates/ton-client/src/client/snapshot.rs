@@ -0,0 +1,243 @@
+use crate::{Client, RequestHandler};
+use std::future::{Ready, ready};
+use std::task::{Context, Poll};
+use ton_tower::request::*;
+use ton_tower::response::{BlockIdExt, MasterchainInfo};
+use tower::Service;
+
+/// Runs every request against one masterchain block, see [`Client::snapshot`].
+#[derive(Debug, Clone)]
+pub struct Snapshot<S> {
+    inner: S,
+    masterchain_info: MasterchainInfo,
+}
+
+impl<S> Snapshot<S> {
+    pub fn new(inner: S, masterchain_info: MasterchainInfo) -> Self {
+        Self {
+            inner,
+            masterchain_info,
+        }
+    }
+
+    pub fn block_id(&self) -> &BlockIdExt {
+        &self.masterchain_info.last
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S> Client<S>
+where
+    S: RequestHandler<GetMasterchainInfo> + Clone,
+{
+    /// Resolves the last masterchain block once and returns a client pinned to it.
+    ///
+    /// Requests for the latest state, e.g. account states and get-methods, are run on the
+    /// resolved block, so results of several calls are consistent with each other.
+    pub async fn snapshot(&mut self) -> anyhow::Result<Client<Snapshot<S>>> {
+        let masterchain_info = self.get_masterchain_info().await?;
+
+        Ok(Client::new(Snapshot::new(
+            self.get_ref().clone(),
+            masterchain_info,
+        )))
+    }
+}
+
+impl<S> Service<GetMasterchainInfo> for Snapshot<S> {
+    type Response = MasterchainInfo;
+    type Error = anyhow::Error;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _: GetMasterchainInfo) -> Self::Future {
+        ready(Ok(self.masterchain_info.clone()))
+    }
+}
+
+impl<S> Service<Sync> for Snapshot<S> {
+    type Response = BlockIdExt;
+    type Error = anyhow::Error;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _: Sync) -> Self::Future {
+        ready(Ok(self.masterchain_info.last.clone()))
+    }
+}
+
+macro_rules! pin_service {
+    ($($req:ty => $pinned:ty: |$r:ident, $block_id:ident| $into:expr),* $(,)?) => {
+        $(
+            impl<S> Service<$req> for Snapshot<S>
+            where
+                S: Service<$pinned>,
+            {
+                type Response = <S as Service<$pinned>>::Response;
+                type Error = <S as Service<$pinned>>::Error;
+                type Future = <S as Service<$pinned>>::Future;
+
+                fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                    self.inner.poll_ready(cx)
+                }
+
+                fn call(&mut self, $r: $req) -> Self::Future {
+                    let $block_id = self.masterchain_info.last.clone();
+
+                    self.inner.call($into)
+                }
+            }
+        )*
+    };
+}
+
+pin_service!(
+    GetAccountState => GetAccountStateOnBlock: |req, block_id| GetAccountStateOnBlock {
+        address: req.address,
+        block_id,
+    },
+    GetShardAccountCell => GetShardAccountCellOnBlock: |req, block_id| GetShardAccountCellOnBlock {
+        address: req.address,
+        block_id,
+    },
+    RunGetMethod => RunGetMethodOnBlock: |req, block_id| RunGetMethodOnBlock {
+        address: req.address,
+        block_id,
+        method: req.method,
+        stack: req.stack,
+    },
+);
+
+macro_rules! forward_service {
+    ($($req:ty),* $(,)?) => {
+        $(
+            impl<S> Service<$req> for Snapshot<S>
+            where
+                S: Service<$req>,
+            {
+                type Response = <S as Service<$req>>::Response;
+                type Error = <S as Service<$req>>::Error;
+                type Future = <S as Service<$req>>::Future;
+
+                fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                    self.inner.poll_ready(cx)
+                }
+
+                fn call(&mut self, req: $req) -> Self::Future {
+                    self.inner.call(req)
+                }
+            }
+        )*
+    };
+}
+
+forward_service!(
+    LookUpBlockBySeqno,
+    LookUpBlockByLt,
+    GetShards,
+    GetBlockHeader,
+    GetTransactionIds,
+    GetTransactions,
+    GetAccountStateOnBlock,
+    GetAccountStateByTransaction,
+    GetAccountTransactions,
+    GetShardAccountCellOnBlock,
+    GetShardAccountCellByTransaction,
+    RunGetMethodOnBlock,
+    GetConfigAll,
+    GetConfigParam,
+    GetLibraries,
+    GetShardBlockProof,
+    SendMessage,
+    SendMessageReturningHash,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::mock;
+    use std::str::FromStr;
+    use ton_address::SmartContractAddress;
+    use ton_tower::response::AccountState;
+
+    #[tokio::test]
+    async fn should_pin_latest_account_state_to_snapshot_block() {
+        let mut inner = MockService::new();
+        inner.expect_poll_ready().returning(|_| Poll::Ready(Ok(())));
+        inner
+            .expect_call()
+            .withf(|req| req.block_id == given_masterchain_info().last)
+            .times(1)
+            .returning(|req| ready(Ok(given_account_state(req.block_id))));
+        let mut client = Client::new(Snapshot::new(inner, given_masterchain_info()));
+        let address =
+            SmartContractAddress::from_str("EQCjk1hh952vWaE9bRguFkAhDAL5jj3xj9p0uPWrFBq_GEMS")
+                .unwrap();
+
+        let state = client.get_account_state(&address).await.unwrap();
+
+        assert_eq!(state.block_id, given_masterchain_info().last);
+    }
+
+    #[tokio::test]
+    async fn should_answer_masterchain_info_with_snapshot_block() {
+        let mut inner = MockService::new();
+        inner.expect_call().never();
+        let mut client = Client::new(Snapshot::new(inner, given_masterchain_info()));
+
+        let info = client.get_masterchain_info().await.unwrap();
+
+        assert_eq!(info.last, given_masterchain_info().last);
+        assert_eq!(client.get_ref().block_id(), &info.last);
+    }
+
+    fn given_masterchain_info() -> MasterchainInfo {
+        let block_id = BlockIdExt {
+            workchain: -1,
+            shard: i64::MIN,
+            seqno: 42,
+            root_hash: "root_hash".to_string(),
+            file_hash: "file_hash".to_string(),
+        };
+
+        MasterchainInfo {
+            last: block_id.clone(),
+            state_root_hash: "state_root_hash".to_string(),
+            init: block_id,
+        }
+    }
+
+    fn given_account_state(block_id: BlockIdExt) -> AccountState {
+        AccountState {
+            balance: None,
+            code: String::new(),
+            data: String::new(),
+            frozen_hash: String::new(),
+            last_transaction_id: None,
+            block_id,
+            sync_utime: 0,
+        }
+    }
+
+    mock! {
+        Service {}
+
+        impl Service<GetAccountStateOnBlock> for Service {
+            type Response = AccountState;
+            type Error = anyhow::Error;
+            type Future = Ready<Result<AccountState, anyhow::Error>>;
+
+            fn poll_ready<'a>(&mut self, _cx: &mut Context<'a>) -> Poll<Result<(), anyhow::Error>>;
+            fn call(&mut self, _req: GetAccountStateOnBlock) -> Ready<Result<AccountState, anyhow::Error>>;
+        }
+    }
+}
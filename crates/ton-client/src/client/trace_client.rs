@@ -0,0 +1,434 @@
+use crate::RequestHandler;
+use crate::client::Client;
+use anyhow::anyhow;
+use futures::TryStreamExt;
+use std::collections::VecDeque;
+use ton_address::SmartContractAddress;
+use ton_tower::request::{
+    GetAccountTransactions, GetBlockHeader, GetMasterchainInfo, GetShards, GetTransactionIds,
+    LookUpBlockByLt,
+};
+use ton_tower::response::{Message, Transaction, TransactionId};
+
+/// Upper bound on the number of transactions collected into one trace.
+const MAX_TRACE_TRANSACTIONS: usize = 1024;
+/// How many consecutive destination shard blocks are scanned for a message.
+const MAX_TRACE_BLOCKS_PER_MESSAGE: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceNode {
+    pub transaction: Transaction,
+    pub children: Vec<TraceNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    pub root: TraceNode,
+    /// `false` when some internal message could not be matched to a destination
+    /// transaction, either because it is not processed yet or a search limit was hit.
+    pub is_complete: bool,
+}
+
+impl<S> Client<S>
+where
+    S: RequestHandler<GetMasterchainInfo>
+        + RequestHandler<LookUpBlockByLt>
+        + RequestHandler<GetBlockHeader>
+        + RequestHandler<GetShards>
+        + RequestHandler<GetTransactionIds>
+        + RequestHandler<GetAccountTransactions>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    /// Builds the message tree spawned by the given transaction: every internal out message
+    /// is followed to the transaction it caused on the destination account.
+    pub async fn get_trace(
+        &mut self,
+        address: &SmartContractAddress,
+        transaction_id: &TransactionId,
+    ) -> anyhow::Result<Trace> {
+        let root = self
+            .get_transaction(address, transaction_id)
+            .await?
+            .ok_or_else(|| anyhow!("transaction {} not found", transaction_id.lt))?;
+
+        let mut transactions = vec![root];
+        let mut children: Vec<Vec<usize>> = vec![vec![]];
+        let mut queue = VecDeque::from([0]);
+        let mut is_complete = true;
+
+        while let Some(parent) = queue.pop_front() {
+            let out_msgs = transactions[parent].out_msgs.clone();
+            for msg in out_msgs.iter().filter(|msg| msg.destination.is_some()) {
+                if transactions.len() >= MAX_TRACE_TRANSACTIONS {
+                    is_complete = false;
+                    break;
+                }
+
+                let Some(tx) = self.find_message_transaction(msg).await? else {
+                    is_complete = false;
+                    continue;
+                };
+
+                transactions.push(tx);
+                children.push(vec![]);
+                children[parent].push(transactions.len() - 1);
+                queue.push_back(transactions.len() - 1);
+            }
+        }
+
+        let mut nodes: Vec<Option<Transaction>> = transactions.into_iter().map(Some).collect();
+
+        Ok(Trace {
+            root: build_trace_node(0, &mut nodes, &children),
+            is_complete,
+        })
+    }
+
+    pub async fn get_transaction(
+        &mut self,
+        address: &SmartContractAddress,
+        transaction_id: &TransactionId,
+    ) -> anyhow::Result<Option<Transaction>> {
+        let txs = self.get_transactions(address, transaction_id).await?;
+
+        Ok(txs
+            .transactions
+            .into_iter()
+            .find(|tx| &tx.transaction_id == transaction_id))
+    }
+
+    /// Looks for the transaction caused by an internal message. The search starts at the
+    /// destination shard block that covers `created_lt` and walks forward block by block.
+    async fn find_message_transaction(
+        &mut self,
+        msg: &Message,
+    ) -> anyhow::Result<Option<Transaction>> {
+        let Some(destination) = msg.destination.as_ref() else {
+            return Ok(None);
+        };
+        let key = (destination.workchain_id(), destination.data_as_bytes());
+
+        let mut lt = msg.created_lt;
+        for _ in 0..MAX_TRACE_BLOCKS_PER_MESSAGE {
+            // The next block may not exist yet: the message is simply not processed.
+            let block = match self.find_account_shard(destination, lt).await {
+                Ok(shard) => self.look_up_block_by_lt(key.0, shard, lt).await,
+                Err(e) => Err(e),
+            };
+            let block = match block {
+                Ok(block) => block,
+                Err(_) if self.is_past_last_block(lt).await? => return Ok(None),
+                Err(e) => return Err(e),
+            };
+
+            let ids = self.get_block_tx_id_stream(&block, false);
+            futures::pin_mut!(ids);
+            while let Some(id) = ids.try_next().await? {
+                if (id.account.workchain_id(), id.account.data_as_bytes()) != key
+                    || id.lt <= msg.created_lt
+                {
+                    continue;
+                }
+
+                let tx_id = TransactionId {
+                    lt: id.lt,
+                    hash: id.hash,
+                };
+                let Some(tx) = self.get_transaction(destination, &tx_id).await? else {
+                    continue;
+                };
+                if tx.in_msg.as_ref().is_some_and(|m| m.hash == msg.hash) {
+                    return Ok(Some(tx));
+                }
+            }
+
+            lt = self.get_block_header(block).await?.end_lt + 1;
+        }
+
+        Ok(None)
+    }
+
+    /// Whether `lt` is beyond the last masterchain block, so no block covers it yet.
+    async fn is_past_last_block(&mut self, lt: i64) -> anyhow::Result<bool> {
+        let last = self.get_masterchain_info().await?.last;
+
+        Ok(self.get_block_header(last).await?.end_lt < lt)
+    }
+}
+
+fn build_trace_node(
+    index: usize,
+    nodes: &mut [Option<Transaction>],
+    children: &[Vec<usize>],
+) -> TraceNode {
+    TraceNode {
+        transaction: nodes[index].take().expect("each node is visited once"),
+        children: children[index]
+            .iter()
+            .map(|&child| build_trace_node(child, nodes, children))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::{Ready, ready};
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+    use ton_tower::response::{
+        BlockHeader, BlockIdExt, BlockTransactions, MasterchainInfo, MessageData, ShortTxId,
+        Transactions,
+    };
+    use tower::Service;
+
+    const MASTERCHAIN_ID: i32 = -1;
+    const MASTERCHAIN_SHARD: i64 = i64::MIN;
+    const WORKCHAIN: i32 = 0;
+    const SHARD: i64 = i64::MIN;
+    const LTS_PER_BLOCK: i64 = 100;
+
+    #[tokio::test]
+    async fn should_follow_messages_across_blocks() {
+        let (a, b, c) = (account(0x11), account(0x22), account(0x33));
+        let m1 = message("m1", &b, 111);
+        let m2 = message("m2", &c, 211);
+        let mut client = Client::new(Chain::new(vec![
+            vec![transaction(&a, 110, None, vec![m1.clone()])],
+            vec![
+                transaction(&b, 210, Some(m1), vec![m2.clone()]),
+                transaction(&c, 220, Some(m2), vec![]),
+            ],
+        ]));
+
+        let trace = client.get_trace(&a, &tx_id(110)).await.unwrap();
+
+        assert!(trace.is_complete);
+        assert_eq!(trace.root.transaction.transaction_id, tx_id(110));
+        let [child] = &trace.root.children[..] else {
+            panic!("one child expected");
+        };
+        assert_eq!(child.transaction.transaction_id, tx_id(210));
+        let [grandchild] = &child.children[..] else {
+            panic!("one grandchild expected");
+        };
+        assert_eq!(grandchild.transaction.transaction_id, tx_id(220));
+        assert!(grandchild.children.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_destination_shard_is_unknown() {
+        let a = account(0x11);
+        let b = SmartContractAddress::raw(WORKCHAIN + 1, [0x22; 32]);
+        let mut client = Client::new(Chain::new(vec![
+            vec![transaction(&a, 110, None, vec![message("m1", &b, 111)])],
+            vec![],
+        ]));
+
+        let result = client.get_trace(&a, &tx_id(110)).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_mark_trace_incomplete_when_message_is_not_processed() {
+        let (a, b) = (account(0x11), account(0x22));
+        let mut client = Client::new(Chain::new(vec![
+            vec![transaction(&a, 110, None, vec![message("m1", &b, 111)])],
+            vec![],
+        ]));
+
+        let trace = client.get_trace(&a, &tx_id(110)).await.unwrap();
+
+        assert!(!trace.is_complete);
+        assert!(trace.root.children.is_empty());
+    }
+
+    fn account(byte: u8) -> SmartContractAddress {
+        SmartContractAddress::raw(WORKCHAIN, [byte; 32])
+    }
+
+    fn tx_id(lt: i64) -> TransactionId {
+        TransactionId {
+            lt,
+            hash: format!("tx{lt}"),
+        }
+    }
+
+    fn message(hash: &str, destination: &SmartContractAddress, created_lt: i64) -> Message {
+        Message {
+            hash: hash.to_string(),
+            source: None,
+            destination: Some(destination.clone()),
+            value: 0,
+            fwd_fee: 0,
+            ihr_fee: 0,
+            created_lt,
+            body_hash: String::new(),
+            msg_data: MessageData::Raw {
+                body: String::new(),
+                init_state: String::new(),
+            },
+            decoded: None,
+        }
+    }
+
+    fn transaction(
+        address: &SmartContractAddress,
+        lt: i64,
+        in_msg: Option<Message>,
+        out_msgs: Vec<Message>,
+    ) -> Transaction {
+        Transaction {
+            address: address.clone(),
+            utime: 0,
+            data: String::new(),
+            transaction_id: tx_id(lt),
+            fee: 0,
+            storage_fee: 0,
+            other_fee: 0,
+            in_msg,
+            out_msgs,
+        }
+    }
+
+    /// Consecutive blocks of a single shard, block `seqno` spans the lts
+    /// `seqno * LTS_PER_BLOCK..(seqno + 1) * LTS_PER_BLOCK`.
+    #[derive(Clone)]
+    struct Chain {
+        blocks: Arc<Vec<Vec<Transaction>>>,
+    }
+
+    impl Chain {
+        fn new(blocks: Vec<Vec<Transaction>>) -> Self {
+            Self {
+                blocks: Arc::new(blocks),
+            }
+        }
+
+        fn block_id(workchain: i32, shard: i64, seqno: i32) -> BlockIdExt {
+            BlockIdExt {
+                workchain,
+                shard,
+                seqno,
+                root_hash: format!("root{workchain}:{seqno}"),
+                file_hash: format!("file{workchain}:{seqno}"),
+            }
+        }
+
+        fn seqno_at(&self, lt: i64) -> anyhow::Result<i32> {
+            let seqno = lt / LTS_PER_BLOCK;
+            if !(1..=self.blocks.len() as i64).contains(&seqno) {
+                return Err(anyhow!("block at lt {lt} not found"));
+            }
+
+            Ok(seqno as i32)
+        }
+
+        fn transactions(&self, block: &BlockIdExt) -> &[Transaction] {
+            &self.blocks[block.seqno as usize - 1]
+        }
+    }
+
+    macro_rules! impl_chain_service {
+        ($req:ty, $resp:ty, $call:expr) => {
+            impl Service<$req> for Chain {
+                type Response = $resp;
+                type Error = anyhow::Error;
+                type Future = Ready<anyhow::Result<$resp>>;
+
+                fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                    Poll::Ready(Ok(()))
+                }
+
+                fn call(&mut self, req: $req) -> Self::Future {
+                    let call: fn(&Chain, $req) -> anyhow::Result<$resp> = $call;
+
+                    ready(call(self, req))
+                }
+            }
+        };
+    }
+
+    impl_chain_service!(LookUpBlockByLt, BlockIdExt, |chain, req| {
+        let seqno = chain.seqno_at(req.lt)?;
+        match (req.chain, req.shard) {
+            (MASTERCHAIN_ID, MASTERCHAIN_SHARD) => {
+                Ok(Chain::block_id(MASTERCHAIN_ID, MASTERCHAIN_SHARD, seqno))
+            }
+            (WORKCHAIN, SHARD) => Ok(Chain::block_id(WORKCHAIN, SHARD, seqno)),
+            (workchain, shard) => Err(anyhow!("unknown shard {workchain}:{shard:x}")),
+        }
+    });
+
+    impl_chain_service!(GetMasterchainInfo, MasterchainInfo, |chain, _| {
+        let last = chain.blocks.len() as i32;
+
+        Ok(MasterchainInfo {
+            last: Chain::block_id(MASTERCHAIN_ID, MASTERCHAIN_SHARD, last),
+            state_root_hash: String::new(),
+            init: Chain::block_id(MASTERCHAIN_ID, MASTERCHAIN_SHARD, 1),
+        })
+    });
+
+    impl_chain_service!(GetShards, Vec<BlockIdExt>, |_, req| {
+        Ok(vec![Chain::block_id(WORKCHAIN, SHARD, req.block_id.seqno)])
+    });
+
+    impl_chain_service!(GetBlockHeader, BlockHeader, |_, req| {
+        let start_lt = i64::from(req.id.seqno) * LTS_PER_BLOCK;
+
+        Ok(BlockHeader {
+            id: req.id,
+            global_id: 0,
+            version: 0,
+            flags: 0,
+            after_merge: false,
+            after_split: false,
+            before_split: false,
+            want_merge: false,
+            want_split: false,
+            validator_list_hash_short: 0,
+            catchain_seqno: 0,
+            min_ref_mc_seqno: 0,
+            is_key_block: false,
+            prev_key_block_seqno: 0,
+            start_lt,
+            end_lt: start_lt + LTS_PER_BLOCK - 1,
+            gen_utime: 0,
+            vert_seqno: 0,
+            prev_blocks: vec![],
+        })
+    });
+
+    impl_chain_service!(GetTransactionIds, BlockTransactions, |chain, req| {
+        Ok(BlockTransactions {
+            incomplete: false,
+            transactions: chain
+                .transactions(&req.block)
+                .iter()
+                .map(|tx| ShortTxId {
+                    account: tx.address.clone(),
+                    lt: tx.transaction_id.lt,
+                    hash: tx.transaction_id.hash.clone(),
+                })
+                .collect(),
+        })
+    });
+
+    impl_chain_service!(GetAccountTransactions, Transactions, |chain, req| {
+        Ok(Transactions {
+            transactions: chain
+                .blocks
+                .iter()
+                .flatten()
+                .filter(|tx| tx.address == req.address && tx.transaction_id == req.from)
+                .cloned()
+                .collect(),
+            previous_transaction_id: None,
+        })
+    });
+}
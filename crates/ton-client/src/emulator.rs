@@ -0,0 +1,479 @@
+use crate::RequestHandler;
+use crate::client::library_client::find_library_hashes;
+use crate::pool::Forward;
+use anyhow::{anyhow, bail, ensure};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as base64_standard;
+use futures::future::BoxFuture;
+use futures::{FutureExt, try_join};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use ton_emulator::TvmEmulator;
+use ton_tlb::account::Account;
+use ton_tlb::account_state::AccountState;
+use ton_tlb::shard_account::ShardAccount;
+use ton_tlb::stack::{decode_result_stack, encode_input_stack, method_id_from_name};
+use ton_tower::request::*;
+use ton_tower::response::{BlockIdExt, Cell as TonCell, Library, SmcRunResult};
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::vec::BitVec;
+use toner::tlb::bits::bitvec::view::BitView;
+use toner::tlb::hashmap::Hashmap;
+use toner::tlb::{BagOfCellsArgs, BoC, Cell, Ref};
+use tower::{Layer, Service, ServiceExt};
+
+/// Serves [`RunGetMethod`] by running the account code in a local TVM emulator.
+///
+/// Only the shard account cell, the block header, the blockchain config and the libraries
+/// referenced from the code are requested from the inner service, so heavy get-methods are not
+/// bound by the lite-server gas limits.
+/// Every other request is passed through untouched.
+#[derive(Debug, Clone, Default)]
+pub struct EmulatorLayer {
+    gas_limit: Option<i64>,
+}
+
+impl EmulatorLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_gas_limit(mut self, gas_limit: i64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+}
+
+impl<S> Layer<S> for EmulatorLayer {
+    type Service = EmulatorService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        EmulatorService {
+            inner,
+            gas_limit: self.gas_limit,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EmulatorService<S> {
+    inner: S,
+    gas_limit: Option<i64>,
+}
+
+impl<S> EmulatorService<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            gas_limit: None,
+        }
+    }
+}
+
+impl<S> Service<RunGetMethod> for EmulatorService<S>
+where
+    S: RequestHandler<GetMasterchainInfo>
+        + RequestHandler<GetShardAccountCellOnBlock>
+        + RequestHandler<GetBlockHeader>
+        + RequestHandler<GetConfigAll>
+        + RequestHandler<GetLibraries>
+        + Clone
+        + 'static,
+{
+    type Response = SmcRunResult;
+    type Error = anyhow::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        <S as Service<GetMasterchainInfo>>::poll_ready(&mut self.inner, cx)
+    }
+
+    fn call(&mut self, req: RunGetMethod) -> Self::Future {
+        let info = self.inner.call(GetMasterchainInfo::default());
+        let inner = self.inner.clone();
+        let gas_limit = self.gas_limit;
+
+        async move {
+            let block_id = info.await?.last;
+            let (shard_account, header, config) = try_join!(
+                inner.clone().oneshot(GetShardAccountCellOnBlock {
+                    address: req.address.clone(),
+                    block_id: block_id.clone(),
+                }),
+                inner.clone().oneshot(GetBlockHeader {
+                    id: block_id.clone(),
+                }),
+                inner.clone().oneshot(GetConfigAll {
+                    block_id: block_id.clone(),
+                }),
+            )?;
+            let account = ActiveAccount::from_shard_account(&req, &shard_account)?;
+            let hashes = find_library_hashes(&account.code)?;
+            let libraries = if hashes.is_empty() {
+                vec![]
+            } else {
+                inner.oneshot(GetLibraries { hashes }).await?
+            };
+
+            tokio::task::spawn_blocking(move || {
+                emulate_get_method(
+                    req,
+                    &block_id,
+                    header.gen_utime,
+                    &account,
+                    &config,
+                    &libraries,
+                    gas_limit,
+                )
+            })
+            .await?
+        }
+        .boxed()
+    }
+}
+
+macro_rules! forward_service {
+    ($($req:ty),* $(,)?) => {
+        $(
+            impl<S> Service<$req> for EmulatorService<S>
+            where
+                S: Service<$req>,
+            {
+                type Response = <S as Service<$req>>::Response;
+                type Error = <S as Service<$req>>::Error;
+                type Future = <S as Service<$req>>::Future;
+
+                fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                    self.inner.poll_ready(cx)
+                }
+
+                fn call(&mut self, req: $req) -> Self::Future {
+                    self.inner.call(req)
+                }
+            }
+
+            impl<S> Service<Forward<$req>> for EmulatorService<S>
+            where
+                S: Service<Forward<$req>>,
+            {
+                type Response = <S as Service<Forward<$req>>>::Response;
+                type Error = <S as Service<Forward<$req>>>::Error;
+                type Future = <S as Service<Forward<$req>>>::Future;
+
+                fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                    self.inner.poll_ready(cx)
+                }
+
+                fn call(&mut self, req: Forward<$req>) -> Self::Future {
+                    self.inner.call(req)
+                }
+            }
+        )*
+    };
+}
+
+forward_service!(
+    GetMasterchainInfo,
+    Sync,
+    LookUpBlockBySeqno,
+    LookUpBlockByLt,
+    GetShards,
+    GetBlockHeader,
+    GetTransactionIds,
+    GetTransactions,
+    GetAccountState,
+    GetAccountStateOnBlock,
+    GetAccountStateByTransaction,
+    GetAccountTransactions,
+    GetShardAccountCell,
+    GetShardAccountCellOnBlock,
+    GetShardAccountCellByTransaction,
+    RunGetMethodOnBlock,
+    GetConfigAll,
+    GetConfigParam,
+    GetLibraries,
+    GetShardBlockProof,
+    SendMessage,
+    SendMessageReturningHash,
+);
+
+// `RunGetMethod` is emulated, but routed calls (e.g. `run_get_method_at_least_block`) are not.
+impl<S> Service<Forward<RunGetMethod>> for EmulatorService<S>
+where
+    S: Service<Forward<RunGetMethod>>,
+{
+    type Response = <S as Service<Forward<RunGetMethod>>>::Response;
+    type Error = <S as Service<Forward<RunGetMethod>>>::Error;
+    type Future = <S as Service<Forward<RunGetMethod>>>::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Forward<RunGetMethod>) -> Self::Future {
+        self.inner.call(req)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TvmResult<T> {
+    success: bool,
+    error: Option<String>,
+    #[serde(flatten)]
+    data: Option<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TvmRunGetMethodResult {
+    vm_exit_code: i32,
+    gas_used: String,
+    stack: String,
+    missing_library: Option<String>,
+}
+
+/// Code, data and balance of an active account, with code and data as base64 BoCs.
+#[derive(Debug)]
+struct ActiveAccount {
+    code: String,
+    data: String,
+    balance: u64,
+}
+
+impl ActiveAccount {
+    fn from_shard_account(req: &RunGetMethod, cell: &TonCell) -> anyhow::Result<Self> {
+        let boc = BoC::parse_base64(&cell.bytes)
+            .map_err(|e| anyhow!("shard account: invalid BoC: {e}"))?;
+        let root = boc
+            .single_root()
+            .ok_or_else(|| anyhow!("shard account: single root expected"))?;
+        let shard_account: ShardAccount = root.parse_fully(())?;
+
+        let Account::Account { storage, .. } = shard_account.account else {
+            bail!("account {} does not exist", req.address);
+        };
+        let AccountState::Active { state_init } = storage.state else {
+            bail!("account {} is not active", req.address);
+        };
+        let code = state_init
+            .code
+            .ok_or_else(|| anyhow!("account {} has no code", req.address))?;
+        let data = state_init
+            .data
+            .unwrap_or_else(|| Arc::new(Cell::builder().into_cell()));
+
+        Ok(Self {
+            code: encode_cell_boc(code)?,
+            data: encode_cell_boc(data)?,
+            balance: u64::try_from(&storage.balance.grams)?,
+        })
+    }
+}
+
+fn encode_cell_boc(cell: Arc<Cell>) -> anyhow::Result<String> {
+    let bytes = BoC::from_root(cell)
+        .serialize(BagOfCellsArgs {
+            has_crc32c: true,
+            ..BagOfCellsArgs::default()
+        })
+        .map_err(|e| anyhow!("BoC serialize failed: {e}"))?;
+
+    Ok(base64_standard.encode(bytes))
+}
+
+/// Packs libraries into the `Hashmap 256 ^Cell` BoC expected by `TvmEmulator::set_libraries`.
+/// Returns `None` when there is nothing to set.
+fn libraries_boc(libraries: &[Library]) -> anyhow::Result<Option<String>> {
+    if libraries.is_empty() {
+        return Ok(None);
+    }
+
+    let dict = libraries
+        .iter()
+        .map(|library| {
+            let hash = base64_standard.decode(&library.hash)?;
+            ensure!(
+                hash.len() == 32,
+                "library hash must be 32 bytes, got {}",
+                hash.len()
+            );
+            let boc = BoC::parse_base64(&library.cell.bytes)
+                .map_err(|e| anyhow!("library {}: invalid BoC: {e}", library.hash))?;
+            let root = boc
+                .single_root()
+                .ok_or_else(|| anyhow!("library {}: single root expected", library.hash))?;
+
+            Ok((hash.view_bits::<Msb0>().to_bitvec(), Arc::clone(root)))
+        })
+        .collect::<anyhow::Result<HashMap<BitVec<u8, Msb0>, Arc<Cell>>>>()?;
+
+    let mut builder = Cell::builder();
+    builder
+        .store_as::<_, &Hashmap<Ref>>(&dict, (256, ()))
+        .map_err(|e| anyhow!("libraries dictionary: {e}"))?;
+
+    encode_cell_boc(Arc::new(builder.into_cell())).map(Some)
+}
+
+fn emulate_get_method(
+    req: RunGetMethod,
+    block_id: &BlockIdExt,
+    gen_utime: i64,
+    account: &ActiveAccount,
+    config: &TonCell,
+    libraries: &[Library],
+    gas_limit: Option<i64>,
+) -> anyhow::Result<SmcRunResult> {
+    let emulator = TvmEmulator::new(&account.code, &account.data, 0)?;
+
+    // The rand seed only has to be deterministic per block, so take the block root hash.
+    let rand_seed = hex::encode(base64_standard.decode(&block_id.root_hash)?);
+    let c7_applied = emulator.set_c7(
+        &req.address.to_raw().to_string(),
+        u32::try_from(gen_utime)?,
+        account.balance,
+        &rand_seed,
+        &config.bytes,
+    )?;
+    if !c7_applied {
+        bail!("emulator rejected c7 for block {}", block_id.seqno);
+    }
+    if let Some(gas_limit) = gas_limit
+        && !emulator.set_gas_limit(gas_limit)
+    {
+        bail!("emulator rejected gas limit {gas_limit}");
+    }
+    if let Some(libs) = libraries_boc(libraries)?
+        && !emulator.set_libraries(&libs)?
+    {
+        bail!("emulator rejected libraries");
+    }
+
+    let method_id = i32::try_from(method_id_from_name(&req.method))?;
+    let stack = base64_standard.encode(encode_input_stack(req.stack)?);
+    let response = emulator.run_get_method(method_id, &stack)?;
+
+    let response: TvmResult<TvmRunGetMethodResult> = serde_json::from_str(response.as_str())?;
+    let result = match response {
+        TvmResult {
+            success: true,
+            data: Some(data),
+            ..
+        } => data,
+        TvmResult { error, .. } => {
+            return Err(anyhow!(
+                error.unwrap_or_else(|| "ambiguous response".to_owned())
+            ));
+        }
+    };
+    if let Some(library) = result.missing_library {
+        bail!("library {} is missing", library);
+    }
+
+    Ok(SmcRunResult {
+        gas_used: result.gas_used.parse()?,
+        exit_code: result.vm_exit_code,
+        stack: decode_result_stack(&base64_standard.decode(&result.stack)?)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_not_pack_empty_libraries() {
+        assert_eq!(libraries_boc(&[]).unwrap(), None);
+    }
+
+    #[test]
+    fn should_pack_libraries_by_hash() {
+        let mut builder = Cell::builder();
+        builder
+            .store_as::<_, Ref>(&Cell::builder().into_cell(), ())
+            .unwrap();
+        let library = Arc::new(builder.into_cell());
+        let hash = library.hash();
+
+        let boc = libraries_boc(&[Library {
+            hash: base64_standard.encode(hash),
+            cell: TonCell {
+                bytes: encode_cell_boc(Arc::clone(&library)).unwrap(),
+            },
+        }])
+        .unwrap()
+        .unwrap();
+
+        let boc = BoC::parse_base64(&boc).unwrap();
+        let dict: HashMap<BitVec<u8, Msb0>, Arc<Cell>> = boc
+            .single_root()
+            .unwrap()
+            .parse_fully_as::<_, Hashmap<Ref>>((256, ()))
+            .unwrap();
+        assert_eq!(dict.len(), 1);
+        assert_eq!(dict[hash.view_bits::<Msb0>()], library);
+    }
+}
+
+#[cfg(test)]
+mod integration {
+    use super::*;
+    use crate::Client;
+    use std::str::FromStr;
+    use testcontainers_ton::LocalLiteServer;
+    use ton_address::SmartContractAddress;
+    use ton_liteserver_client::{LiteServerAdapter, LiteServerClient};
+    use ton_tower::response::StackEntry;
+
+    const FAUCET_WALLET_ADDR: &str =
+        "-1:22f53b7d9aba2cef44755f7078b01614cd4dde2388a1729c2c386cf8f9898afe";
+
+    #[tokio::test]
+    async fn should_run_get_method_locally() {
+        let server = LocalLiteServer::shared().await.unwrap();
+        let inner = LiteServerClient::connect(server.addr(), server.server_key())
+            .await
+            .unwrap();
+        let mut client = Client::new(EmulatorLayer::new().layer(LiteServerAdapter::new(inner)));
+        let address = SmartContractAddress::from_str(FAUCET_WALLET_ADDR).unwrap();
+
+        let result = client
+            .run_get_method(&address, "get_public_key", vec![])
+            .await
+            .unwrap();
+
+        assert_eq!(result.exit_code, 0);
+        assert!(result.gas_used > 0);
+        assert_eq!(
+            result.stack,
+            vec![StackEntry::Number {
+                number:
+                    "61538797250860244891658288584886086813375283594678556485491459892974908044290"
+                        .to_owned()
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_stop_get_method_at_gas_limit() {
+        let server = LocalLiteServer::shared().await.unwrap();
+        let inner = LiteServerClient::connect(server.addr(), server.server_key())
+            .await
+            .unwrap();
+        let mut client = Client::new(
+            EmulatorLayer::new()
+                .set_gas_limit(10)
+                .layer(LiteServerAdapter::new(inner)),
+        );
+        let address = SmartContractAddress::from_str(FAUCET_WALLET_ADDR).unwrap();
+
+        let result = client
+            .run_get_method(&address, "get_public_key", vec![])
+            .await
+            .unwrap();
+
+        // out of gas, `~13` as reported by the VM
+        assert_eq!(result.exit_code, -14);
+    }
+}
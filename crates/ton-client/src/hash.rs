@@ -0,0 +1,24 @@
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD as base64_standard, URL_SAFE as base64_url};
+
+/// Brings a 32-byte hash given as hex or url-safe base64 to the standard base64 used by
+/// transactions and messages. Anything else is kept as is.
+pub fn normalize_hash(hash: &str) -> String {
+    let bytes = if hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        (0..hash.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hash[i..i + 2], 16))
+            .collect::<Result<Vec<_>, _>>()
+            .ok()
+    } else {
+        base64_standard
+            .decode(hash)
+            .or_else(|_| base64_url.decode(hash))
+            .ok()
+    };
+
+    match bytes {
+        Some(bytes) if bytes.len() == 32 => base64_standard.encode(bytes),
+        _ => hash.to_owned(),
+    }
+}
@@ -1,8 +1,12 @@
 pub(crate) mod algo;
 mod builder;
 pub mod client;
+#[cfg(feature = "emulator")]
+pub mod emulator;
+pub mod hash;
 pub mod pool;
 pub mod route;
+pub mod tx_index;
 
 use crate::pool::Forward;
 use ton_tower::{Request, request::*};
@@ -79,6 +83,11 @@ define_ton_service! {
     GetShardAccountCellOnBlock,
     GetShardAccountCellByTransaction,
     RunGetMethod,
+    RunGetMethodOnBlock,
+    GetConfigAll,
+    GetConfigParam,
+    GetLibraries,
+    GetShardBlockProof,
     SendMessage,
     SendMessageReturningHash,
 }
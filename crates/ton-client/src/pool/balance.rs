@@ -1,3 +1,5 @@
+use crate::pool::Ejectable;
+use crate::pool::hedge::HedgePolicy;
 use crate::route::Routed;
 use crate::{Router, ToRoute};
 use futures::FutureExt;
@@ -8,6 +10,7 @@ use std::hash::Hash;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use ton_tower::IntoRequest;
+use ton_tower::service::retry::Retryable;
 use tower::discover::Discover;
 use tower::load::Load;
 use tower::{MakeService, Service, ServiceExt};
@@ -18,6 +21,7 @@ where
     D::Key: Hash,
 {
     router: Router<S, D>,
+    hedge: Option<HedgePolicy>,
 }
 
 impl<S, D> Balance<S, D>
@@ -28,16 +32,26 @@ where
     pub fn new(discover: D) -> Self {
         let router = Router::new(discover);
 
-        Balance { router }
+        Balance {
+            router,
+            hedge: None,
+        }
+    }
+
+    pub fn with_hedge(mut self, hedge: HedgePolicy) -> Self {
+        self.hedge = Some(hedge);
+        self
     }
 }
+
 impl<S, F, D> Service<F> for Balance<S, D>
 where
-    F: ToRoute + IntoRequest<Request: Send> + Send + 'static,
+    F: ToRoute + IntoRequest<Request: Retryable + Clone + Send + 'static> + Send + 'static,
     S: Clone
-        + Service<F::Request, Error: Into<tower::BoxError>, Future: Send>
+        + Service<F::Request, Error: Into<tower::BoxError>, Future: Send, Response: Send>
         + Load
         + Routed
+        + Ejectable
         + Send
         + 'static,
     D: Discover<Service = S, Error: Into<tower::BoxError> + Debug> + Unpin,
@@ -53,6 +67,13 @@ where
     }
 
     fn call(&mut self, req: F) -> Self::Future {
+        if let Some(hedge) = self.hedge.as_ref().filter(|_| F::Request::IS_RETRYABLE) {
+            return match self.router.candidates(&req.to_route()) {
+                Ok(candidates) => hedge.call(candidates, req.into_request()),
+                Err(e) => futures::future::ready(Err(e)).boxed(),
+            };
+        }
+
         self.router
             .make_service(&req)
             .and_then(|svc| svc.oneshot(req.into_request()))
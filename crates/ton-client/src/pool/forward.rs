@@ -3,10 +3,10 @@ use crate::route::Route;
 use std::time::Duration;
 use ton_tower::{
     IntoRequest, Request,
-    service::{retry::Retryable, timeout::ToTimeout},
+    service::{cache::Cacheable, retry::Retryable, timeout::ToTimeout},
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Forward<T> {
     route: Route,
     inner: T,
@@ -61,3 +61,10 @@ where
 {
     const IS_RETRYABLE: bool = T::IS_RETRYABLE;
 }
+
+impl<T> Cacheable for Forward<T>
+where
+    T: Cacheable,
+{
+    const IS_CACHEABLE: bool = T::IS_CACHEABLE;
+}
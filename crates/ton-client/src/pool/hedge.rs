@@ -0,0 +1,361 @@
+use futures::future::{BoxFuture, Either, select};
+use futures::{FutureExt, pin_mut};
+use hdrhistogram::Histogram;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::balance::p2c::Balance as P2cBalance;
+use tower::discover::ServiceList;
+use tower::load::Load;
+use tower::retry::budget::{Budget, TpsBudget};
+use tower::{BoxError, Service, ServiceExt};
+
+/// Sends a duplicate of a slow request to another lite-server and takes the first answer.
+///
+/// A request is slow when it has not completed within `latency_percentile` of the latencies
+/// observed for its type during the last `period`.
+#[derive(Clone)]
+pub struct HedgePolicy {
+    budget: Arc<TpsBudget>,
+    latency_percentile: f64,
+    min_data_points: u64,
+    period: Duration,
+    latencies: Arc<Mutex<HashMap<&'static str, RotatingHistogram>>>,
+}
+
+impl HedgePolicy {
+    pub fn new(
+        budget: TpsBudget,
+        latency_percentile: f64,
+        min_data_points: u64,
+        period: Duration,
+    ) -> Self {
+        metrics::describe_counter!(
+            "ton_hedge_sent_total",
+            "Number of duplicate requests sent to another lite-server"
+        );
+        metrics::describe_counter!(
+            "ton_hedge_budget_exhausted_total",
+            "Number of slow requests left without a duplicate due to the budget"
+        );
+
+        Self {
+            budget: Arc::new(budget),
+            latency_percentile,
+            min_data_points,
+            period,
+            latencies: Default::default(),
+        }
+    }
+
+    pub fn call<S, R>(
+        &self,
+        candidates: Vec<S>,
+        req: R,
+    ) -> BoxFuture<'static, Result<S::Response, BoxError>>
+    where
+        R: Clone + Send + 'static,
+        S: Service<R, Error: Into<BoxError>, Future: Send, Response: Send>
+            + Load<Metric: Debug>
+            + Clone
+            + Send
+            + 'static,
+    {
+        let request_type = std::any::type_name::<R>();
+        self.budget.deposit();
+
+        let picked = Arc::new(AtomicUsize::new(usize::MAX));
+        let tracked = candidates
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(index, inner)| Tracked {
+                inner,
+                index,
+                picked: Arc::clone(&picked),
+            })
+            .collect();
+        let primary = {
+            let policy = self.clone();
+            let started_at = Instant::now();
+            let response = P2cBalance::new(ServiceList::new(tracked)).oneshot(req.clone());
+
+            async move {
+                let response = response.await;
+                if response.is_ok() {
+                    policy.record(request_type, started_at.elapsed());
+                }
+
+                response
+            }
+        };
+
+        let Some(delay) = self.delay(request_type) else {
+            return primary.boxed();
+        };
+        let policy = self.clone();
+
+        async move {
+            pin_mut!(primary);
+            let timer = tokio::time::sleep(delay);
+            pin_mut!(timer);
+            if let Either::Left((response, _)) = select(primary.as_mut(), timer).await {
+                return response;
+            }
+
+            let picked = picked.load(Ordering::Relaxed);
+            let rest: Vec<_> = candidates
+                .into_iter()
+                .enumerate()
+                .filter_map(|(index, svc)| (index != picked).then_some(svc))
+                .collect();
+            if rest.is_empty() {
+                return primary.await;
+            }
+            if !policy.budget.withdraw() {
+                metrics::counter!("ton_hedge_budget_exhausted_total", "request_type" => request_type).increment(1);
+
+                return primary.await;
+            }
+            metrics::counter!("ton_hedge_sent_total", "request_type" => request_type).increment(1);
+
+            let hedged = P2cBalance::new(ServiceList::new(rest)).oneshot(req);
+            pin_mut!(hedged);
+            match select(primary, hedged).await {
+                Either::Left((Ok(response), _)) | Either::Right((Ok(response), _)) => Ok(response),
+                Either::Left((Err(_), hedged)) => hedged.await,
+                Either::Right((Err(_), primary)) => primary.await,
+            }
+        }
+        .boxed()
+    }
+
+    fn delay(&self, request_type: &'static str) -> Option<Duration> {
+        let mut latencies = self.latencies.lock().unwrap();
+        let histogram = latencies
+            .entry(request_type)
+            .or_insert_with(|| RotatingHistogram::new(self.period))
+            .read();
+
+        if histogram.len() < self.min_data_points {
+            return None;
+        }
+
+        Some(Duration::from_micros(
+            histogram.value_at_quantile(self.latency_percentile),
+        ))
+    }
+
+    fn record(&self, request_type: &'static str, latency: Duration) {
+        let mut latencies = self.latencies.lock().unwrap();
+
+        latencies
+            .entry(request_type)
+            .or_insert_with(|| RotatingHistogram::new(self.period))
+            .write()
+            .saturating_record(latency.as_micros() as u64);
+    }
+}
+
+/// Latencies in microseconds of the previous period are read while the current period is
+/// written, so sub-millisecond answers are not rounded down to a zero delay.
+struct RotatingHistogram {
+    read: Histogram<u64>,
+    write: Histogram<u64>,
+    last_rotation: Instant,
+    period: Duration,
+}
+
+impl RotatingHistogram {
+    fn new(period: Duration) -> Self {
+        Self {
+            read: Histogram::new(3).expect("valid histogram precision"),
+            write: Histogram::new(3).expect("valid histogram precision"),
+            last_rotation: Instant::now(),
+            period,
+        }
+    }
+
+    fn read(&mut self) -> &Histogram<u64> {
+        self.maybe_rotate();
+
+        &self.read
+    }
+
+    fn write(&mut self) -> &mut Histogram<u64> {
+        self.maybe_rotate();
+
+        &mut self.write
+    }
+
+    fn maybe_rotate(&mut self) {
+        if self.last_rotation.elapsed() >= self.period {
+            std::mem::swap(&mut self.read, &mut self.write);
+            self.write.clear();
+            self.last_rotation = Instant::now();
+        }
+    }
+}
+
+/// Remembers which of the candidates served the primary request.
+#[derive(Clone)]
+struct Tracked<S> {
+    inner: S,
+    index: usize,
+    picked: Arc<AtomicUsize>,
+}
+
+impl<S, R> Service<R> for Tracked<S>
+where
+    S: Service<R>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        self.picked.store(self.index, Ordering::Relaxed);
+
+        self.inner.call(req)
+    }
+}
+
+impl<S> Load for Tracked<S>
+where
+    S: Load,
+{
+    type Metric = S::Metric;
+
+    fn load(&self) -> Self::Metric {
+        self.inner.load()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::mock;
+
+    #[test]
+    fn delay_is_unknown_until_enough_data_points() {
+        let policy = given_policy(2);
+
+        policy.record("request", Duration::from_millis(10));
+
+        assert_eq!(policy.delay("request"), None);
+    }
+
+    #[test]
+    fn delay_is_taken_from_previous_period() {
+        let policy = given_policy(2);
+
+        policy.record("request", Duration::from_millis(10));
+        policy.record("request", Duration::from_millis(20));
+        policy.record("other", Duration::from_millis(100));
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(policy.delay("request").unwrap().as_millis(), 20);
+        assert_eq!(policy.delay("unknown"), None);
+    }
+
+    #[test]
+    fn delay_keeps_sub_millisecond_latencies() {
+        let policy = given_policy(1);
+
+        policy.record("request", Duration::from_micros(500));
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(policy.delay("request").unwrap().as_micros(), 500);
+    }
+
+    #[tokio::test]
+    async fn takes_answer_of_the_faster_candidate() {
+        let policy = given_policy(0);
+        // the least loaded candidate serves the primary request
+        let slow = given_candidate(None, 0, 0, 1);
+        let fast = given_candidate(Some(2), 1, 1, 0);
+
+        let response = policy.call(vec![slow, fast], Req).await.unwrap();
+
+        assert_eq!(response, 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_hedge_to_the_same_candidate() {
+        let policy = given_policy(0);
+        let only = given_candidate(Some(1), 0, 0, 1);
+
+        let response = policy.call(vec![only], Req).await.unwrap();
+
+        assert_eq!(response, 1);
+    }
+
+    fn given_policy(min_data_points: u64) -> HedgePolicy {
+        HedgePolicy::new(
+            TpsBudget::new(Duration::from_secs(10), 10, 0.1),
+            0.99,
+            min_data_points,
+            Duration::from_millis(10),
+        )
+    }
+
+    #[derive(Clone)]
+    struct Req;
+
+    mock! {
+        Service {}
+
+        impl Clone for Service {
+            fn clone(&self) -> Self;
+        }
+
+        impl Service<Req> for Service {
+            type Response = u32;
+            type Error = BoxError;
+            type Future = BoxFuture<'static, Result<u32, BoxError>>;
+
+            fn poll_ready<'a>(&mut self, _cx: &mut Context<'a>) -> Poll<Result<(), BoxError>>;
+            fn call(&mut self, _req: Req) -> BoxFuture<'static, Result<u32, BoxError>>;
+        }
+
+        impl Load for Service {
+            type Metric = usize;
+
+            fn load(&self) -> usize;
+        }
+    }
+
+    /// The candidate is called for the hedged request, its clone for the primary one.
+    fn given_candidate(
+        answer: Option<u32>,
+        load: usize,
+        hedged_calls: usize,
+        primary_calls: usize,
+    ) -> MockService {
+        let mut svc = given_service(answer, load, hedged_calls);
+        svc.expect_clone()
+            .return_once(move || given_service(answer, load, primary_calls));
+        svc
+    }
+
+    /// Answers with `answer` or never when it is `None`.
+    fn given_service(answer: Option<u32>, load: usize, calls: usize) -> MockService {
+        let mut svc = MockService::new();
+        svc.expect_poll_ready().returning(|_| Poll::Ready(Ok(())));
+        svc.expect_load().return_const(load);
+        svc.expect_call()
+            .times(calls)
+            .returning(move |_| match answer {
+                Some(answer) => futures::future::ready(Ok(answer)).boxed(),
+                None => futures::future::pending().boxed(),
+            });
+        svc
+    }
+}
@@ -1,7 +1,11 @@
 mod balance;
 mod discover;
 mod forward;
+mod hedge;
+mod outlier;
 
 pub use balance::Balance;
 pub use discover::{LiteServerDiscoverError, LiteServerDiscoverHandle};
 pub use forward::Forward;
+pub use hedge::HedgePolicy;
+pub use outlier::{Ejectable, OutlierConfig, OutlierDetection};
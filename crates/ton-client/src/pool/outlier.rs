@@ -0,0 +1,493 @@
+use crate::route::{BlockCriteria, ChainId, Routed, Seqno};
+use pin_project::{pin_project, pinned_drop};
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, ready};
+use std::time::{Duration, Instant};
+use ton_tower::service::error::RequestError;
+use tower::Service;
+use tower::load::Load;
+
+pub trait Ejectable {
+    fn is_ejected(&self) -> bool;
+}
+
+/// Tells errors of the lite-server apart from errors caused by the request itself.
+pub trait ServerFailure {
+    fn is_server_failure(&self) -> bool;
+}
+
+impl ServerFailure for anyhow::Error {
+    fn is_server_failure(&self) -> bool {
+        !RequestError::is_cause_of(&**self)
+    }
+}
+
+impl ServerFailure for Infallible {
+    fn is_server_failure(&self) -> bool {
+        match *self {}
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OutlierConfig {
+    /// Ejects a server after this many errors in a row.
+    pub consecutive_errors: u32,
+    /// Ejects a server when the share of errors in a window reaches this ratio.
+    pub error_ratio: f64,
+    /// Requests in a window needed before the error ratio is taken into account.
+    pub min_requests: u32,
+    pub window: Duration,
+    pub base_ejection: Duration,
+    pub max_ejection: Duration,
+}
+
+impl Default for OutlierConfig {
+    fn default() -> Self {
+        Self {
+            consecutive_errors: 5,
+            error_ratio: 0.5,
+            min_requests: 20,
+            window: Duration::from_secs(10),
+            base_ejection: Duration::from_secs(5),
+            max_ejection: Duration::from_secs(300),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Healthy,
+    Ejected {
+        until: Instant,
+    },
+    /// The ejection is over and the next request decides whether the server is back.
+    Probing,
+}
+
+struct Detector {
+    liteserver_id: String,
+    config: OutlierConfig,
+    state: State,
+    ejections: u32,
+    consecutive_errors: u32,
+    window_started_at: Instant,
+    requests: u32,
+    errors: u32,
+}
+
+impl Detector {
+    fn new(liteserver_id: String, config: OutlierConfig) -> Self {
+        Self {
+            liteserver_id,
+            config,
+            state: State::Healthy,
+            ejections: 0,
+            consecutive_errors: 0,
+            window_started_at: Instant::now(),
+            requests: 0,
+            errors: 0,
+        }
+    }
+
+    fn is_ejected(&self) -> bool {
+        match self.state {
+            State::Healthy => false,
+            State::Ejected { until } => until > Instant::now(),
+            State::Probing => true,
+        }
+    }
+
+    /// Returns whether the call is the probe of an ejected server.
+    fn on_call(&mut self) -> bool {
+        if let State::Ejected { until } = self.state
+            && until <= Instant::now()
+        {
+            tracing::info!(liteserver_id = %self.liteserver_id, "probing ejected liteserver");
+
+            self.state = State::Probing;
+
+            return true;
+        }
+
+        false
+    }
+
+    /// The probe was dropped before it completed, so nothing proves the server is back.
+    fn on_probe_dropped(&mut self) {
+        if self.state == State::Probing {
+            self.eject();
+        }
+    }
+
+    fn on_response(&mut self, is_ok: bool) {
+        if self.window_started_at.elapsed() >= self.config.window {
+            self.window_started_at = Instant::now();
+            self.requests = 0;
+            self.errors = 0;
+        }
+        self.requests += 1;
+
+        match (self.state, is_ok) {
+            (State::Probing, true) => self.reinstate(),
+            (State::Probing, false) => self.eject(),
+            (State::Ejected { .. }, _) => {}
+            (State::Healthy, true) => self.consecutive_errors = 0,
+            (State::Healthy, false) => {
+                self.consecutive_errors += 1;
+                self.errors += 1;
+
+                let ratio = f64::from(self.errors) / f64::from(self.requests);
+                if self.consecutive_errors >= self.config.consecutive_errors
+                    || (self.requests >= self.config.min_requests
+                        && ratio >= self.config.error_ratio)
+                {
+                    self.eject();
+                }
+            }
+        }
+    }
+
+    fn eject(&mut self) {
+        let backoff = self
+            .config
+            .base_ejection
+            .saturating_mul(2u32.saturating_pow(self.ejections))
+            .min(self.config.max_ejection);
+        self.ejections += 1;
+        self.state = State::Ejected {
+            until: Instant::now() + backoff,
+        };
+
+        tracing::warn!(
+            liteserver_id = %self.liteserver_id,
+            consecutive_errors = self.consecutive_errors,
+            errors = self.errors,
+            requests = self.requests,
+            ?backoff,
+            "liteserver ejected"
+        );
+        metrics::counter!("ton_liteserver_ejections_total", "liteserver_id" => self.liteserver_id.clone()).increment(1);
+        metrics::gauge!("ton_liteserver_ejected", "liteserver_id" => self.liteserver_id.clone())
+            .set(1.0);
+    }
+
+    fn reinstate(&mut self) {
+        self.state = State::Healthy;
+        self.ejections = 0;
+        self.consecutive_errors = 0;
+        self.window_started_at = Instant::now();
+        self.requests = 0;
+        self.errors = 0;
+
+        tracing::info!(liteserver_id = %self.liteserver_id, "liteserver reinstated");
+        metrics::gauge!("ton_liteserver_ejected", "liteserver_id" => self.liteserver_id.clone())
+            .set(0.0);
+    }
+}
+
+/// Ejects a lite-server from routing while it keeps failing, see [`OutlierConfig`].
+#[derive(Clone)]
+pub struct OutlierDetection<S> {
+    inner: S,
+    detector: Option<Arc<Mutex<Detector>>>,
+}
+
+impl<S> OutlierDetection<S> {
+    pub fn new(inner: S, liteserver_id: String, config: OutlierConfig) -> Self {
+        metrics::describe_counter!(
+            "ton_liteserver_ejections_total",
+            "Number of times a liteserver was ejected from routing"
+        );
+        metrics::describe_gauge!(
+            "ton_liteserver_ejected",
+            "Whether a liteserver is ejected from routing"
+        );
+        metrics::gauge!("ton_liteserver_ejected", "liteserver_id" => liteserver_id.clone())
+            .set(0.0);
+
+        Self {
+            inner,
+            detector: Some(Arc::new(Mutex::new(Detector::new(liteserver_id, config)))),
+        }
+    }
+
+    /// Passes every request through and never ejects the lite-server.
+    pub fn disabled(inner: S) -> Self {
+        Self {
+            inner,
+            detector: None,
+        }
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S> Ejectable for OutlierDetection<S> {
+    fn is_ejected(&self) -> bool {
+        self.detector
+            .as_ref()
+            .is_some_and(|detector| detector.lock().unwrap().is_ejected())
+    }
+}
+
+impl<S, Request> Service<Request> for OutlierDetection<S>
+where
+    S: Service<Request>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let probe = self
+            .detector
+            .as_ref()
+            .is_some_and(|detector| detector.lock().unwrap().on_call());
+
+        ResponseFuture {
+            inner: self.inner.call(req),
+            detector: self.detector.clone(),
+            probe,
+        }
+    }
+}
+
+impl<S> Load for OutlierDetection<S>
+where
+    S: Load,
+{
+    type Metric = S::Metric;
+
+    fn load(&self) -> Self::Metric {
+        self.inner.load()
+    }
+}
+
+impl<S> Routed for OutlierDetection<S>
+where
+    S: Routed,
+{
+    fn contains(&self, chain: &ChainId, criteria: &BlockCriteria) -> bool {
+        self.inner.contains(chain, criteria)
+    }
+
+    fn contains_not_available(&self, chain: &ChainId, criteria: &BlockCriteria) -> bool {
+        self.inner.contains_not_available(chain, criteria)
+    }
+
+    fn last_seqno(&self) -> Option<Seqno> {
+        self.inner.last_seqno()
+    }
+}
+
+#[pin_project(PinnedDrop)]
+pub struct ResponseFuture<F> {
+    #[pin]
+    inner: F,
+    detector: Option<Arc<Mutex<Detector>>>,
+    probe: bool,
+}
+
+#[pinned_drop]
+impl<F> PinnedDrop for ResponseFuture<F> {
+    fn drop(self: Pin<&mut Self>) {
+        if self.probe
+            && let Some(detector) = &self.detector
+        {
+            detector.lock().unwrap().on_probe_dropped();
+        }
+    }
+}
+
+impl<F, T, E> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<T, E>>,
+    E: ServerFailure,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let response = ready!(this.inner.poll(cx));
+        if let Some(detector) = this.detector {
+            let is_ok = response
+                .as_ref()
+                .err()
+                .is_none_or(|e| !e.is_server_failure());
+            detector.lock().unwrap().on_response(is_ok);
+        }
+        *this.probe = false;
+
+        Poll::Ready(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::{Ready, ready};
+    use ton_liteserver_client::adapter::client_error;
+    use ton_liteserver_client::client;
+    use ton_liteserver_client::tl::LiteServerError;
+
+    #[test]
+    fn ejects_after_consecutive_errors() {
+        let mut detector = given_detector();
+
+        respond(&mut detector, &[false, false]);
+        assert!(!detector.is_ejected());
+        respond(&mut detector, &[false]);
+
+        assert!(detector.is_ejected());
+    }
+
+    #[test]
+    fn success_resets_consecutive_errors() {
+        let mut detector = given_detector();
+
+        respond(&mut detector, &[false, false, true, false, false]);
+
+        assert!(!detector.is_ejected());
+    }
+
+    #[test]
+    fn ejects_on_error_ratio() {
+        let mut detector = given_detector();
+
+        respond(
+            &mut detector,
+            &[true, false, true, false, true, false, true, false],
+        );
+
+        assert!(detector.is_ejected());
+    }
+
+    #[test]
+    fn successful_probe_reinstates() {
+        let mut detector = given_detector();
+        respond(&mut detector, &[false, false, false]);
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!detector.is_ejected());
+
+        detector.on_call();
+        assert!(detector.is_ejected());
+        detector.on_response(true);
+
+        assert_eq!(detector.state, State::Healthy);
+        assert!(!detector.is_ejected());
+    }
+
+    #[test]
+    fn failed_probe_doubles_ejection() {
+        let mut detector = given_detector();
+        respond(&mut detector, &[false, false, false]);
+        std::thread::sleep(Duration::from_millis(20));
+
+        detector.on_call();
+        detector.on_response(false);
+
+        let State::Ejected { until } = detector.state else {
+            panic!("liteserver must be ejected");
+        };
+        assert!(until > Instant::now() + Duration::from_millis(10));
+    }
+
+    #[test]
+    fn dropped_probe_ejects_again() {
+        let mut detector = given_detector();
+        respond(&mut detector, &[false, false, false]);
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert!(detector.on_call());
+        detector.on_probe_dropped();
+
+        assert!(matches!(detector.state, State::Ejected { .. }));
+        assert!(detector.is_ejected());
+    }
+
+    #[test]
+    fn request_errors_are_not_server_failures() {
+        let transport = anyhow::anyhow!("connection reset");
+        let request = anyhow::Error::from(RequestError(anyhow::anyhow!("account not found")))
+            .context("get account state");
+
+        assert!(transport.is_server_failure());
+        assert!(!request.is_server_failure());
+    }
+
+    #[tokio::test]
+    async fn ejects_liteserver_answering_not_ready() {
+        let mut service = given_liteserver_answering(651);
+
+        for _ in 0..3 {
+            service.call(()).await.unwrap_err();
+        }
+
+        assert!(service.is_ejected());
+    }
+
+    #[tokio::test]
+    async fn keeps_liteserver_rejecting_queries() {
+        let mut service = given_liteserver_answering(-400);
+
+        for _ in 0..3 {
+            service.call(()).await.unwrap_err();
+        }
+
+        assert!(!service.is_ejected());
+    }
+
+    fn given_liteserver_answering(
+        code: i32,
+    ) -> OutlierDetection<
+        impl Service<(), Response = (), Error = anyhow::Error, Future = Ready<anyhow::Result<()>>>,
+    > {
+        let service = tower::service_fn(move |()| {
+            ready(Err::<(), _>(client_error(client::Error::LiteServerError(
+                LiteServerError {
+                    code,
+                    message: "cannot load block".to_string(),
+                },
+            ))))
+        });
+
+        OutlierDetection::new(
+            service,
+            "liteserver".to_string(),
+            OutlierConfig {
+                base_ejection: Duration::from_secs(1),
+                ..given_detector().config
+            },
+        )
+    }
+
+    fn given_detector() -> Detector {
+        Detector::new(
+            "liteserver".to_string(),
+            OutlierConfig {
+                consecutive_errors: 3,
+                error_ratio: 0.5,
+                min_requests: 8,
+                window: Duration::from_secs(60),
+                base_ejection: Duration::from_millis(10),
+                max_ejection: Duration::from_secs(1),
+            },
+        )
+    }
+
+    fn respond(detector: &mut Detector, responses: &[bool]) {
+        for is_ok in responses {
+            detector.on_call();
+            detector.on_response(*is_ok);
+        }
+    }
+}
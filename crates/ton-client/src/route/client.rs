@@ -179,6 +179,11 @@ forward_service!(
     GetShardAccountCellOnBlock,
     GetShardAccountCellByTransaction,
     RunGetMethod,
+    RunGetMethodOnBlock,
+    GetConfigAll,
+    GetConfigParam,
+    GetLibraries,
+    GetShardBlockProof,
 );
 
 impl<S> Load for RoutedClient<S>
@@ -4,7 +4,7 @@ mod registry;
 mod request;
 mod router;
 mod shard_bounds;
-mod shard_prefix;
+pub(crate) mod shard_prefix;
 
 use itertools::Itertools;
 use ton_tower::response::BlockIdExt;
@@ -24,13 +24,13 @@ pub trait ToRoute {
     fn to_route(&self) -> Route;
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Route {
     Block { chain: i32, criteria: BlockCriteria },
     Latest,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BlockCriteria {
     Seqno { shard: i64, seqno: i32 },
     LogicalTime { address: [u8; 32], lt: i64 },
@@ -103,6 +103,18 @@ impl ToRoute for RunGetMethod {
     }
 }
 
+impl ToRoute for RunGetMethodOnBlock {
+    fn to_route(&self) -> Route {
+        Route::Block {
+            chain: self.block_id.workchain,
+            criteria: BlockCriteria::Seqno {
+                shard: self.block_id.shard,
+                seqno: self.block_id.seqno,
+            },
+        }
+    }
+}
+
 impl ToRoute for LookUpBlockBySeqno {
     fn to_route(&self) -> Route {
         Route::Block {
@@ -178,6 +190,48 @@ impl ToRoute for GetTransactions {
     }
 }
 
+impl ToRoute for GetConfigAll {
+    fn to_route(&self) -> Route {
+        Route::Block {
+            chain: self.block_id.workchain,
+            criteria: BlockCriteria::Seqno {
+                shard: self.block_id.shard,
+                seqno: self.block_id.seqno,
+            },
+        }
+    }
+}
+
+impl ToRoute for GetConfigParam {
+    fn to_route(&self) -> Route {
+        Route::Block {
+            chain: self.block_id.workchain,
+            criteria: BlockCriteria::Seqno {
+                shard: self.block_id.shard,
+                seqno: self.block_id.seqno,
+            },
+        }
+    }
+}
+
+impl ToRoute for GetLibraries {
+    fn to_route(&self) -> Route {
+        Route::Latest
+    }
+}
+
+impl ToRoute for GetShardBlockProof {
+    fn to_route(&self) -> Route {
+        Route::Block {
+            chain: self.id.workchain,
+            criteria: BlockCriteria::Seqno {
+                shard: self.id.shard,
+                seqno: self.id.seqno,
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,6 +267,28 @@ mod tests {
         GetShardAccountCellOnBlock { address: addr(), block_id: block_id(0, 1, 10) }.to_route(),
         block_route(0, 1, 10)
     )]
+    #[case::run_get_method_on_block(
+        RunGetMethodOnBlock {
+            address: addr(),
+            block_id: block_id(-1, i64::MIN, 42),
+            method: "seqno".to_owned(),
+            stack: vec![],
+        }.to_route(),
+        block_route(-1, i64::MIN, 42)
+    )]
+    #[case::get_config_all(
+        GetConfigAll { block_id: block_id(-1, i64::MIN, 42) }.to_route(),
+        block_route(-1, i64::MIN, 42)
+    )]
+    #[case::get_config_param(
+        GetConfigParam { block_id: block_id(-1, i64::MIN, 42), param: 34 }.to_route(),
+        block_route(-1, i64::MIN, 42)
+    )]
+    #[case::get_libraries(GetLibraries { hashes: vec![] }.to_route(), Route::Latest)]
+    #[case::get_shard_block_proof(
+        GetShardBlockProof { id: block_id(0, 1, 10) }.to_route(),
+        block_route(0, 1, 10)
+    )]
     fn to_route(#[case] actual: Route, #[case] expected: Route) {
         assert_eq!(actual, expected);
     }
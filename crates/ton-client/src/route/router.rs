@@ -1,4 +1,5 @@
 use crate::ToRoute;
+use crate::pool::Ejectable;
 use crate::route::Route;
 use crate::route::{Error, Routed, choose};
 use std::collections::HashMap;
@@ -73,7 +74,7 @@ where
 impl<S, D, R> Service<&R> for Router<S, D>
 where
     R: ToRoute + IntoRequest,
-    S: Service<R::Request, Error: Into<BoxError>> + Routed + Clone,
+    S: Service<R::Request, Error: Into<BoxError>> + Routed + Ejectable + Clone,
     D: Discover<Service = S, Error: Into<BoxError>> + Unpin,
     D::Key: Hash,
 {
@@ -98,21 +99,41 @@ where
     }
 
     fn call(&mut self, req: &R) -> Self::Future {
-        ready(match choose(&req.to_route(), self.services.values()) {
-            Ok(services) => Ok(P2cBalance::new(ServiceList::new(services))),
+        ready(
+            self.candidates(&req.to_route())
+                .map(|services| P2cBalance::new(ServiceList::new(services))),
+        )
+    }
+}
+
+impl<S, D> Router<S, D>
+where
+    S: Routed + Ejectable + Clone,
+    D: Discover<Service = S>,
+    D::Key: Hash,
+{
+    /// Services able to serve `route`, falls back to the latest ones when the route is unknown.
+    ///
+    /// Ejected services are skipped unless all of them are ejected.
+    pub fn candidates(&self, route: &Route) -> Result<Vec<S>, BoxError> {
+        let mut services: Vec<_> = self.services.values().filter(|s| !s.is_ejected()).collect();
+        if services.is_empty() {
+            services = self.services.values().collect();
+        }
+
+        match choose(route, services.iter().copied()) {
+            Ok(services) => Ok(services),
             Err(Error::RouteUnknown) => {
                 metrics::counter!("ton_router_miss_count").increment(1);
 
-                choose(&Route::Latest, self.services.values())
-                    .map(|services| P2cBalance::new(ServiceList::new(services)))
-                    .map_err(Into::into)
+                choose(&Route::Latest, services.iter().copied()).map_err(Into::into)
             }
             Err(Error::RouteNotAvailable) => {
                 metrics::counter!("ton_router_delayed_count").increment(1);
 
                 Err(Error::RouteNotAvailable.into())
             }
-        })
+        }
     }
 }
 
@@ -149,6 +170,10 @@ mod tests {
             fn contains_not_available(&self, _chain: &i32, _criteria: &BlockCriteria) -> bool;
             fn last_seqno(&self) -> Option<i32>;
         }
+
+        impl Ejectable for Service {
+            fn is_ejected(&self) -> bool;
+        }
     }
 
     #[tokio::test]
@@ -0,0 +1,281 @@
+use crate::RequestHandler;
+use crate::client::Client;
+use crate::hash::normalize_hash;
+use futures::TryStreamExt;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+use ton_address::SmartContractAddress;
+use ton_tower::request::{GetBlockHeader, GetShards, GetTransactions, LookUpBlockBySeqno, Sync};
+use ton_tower::response::{Transaction, TransactionId};
+
+const RESTART_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedTransaction {
+    pub address: SmartContractAddress,
+    pub transaction_id: TransactionId,
+    /// Seqno of the masterchain block that committed the transaction.
+    pub mc_seqno: i32,
+    in_msg_hash: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    entries: VecDeque<IndexedTransaction>,
+    by_hash: HashMap<String, usize>,
+    by_in_msg_hash: HashMap<String, usize>,
+    /// Absolute position of `entries[0]`, so map values stay valid across evictions.
+    offset: usize,
+}
+
+impl State {
+    fn get(&self, position: Option<&usize>) -> Option<&IndexedTransaction> {
+        self.entries.get(position?.checked_sub(self.offset)?)
+    }
+
+    fn evict(&mut self) {
+        let Some(entry) = self.entries.pop_front() else {
+            return;
+        };
+
+        let hash = normalize_hash(&entry.transaction_id.hash);
+        if self.by_hash.get(&hash) == Some(&self.offset) {
+            self.by_hash.remove(&hash);
+        }
+        if let Some(hash) = entry.in_msg_hash
+            && self.by_in_msg_hash.get(&hash) == Some(&self.offset)
+        {
+            self.by_in_msg_hash.remove(&hash);
+        }
+        self.offset += 1;
+    }
+}
+
+/// Bounded in-memory index of recent transactions, keyed by transaction hash and by the
+/// hash of the inbound message. The oldest transactions are evicted once `capacity` is hit.
+#[derive(Debug)]
+pub struct TransactionIndex {
+    capacity: usize,
+    state: Mutex<State>,
+}
+
+impl TransactionIndex {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::default(),
+        }
+    }
+
+    pub fn insert(&self, mc_seqno: i32, tx: &Transaction) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let hash = normalize_hash(&tx.transaction_id.hash);
+        let mut state = self.state.lock().expect("poisoned");
+        // a block is indexed again when the stream restarts in the middle of it
+        if state.get(state.by_hash.get(&hash)).is_some() {
+            return;
+        }
+
+        while state.entries.len() >= self.capacity {
+            state.evict();
+        }
+
+        let position = state.offset + state.entries.len();
+        let in_msg_hash = tx.in_msg.as_ref().map(|m| normalize_hash(&m.hash));
+
+        state.by_hash.insert(hash, position);
+        if let Some(hash) = in_msg_hash.as_ref() {
+            state.by_in_msg_hash.insert(hash.clone(), position);
+        }
+        state.entries.push_back(IndexedTransaction {
+            address: tx.address.clone(),
+            transaction_id: tx.transaction_id.clone(),
+            mc_seqno,
+            in_msg_hash,
+        });
+    }
+
+    /// Accepts the hash as hex, base64 or url-safe base64.
+    pub fn get_by_hash(&self, hash: &str) -> Option<IndexedTransaction> {
+        let state = self.state.lock().expect("poisoned");
+
+        state.get(state.by_hash.get(&normalize_hash(hash))).cloned()
+    }
+
+    /// Accepts the hash as hex, base64 or url-safe base64.
+    pub fn get_by_in_msg_hash(&self, hash: &str) -> Option<IndexedTransaction> {
+        let state = self.state.lock().expect("poisoned");
+
+        state
+            .get(state.by_in_msg_hash.get(&normalize_hash(hash)))
+            .cloned()
+    }
+
+    /// Range of masterchain seqnos currently covered by the index.
+    pub fn window(&self) -> Option<(i32, i32)> {
+        let state = self.state.lock().expect("poisoned");
+
+        Some((
+            state.entries.front()?.mc_seqno,
+            state.entries.back()?.mc_seqno,
+        ))
+    }
+
+    /// Follows new masterchain blocks and indexes every transaction committed by them.
+    /// Never returns: stream errors are logged and indexing resumes from the next block.
+    pub async fn run<S>(&self, client: Client<S>)
+    where
+        S: RequestHandler<Sync>
+            + RequestHandler<LookUpBlockBySeqno>
+            + RequestHandler<GetShards>
+            + RequestHandler<GetBlockHeader>
+            + RequestHandler<GetTransactions>
+            + Clone
+            + Send
+            + std::marker::Sync
+            + 'static,
+    {
+        let mut next_seqno = None;
+        loop {
+            if let Err(e) = self.fill(&client, &mut next_seqno).await {
+                tracing::warn!(error = %e, "transaction index stream failed");
+
+                tokio::time::sleep(RESTART_DELAY).await;
+            }
+        }
+    }
+
+    async fn fill<S>(&self, client: &Client<S>, next_seqno: &mut Option<i32>) -> anyhow::Result<()>
+    where
+        S: RequestHandler<Sync>
+            + RequestHandler<LookUpBlockBySeqno>
+            + RequestHandler<GetShards>
+            + RequestHandler<GetBlockHeader>
+            + RequestHandler<GetTransactions>
+            + Clone
+            + Send
+            + std::marker::Sync
+            + 'static,
+    {
+        let blocks = client.get_masterchain_block_stream_from(*next_seqno);
+        futures::pin_mut!(blocks);
+
+        while let Some((master, shards)) = blocks.try_next().await? {
+            let mc_seqno = master.seqno;
+
+            for block in std::iter::once(master).chain(shards) {
+                let txs = client.get_block_tx_stream(&block, false);
+                futures::pin_mut!(txs);
+
+                while let Some(tx) = txs.try_next().await? {
+                    self.insert(mc_seqno, &tx);
+                }
+            }
+
+            *next_seqno = Some(mc_seqno + 1);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use ton_tower::response::{Message, MessageData};
+
+    fn tx(lt: i64) -> Transaction {
+        Transaction {
+            address: SmartContractAddress::from_str(
+                "-1:22f53b7d9aba2cef44755f7078b01614cd4dde2388a1729c2c386cf8f9898afe",
+            )
+            .unwrap(),
+            utime: 0,
+            data: String::new(),
+            transaction_id: TransactionId {
+                lt,
+                hash: format!("tx{}", lt),
+            },
+            fee: 0,
+            storage_fee: 0,
+            other_fee: 0,
+            in_msg: Some(Message {
+                hash: format!("msg{}", lt),
+                source: None,
+                destination: None,
+                value: 0,
+                fwd_fee: 0,
+                ihr_fee: 0,
+                created_lt: 0,
+                body_hash: String::new(),
+                msg_data: MessageData::Raw {
+                    body: String::new(),
+                    init_state: String::new(),
+                },
+                decoded: None,
+            }),
+            out_msgs: vec![],
+        }
+    }
+
+    #[test]
+    fn should_find_by_hash_and_in_msg_hash() {
+        let index = TransactionIndex::new(4);
+        index.insert(1, &tx(1));
+        index.insert(2, &tx(2));
+
+        assert_eq!(index.get_by_hash("tx1").unwrap().transaction_id.lt, 1);
+        assert_eq!(index.get_by_in_msg_hash("msg2").unwrap().mc_seqno, 2);
+        assert_eq!(index.get_by_hash("tx3"), None);
+        assert_eq!(index.window(), Some((1, 2)));
+    }
+
+    #[test]
+    fn should_evict_oldest_when_full() {
+        let index = TransactionIndex::new(2);
+        index.insert(1, &tx(1));
+        index.insert(2, &tx(2));
+        index.insert(3, &tx(3));
+
+        assert_eq!(index.get_by_hash("tx1"), None);
+        assert_eq!(index.get_by_in_msg_hash("msg1"), None);
+        assert_eq!(index.get_by_hash("tx3").unwrap().transaction_id.lt, 3);
+        assert_eq!(index.window(), Some((2, 3)));
+    }
+
+    #[test]
+    fn should_skip_already_indexed_transaction() {
+        let index = TransactionIndex::new(2);
+        index.insert(1, &tx(1));
+        index.insert(1, &tx(1));
+        index.insert(2, &tx(2));
+
+        assert_eq!(index.get_by_hash("tx1").unwrap().transaction_id.lt, 1);
+        assert_eq!(index.window(), Some((1, 2)));
+    }
+
+    #[test]
+    fn should_find_hash_in_any_encoding() {
+        let index = TransactionIndex::new(2);
+        let mut tx = tx(1);
+        tx.transaction_id.hash = "/".repeat(42) + "8=";
+        index.insert(1, &tx);
+
+        assert!(index.get_by_hash(&"f".repeat(64)).is_some());
+        assert!(index.get_by_hash(&"F".repeat(64)).is_some());
+        assert!(index.get_by_hash(&("_".repeat(42) + "8=")).is_some());
+        assert!(index.get_by_hash(&("/".repeat(42) + "8=")).is_some());
+    }
+
+    #[test]
+    fn should_keep_empty_window_without_transactions() {
+        let index = TransactionIndex::new(2);
+
+        assert_eq!(index.window(), None);
+    }
+}
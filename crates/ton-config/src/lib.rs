@@ -39,6 +39,26 @@ impl TonConfig {
             data: self.data.clone(),
         }
     }
+
+    /// The masterchain block a light client may trust without a proof:
+    /// `validator.init_block`, or `validator.zero_state` for configs without one.
+    pub fn init_block(&self) -> Option<BlockIdExt> {
+        let validator = self.data.get("validator")?;
+        let block = validator
+            .get("init_block")
+            .or_else(|| validator.get("zero_state"))?;
+
+        BlockIdExt::deserialize(block).ok()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct BlockIdExt {
+    pub workchain: i32,
+    pub shard: i64,
+    pub seqno: i32,
+    pub root_hash: String,
+    pub file_hash: String,
 }
 
 impl Display for TonConfig {
@@ -187,6 +207,32 @@ mod tests {
         assert!(result["liteservers"].as_array().unwrap().is_empty());
     }
 
+    #[test]
+    fn init_block_falls_back_to_zero_state() {
+        let zero_state = json!({
+            "workchain": -1,
+            "shard": -9223372036854775808i64,
+            "seqno": 0,
+            "root_hash": "F6OpKZKqvqeFp6CQmFomXNMfMj2EnaUSOXN+Mh+wVWk=",
+            "file_hash": "XplPz01CXAps5qeSWUtxcyBfdAo5zVb1N979KLSKD24="
+        });
+        let json = json!({
+            "@type": "config.global",
+            "liteservers": [],
+            "validator": { "zero_state": zero_state }
+        });
+
+        let config = serde_json::from_value::<TonConfig>(json).unwrap();
+        let result = config.init_block().unwrap();
+
+        assert_eq!(result.seqno, 0);
+        assert_eq!(result.shard, i64::MIN);
+        assert_eq!(
+            result.root_hash,
+            "F6OpKZKqvqeFp6CQmFomXNMfMj2EnaUSOXN+Mh+wVWk="
+        );
+    }
+
     #[test]
     fn deserialize_liteserver() {
         let json = json!({
@@ -1,23 +1,34 @@
 #![allow(clippy::blocks_in_conditions)]
 
-use crate::helpers::{extend_block_id, extend_from_tx_id, extend_to_tx_id};
+use crate::helpers::{extend_block_id, extend_from_tx_id, extend_to_tx_id, to_transaction};
 use crate::ton::account_service_server::AccountService as BaseAccountService;
 use crate::ton::get_account_state_response::AccountState;
 use crate::ton::get_account_transactions_request::Order;
 use crate::ton::{
-    GetAccountStateRequest, GetAccountStateResponse, GetAccountTransactionsRequest,
-    GetShardAccountCellRequest, GetShardAccountCellResponse, Transaction,
+    AccountStateResult, GetAccountStateRequest, GetAccountStateResponse,
+    GetAccountStatesBatchRequest, GetAccountStatesBatchResponse, GetAccountTransactionsRequest,
+    GetLibrariesRequest, GetLibrariesResponse, GetShardAccountCellRequest,
+    GetShardAccountCellResponse, GetTraceRequest, GetTraceResponse,
+    SubscribeAccountTransactionsRequest, Transaction,
+};
+use crate::ton::{
+    account_state_result, get_account_state_request, get_account_states_batch_request,
+    get_shard_account_cell_request,
 };
-use crate::ton::{get_account_state_request, get_shard_account_cell_request};
 use anyhow::Result;
 use derive_new::new;
-use futures::{Stream, StreamExt, TryFutureExt, TryStreamExt, try_join};
+use futures::{Stream, StreamExt, TryFutureExt, TryStreamExt, future, try_join};
 use std::pin::Pin;
 use std::str::FromStr;
 use ton_address::SmartContractAddress;
+use ton_client::hash::normalize_hash;
 use ton_client::{Client, TonPoolService};
+use ton_tower::service::error::RequestError;
 use tonic::{Request, Response, Status, async_trait};
 
+const MAX_BATCH_SIZE: usize = 1000;
+const BATCH_CONCURRENCY: usize = 32;
+
 #[derive(new)]
 pub struct AccountService<S: TonPoolService> {
     client: Client<S>,
@@ -36,27 +47,106 @@ impl<S: TonPoolService> BaseAccountService for AccountService<S> {
             .fetch_account_state(&msg)
             .map_err(|e| Status::internal(e.to_string()))
             .await?;
-
-        let balance = state.balance.unwrap_or_default();
-        let last_transaction_id =
-            state
-                .last_transaction_id
+        let libraries = if msg.resolve_libraries {
+            self.client
                 .clone()
-                .map(|t| crate::ton::TransactionId {
-                    account_address: msg.account_address.clone(),
-                    lt: t.lt,
-                    hash: t.hash,
-                });
-        let account_state: AccountState = state.clone().into();
-        let block_id = state.block_id.into();
-
-        Ok(Response::new(GetAccountStateResponse {
-            balance,
-            account_address: msg.account_address,
-            block_id: Some(block_id),
-            last_transaction_id,
-            account_state: Some(account_state),
-        }))
+                .get_account_libraries(&state)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?
+        } else {
+            vec![]
+        };
+
+        let mut response = to_account_state_response(msg.account_address, state);
+        response.libraries = libraries.into_iter().map(Into::into).collect();
+
+        Ok(Response::new(response))
+    }
+
+    #[tracing::instrument(skip_all, err)]
+    async fn get_account_states_batch(
+        &self,
+        request: Request<GetAccountStatesBatchRequest>,
+    ) -> std::result::Result<Response<GetAccountStatesBatchResponse>, Status> {
+        let msg = request.into_inner();
+        if msg.account_addresses.is_empty() {
+            return Err(Status::invalid_argument("account addresses are required"));
+        }
+        if msg.account_addresses.len() > MAX_BATCH_SIZE {
+            return Err(Status::invalid_argument(format!(
+                "too many account addresses, max is {MAX_BATCH_SIZE}"
+            )));
+        }
+
+        let mut client = self.client.clone();
+        let criteria = match &msg.criteria {
+            None => {
+                let block_id = client
+                    .get_masterchain_info()
+                    .await
+                    .map_err(|e| Status::internal(e.to_string()))?
+                    .last;
+
+                BatchCriteria::AtLeastBlock(block_id)
+            }
+            Some(get_account_states_batch_request::Criteria::BlockId(block_id)) => {
+                let block_id = extend_block_id(&mut client, block_id)
+                    .await
+                    .map_err(|e| Status::internal(e.to_string()))?;
+
+                BatchCriteria::OnBlock(block_id)
+            }
+            Some(get_account_states_batch_request::Criteria::AtLeastBlockId(block_id)) => {
+                let block_id = extend_block_id(&mut client, block_id)
+                    .await
+                    .map_err(|e| Status::internal(e.to_string()))?;
+
+                BatchCriteria::AtLeastBlock(block_id)
+            }
+        };
+
+        let results = futures::stream::iter(msg.account_addresses)
+            .map(|account_address| {
+                let mut client = client.clone();
+                let criteria = &criteria;
+
+                async move {
+                    let state = async {
+                        let address = SmartContractAddress::from_str(&account_address)?;
+
+                        match criteria {
+                            BatchCriteria::OnBlock(block_id) => {
+                                client
+                                    .get_account_state_on_block(&address, block_id.clone())
+                                    .await
+                            }
+                            BatchCriteria::AtLeastBlock(block_id) => {
+                                client
+                                    .get_account_state_at_least_block(&address, block_id)
+                                    .await
+                            }
+                        }
+                    }
+                    .await;
+
+                    let result = match state {
+                        Ok(state) => account_state_result::Result::State(
+                            to_account_state_response(account_address.clone(), state),
+                        ),
+                        Err(e) => account_state_result::Result::Error(e.to_string()),
+                    };
+
+                    AccountStateResult {
+                        account_address,
+                        result: Some(result),
+                    }
+                }
+            })
+            .buffered(BATCH_CONCURRENCY)
+            .collect()
+            .await;
+
+        Ok(Response::new(GetAccountStatesBatchResponse { results }))
     }
 
     #[tracing::instrument(skip_all, err)]
@@ -91,6 +181,7 @@ impl<S: TonPoolService> BaseAccountService for AccountService<S> {
         let msg = request.into_inner();
         let account_address = SmartContractAddress::from_str(&msg.account_address)
             .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let include_description = msg.include_description;
 
         let mut client = self.client.clone();
         let mut client_to = client.clone();
@@ -110,8 +201,11 @@ impl<S: TonPoolService> BaseAccountService for AccountService<S> {
             Order::FromNewToOld => client
                 .get_account_tx_range(&account_address, (from_tx, to_tx))
                 .boxed(),
+            Order::FromOldToNew => client
+                .get_account_tx_range_asc(&account_address, (from_tx, to_tx))
+                .boxed(),
         }
-        .map_ok(move |t| t.into())
+        .and_then(move |t| future::ready(to_transaction(t, include_description)))
         .map_err(|e: anyhow::Error| {
             tracing::error!(error = %e, "get_account_transactions failed");
             Status::internal(e.to_string())
@@ -120,6 +214,148 @@ impl<S: TonPoolService> BaseAccountService for AccountService<S> {
 
         Ok(Response::new(stream))
     }
+
+    type SubscribeAccountTransactionsStream =
+        Pin<Box<dyn Stream<Item = Result<Transaction, Status>> + Send + 'static>>;
+
+    #[tracing::instrument(skip_all, err)]
+    async fn subscribe_account_transactions(
+        &self,
+        request: Request<SubscribeAccountTransactionsRequest>,
+    ) -> std::result::Result<Response<Self::SubscribeAccountTransactionsStream>, Status> {
+        let msg = request.into_inner();
+        if msg.account_addresses.is_empty() {
+            return Err(Status::invalid_argument("account addresses are required"));
+        }
+
+        let addresses = msg
+            .account_addresses
+            .iter()
+            .map(|a| SmartContractAddress::from_str(a))
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let after = match msg.after {
+            Some(after) => {
+                if after.lt <= 0 || after.hash.is_empty() {
+                    return Err(Status::invalid_argument(
+                        "transaction id requires lt and hash",
+                    ));
+                }
+                let mut tx: ton_tower::response::TransactionId = after.into();
+                tx.hash = normalize_hash(&tx.hash);
+                let block = self
+                    .client
+                    .clone()
+                    .find_account_transaction_block(&addresses, &tx)
+                    .await
+                    .map_err(|e| Status::internal(e.to_string()))?
+                    .ok_or_else(|| {
+                        Status::not_found(format!(
+                            "transaction {}:{} of the accounts is not found",
+                            tx.lt, tx.hash
+                        ))
+                    })?;
+
+                Some((tx, block))
+            }
+            None => None,
+        };
+
+        let stream = self
+            .client
+            .get_accounts_tx_subscription_stream(&addresses, after)
+            .map_ok(move |t| t.into())
+            .map_err(|e: anyhow::Error| {
+                tracing::error!(error = %e, "subscribe_account_transactions failed");
+                Status::internal(e.to_string())
+            })
+            .boxed();
+
+        Ok(Response::new(stream))
+    }
+
+    #[tracing::instrument(skip_all, err)]
+    async fn get_trace(
+        &self,
+        request: Request<GetTraceRequest>,
+    ) -> std::result::Result<Response<GetTraceResponse>, Status> {
+        let msg = request.into_inner();
+        let address = SmartContractAddress::from_str(&msg.account_address)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let transaction_id = msg
+            .transaction_id
+            .ok_or_else(|| Status::invalid_argument("transaction id is required"))?;
+
+        let trace = self
+            .client
+            .clone()
+            .get_trace(&address, &transaction_id.into())
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(trace.into()))
+    }
+
+    #[tracing::instrument(skip_all, err)]
+    async fn get_libraries(
+        &self,
+        request: Request<GetLibrariesRequest>,
+    ) -> std::result::Result<Response<GetLibrariesResponse>, Status> {
+        let msg = request.into_inner();
+        if msg.hashes.is_empty() {
+            return Err(Status::invalid_argument("library hashes are required"));
+        }
+
+        let libraries = self
+            .client
+            .clone()
+            .get_libraries(msg.hashes)
+            .await
+            .map_err(|e| {
+                if RequestError::is_cause_of(&*e) {
+                    Status::invalid_argument(e.to_string())
+                } else {
+                    Status::internal(e.to_string())
+                }
+            })?;
+
+        Ok(Response::new(GetLibrariesResponse {
+            libraries: libraries.into_iter().map(Into::into).collect(),
+        }))
+    }
+}
+
+enum BatchCriteria {
+    OnBlock(ton_tower::response::BlockIdExt),
+    AtLeastBlock(ton_tower::response::BlockIdExt),
+}
+
+fn to_account_state_response(
+    account_address: String,
+    state: ton_tower::response::AccountState,
+) -> GetAccountStateResponse {
+    let balance = state.balance.unwrap_or_default();
+    let last_transaction_id =
+        state
+            .last_transaction_id
+            .clone()
+            .map(|t| crate::ton::TransactionId {
+                account_address: account_address.clone(),
+                lt: t.lt,
+                hash: t.hash,
+            });
+    let account_state: AccountState = state.clone().into();
+    let block_id = state.block_id.into();
+
+    GetAccountStateResponse {
+        balance,
+        account_address,
+        block_id: Some(block_id),
+        last_transaction_id,
+        account_state: Some(account_state),
+        libraries: vec![],
+    }
 }
 
 impl<S: TonPoolService> AccountService<S> {
@@ -221,14 +457,19 @@ mod integration {
     use crate::account::AccountService;
     use crate::ton::account_service_client::AccountServiceClient;
     use crate::ton::account_service_server::AccountServiceServer;
+    use crate::ton::transaction_description::AccountStatus;
     use crate::ton::{
-        BlockId, GetAccountStateRequest, GetAccountTransactionsRequest, GetShardAccountCellRequest,
-        get_account_state_request, get_shard_account_cell_request,
+        BlockId, GetAccountStateRequest, GetAccountStatesBatchRequest,
+        GetAccountTransactionsRequest, GetLibrariesRequest, GetShardAccountCellRequest,
+        GetTraceRequest, PartialTransactionId, SubscribeAccountTransactionsRequest,
+        account_state_result, get_account_state_request, get_account_states_batch_request,
+        get_shard_account_cell_request,
     };
     use futures::StreamExt;
     use testcontainers_ton::{LocalLiteServer, SharedLiteServer};
     use tokio::net::TcpListener;
-    use ton_client::TonClientBuilder;
+    use ton_client::{Client, TonClientBuilder, TonPoolService};
+    use ton_liteserver_client::MakeLiteServerAdapter;
     use tonic::transport::Channel;
     use tonlibjson_client::MakeTonlibjsonAdapter;
 
@@ -243,6 +484,7 @@ mod integration {
             .get_account_state(GetAccountStateRequest {
                 account_address: ACCOUNT_ADDRESS.to_string(),
                 criteria: None,
+                resolve_libraries: false,
             })
             .await
             .unwrap()
@@ -272,6 +514,7 @@ mod integration {
             .get_account_state(GetAccountStateRequest {
                 account_address: ACCOUNT_ADDRESS.to_string(),
                 criteria: None,
+                resolve_libraries: false,
             })
             .await
             .unwrap()
@@ -288,6 +531,7 @@ mod integration {
                     root_hash: Some(block_id.root_hash),
                     file_hash: Some(block_id.file_hash),
                 })),
+                resolve_libraries: false,
             })
             .await
             .unwrap()
@@ -309,6 +553,7 @@ mod integration {
             .get_account_state(GetAccountStateRequest {
                 account_address: ACCOUNT_ADDRESS.to_string(),
                 criteria: None,
+                resolve_libraries: false,
             })
             .await
             .unwrap()
@@ -324,6 +569,7 @@ mod integration {
                         lt: last_tx.lt,
                     },
                 )),
+                resolve_libraries: false,
             })
             .await
             .unwrap()
@@ -337,6 +583,141 @@ mod integration {
         ));
     }
 
+    #[tokio::test]
+    async fn should_get_account_state_without_libraries() {
+        let (_server, mut accounts) = setup().await;
+
+        let resp = accounts
+            .get_account_state(GetAccountStateRequest {
+                account_address: ACCOUNT_ADDRESS.to_string(),
+                criteria: None,
+                resolve_libraries: true,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(resp.libraries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_fail_get_libraries_of_invalid_hash() {
+        let (_server, mut accounts) = setup().await;
+
+        let err = accounts
+            .get_libraries(GetLibrariesRequest {
+                hashes: vec!["invalid".to_string()],
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn should_fail_get_libraries_of_invalid_hash_on_liteserver() {
+        let (_server, mut accounts) = setup_liteserver().await;
+
+        let err = accounts
+            .get_libraries(GetLibrariesRequest {
+                hashes: vec!["invalid".to_string()],
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn should_get_account_states_batch_in_input_order() {
+        let (_server, mut accounts) = setup().await;
+        let addresses = vec![
+            ACCOUNT_ADDRESS.to_string(),
+            "invalid".to_string(),
+            "-1:3333333333333333333333333333333333333333333333333333333333333333".to_string(),
+        ];
+
+        let resp = accounts
+            .get_account_states_batch(GetAccountStatesBatchRequest {
+                account_addresses: addresses.clone(),
+                criteria: None,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        let actual: Vec<_> = resp
+            .results
+            .iter()
+            .map(|r| r.account_address.clone())
+            .collect();
+        assert_eq!(actual, addresses);
+        let Some(account_state_result::Result::State(state)) = &resp.results[0].result else {
+            panic!("expected state, got {:?}", resp.results[0].result);
+        };
+        assert_eq!(state.balance, 10000000000);
+        assert!(matches!(
+            resp.results[1].result,
+            Some(account_state_result::Result::Error(_))
+        ));
+        assert!(matches!(
+            resp.results[2].result,
+            Some(account_state_result::Result::State(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_get_account_states_batch_on_block() {
+        let (_server, mut accounts) = setup().await;
+        let state = accounts
+            .get_account_state(GetAccountStateRequest {
+                account_address: ACCOUNT_ADDRESS.to_string(),
+                criteria: None,
+                resolve_libraries: false,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        let block_id = state.block_id.unwrap();
+
+        let resp = accounts
+            .get_account_states_batch(GetAccountStatesBatchRequest {
+                account_addresses: vec![ACCOUNT_ADDRESS.to_string()],
+                criteria: Some(get_account_states_batch_request::Criteria::BlockId(
+                    BlockId {
+                        workchain: block_id.workchain,
+                        shard: block_id.shard,
+                        seqno: block_id.seqno,
+                        root_hash: Some(block_id.root_hash.clone()),
+                        file_hash: Some(block_id.file_hash.clone()),
+                    },
+                )),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(account_state_result::Result::State(actual)) = &resp.results[0].result else {
+            panic!("expected state, got {:?}", resp.results[0].result);
+        };
+        assert_eq!(actual.block_id.as_ref().unwrap().seqno, block_id.seqno);
+    }
+
+    #[tokio::test]
+    async fn should_fail_get_account_states_batch_without_addresses() {
+        let (_server, mut accounts) = setup().await;
+
+        let err = accounts
+            .get_account_states_batch(GetAccountStatesBatchRequest {
+                account_addresses: vec![],
+                criteria: None,
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
     #[tokio::test]
     async fn should_get_shard_account_cell() {
         let (_server, mut accounts) = setup().await;
@@ -402,6 +783,7 @@ mod integration {
                 order: crate::ton::get_account_transactions_request::Order::FromNewToOld as i32,
                 from: None,
                 to: None,
+                include_description: false,
             })
             .await
             .unwrap()
@@ -421,6 +803,212 @@ mod integration {
         }
     }
 
+    #[tokio::test]
+    async fn should_get_account_transactions_with_description() {
+        let (_server, mut accounts) = setup().await;
+
+        let stream = accounts
+            .get_account_transactions(GetAccountTransactionsRequest {
+                account_address: ACCOUNT_ADDRESS.to_string(),
+                order: crate::ton::get_account_transactions_request::Order::FromNewToOld as i32,
+                from: None,
+                to: None,
+                include_description: true,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        let txs: Vec<_> = stream.take(5).collect().await;
+
+        assert!(!txs.is_empty());
+        for tx in &txs {
+            let description = tx.as_ref().unwrap().description.as_ref().unwrap();
+            assert_eq!(description.end_status(), AccountStatus::Active);
+            assert!(description.compute_ph.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn should_get_account_transactions_from_old_to_new() {
+        let (_server, mut accounts) = setup().await;
+
+        let stream = accounts
+            .get_account_transactions(GetAccountTransactionsRequest {
+                account_address: ACCOUNT_ADDRESS.to_string(),
+                order: crate::ton::get_account_transactions_request::Order::FromOldToNew as i32,
+                from: None,
+                to: None,
+                include_description: false,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        let txs: Vec<_> = stream.take(5).map(|tx| tx.unwrap()).collect().await;
+
+        assert_eq!(txs.len(), 5);
+        for pair in txs.windows(2) {
+            let prev = pair[0].id.as_ref().unwrap();
+            let next = pair[1].id.as_ref().unwrap();
+            assert_eq!(next.account_address, ACCOUNT_ADDRESS);
+            assert!(next.lt > prev.lt);
+        }
+    }
+
+    #[tokio::test]
+    async fn should_subscribe_account_transactions_and_resume_from_cursor() {
+        let (_server, mut accounts) = setup().await;
+
+        let stream = accounts
+            .subscribe_account_transactions(SubscribeAccountTransactionsRequest {
+                account_addresses: vec![ACCOUNT_ADDRESS.to_string()],
+                after: None,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        let txs: Vec<_> = stream.take(2).map(|tx| tx.unwrap()).collect().await;
+
+        assert_eq!(txs.len(), 2);
+        let first = txs[0].id.as_ref().unwrap();
+        let second = txs[1].id.as_ref().unwrap();
+        assert_eq!(first.account_address, ACCOUNT_ADDRESS);
+        assert!(second.lt > first.lt);
+
+        let resumed = accounts
+            .subscribe_account_transactions(SubscribeAccountTransactionsRequest {
+                account_addresses: vec![ACCOUNT_ADDRESS.to_string()],
+                after: Some(PartialTransactionId {
+                    hash: first.hash.clone(),
+                    lt: first.lt,
+                }),
+            })
+            .await
+            .unwrap()
+            .into_inner()
+            .take(1)
+            .map(|tx| tx.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(resumed[0].id.as_ref(), Some(second));
+    }
+
+    #[tokio::test]
+    async fn should_resume_subscription_from_url_safe_cursor() {
+        let (_server, mut accounts) = setup().await;
+
+        let stream = accounts
+            .subscribe_account_transactions(SubscribeAccountTransactionsRequest {
+                account_addresses: vec![ACCOUNT_ADDRESS.to_string()],
+                after: None,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        let txs: Vec<_> = stream.take(2).map(|tx| tx.unwrap()).collect().await;
+        let first = txs[0].id.as_ref().unwrap();
+        let second = txs[1].id.as_ref().unwrap();
+
+        let resumed = accounts
+            .subscribe_account_transactions(SubscribeAccountTransactionsRequest {
+                account_addresses: vec![ACCOUNT_ADDRESS.to_string()],
+                after: Some(PartialTransactionId {
+                    hash: first.hash.replace('+', "-").replace('/', "_"),
+                    lt: first.lt,
+                }),
+            })
+            .await
+            .unwrap()
+            .into_inner()
+            .take(1)
+            .map(|tx| tx.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(resumed[0].id.as_ref(), Some(second));
+    }
+
+    #[tokio::test]
+    async fn should_not_find_unknown_subscription_cursor() {
+        let (_server, mut accounts) = setup().await;
+
+        let status = accounts
+            .subscribe_account_transactions(SubscribeAccountTransactionsRequest {
+                account_addresses: vec![ACCOUNT_ADDRESS.to_string()],
+                after: Some(PartialTransactionId {
+                    hash: "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string(),
+                    lt: 1,
+                }),
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn should_reject_subscription_cursor_without_lt() {
+        let (_server, mut accounts) = setup().await;
+
+        let status = accounts
+            .subscribe_account_transactions(SubscribeAccountTransactionsRequest {
+                account_addresses: vec![ACCOUNT_ADDRESS.to_string()],
+                after: Some(PartialTransactionId {
+                    hash: "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string(),
+                    lt: 0,
+                }),
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn should_get_trace() {
+        let (_server, mut accounts) = setup().await;
+        let state = accounts
+            .get_account_state(GetAccountStateRequest {
+                account_address: ACCOUNT_ADDRESS.to_string(),
+                criteria: None,
+                resolve_libraries: false,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        let last_tx = state.last_transaction_id.unwrap();
+
+        let resp = accounts
+            .get_trace(GetTraceRequest {
+                account_address: ACCOUNT_ADDRESS.to_string(),
+                transaction_id: Some(PartialTransactionId {
+                    hash: last_tx.hash.clone(),
+                    lt: last_tx.lt,
+                }),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        let root = resp.root.unwrap().transaction.unwrap().id.unwrap();
+        assert_eq!(root.hash, last_tx.hash);
+        assert_eq!(root.lt, last_tx.lt);
+    }
+
+    #[tokio::test]
+    async fn should_fail_get_trace_without_transaction_id() {
+        let (_server, mut accounts) = setup().await;
+
+        let result = accounts
+            .get_trace(GetTraceRequest {
+                account_address: ACCOUNT_ADDRESS.to_string(),
+                transaction_id: None,
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+    }
+
     async fn setup() -> (SharedLiteServer, AccountServiceClient<Channel>) {
         let server = LocalLiteServer::shared().await.unwrap();
         let mut client = TonClientBuilder::<MakeTonlibjsonAdapter>::from_config(server.config())
@@ -428,6 +1016,20 @@ mod integration {
             .unwrap();
         client.wait_ready().await.unwrap();
 
+        (server, serve(client).await)
+    }
+
+    async fn setup_liteserver() -> (SharedLiteServer, AccountServiceClient<Channel>) {
+        let server = LocalLiteServer::shared().await.unwrap();
+        let mut client = TonClientBuilder::<MakeLiteServerAdapter>::from_config(server.config())
+            .build()
+            .unwrap();
+        client.wait_ready().await.unwrap();
+
+        (server, serve(client).await)
+    }
+
+    async fn serve<S: TonPoolService>(client: Client<S>) -> AccountServiceClient<Channel> {
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let addr = listener.local_addr().unwrap();
 
@@ -445,6 +1047,6 @@ mod integration {
             .await
             .unwrap();
 
-        (server, AccountServiceClient::new(channel))
+        AccountServiceClient::new(channel)
     }
 }
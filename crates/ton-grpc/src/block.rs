@@ -1,16 +1,18 @@
 #![allow(clippy::blocks_in_conditions)]
 
-use crate::helpers::{extend_block_id, extend_get_block_header};
+use crate::helpers::{extend_block_id, extend_get_block_header, to_transaction};
 use crate::ton::block_service_server::BlockService as BaseBlockService;
 use crate::ton::get_transaction_ids_request::Order;
+use crate::ton::get_transactions_request::Order as TransactionsOrder;
 use crate::ton::{
     AccountAddress, BlockId, BlockIdExt, BlocksHeader, GetLastBlockRequest, GetShardsResponse,
-    GetTransactionIdsRequest, GetTransactionsRequest, Transaction, TransactionId,
+    GetTransactionIdsRequest, GetTransactionsRequest, ShardBlockProof, SubscribeBlocksRequest,
+    SubscribeBlocksResponse, Transaction, TransactionId,
 };
 use anyhow::Context;
 use derive_new::new;
 use futures::stream::BoxStream;
-use futures::{StreamExt, TryStreamExt};
+use futures::{StreamExt, TryStreamExt, future};
 use ton_client::{Client, TonService};
 use tonic::{Request, Response, Status, async_trait};
 
@@ -79,6 +81,24 @@ impl<S: TonService> BaseBlockService for BlockService<S> {
         }))
     }
 
+    #[tracing::instrument(skip_all, err)]
+    async fn get_shard_block_proof(
+        &self,
+        request: Request<BlockId>,
+    ) -> Result<Response<ShardBlockProof>, Status> {
+        let mut client = self.client.clone();
+        let block_id = extend_block_id(&mut client, &request.into_inner())
+            .await
+            .map_err(|e: anyhow::Error| Status::internal(e.to_string()))?;
+
+        let proof = client
+            .get_shard_block_proof(block_id)
+            .await
+            .map_err(|e: anyhow::Error| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(proof.into()))
+    }
+
     type GetTransactionIdsStream = BoxStream<'static, Result<TransactionId, Status>>;
 
     #[tracing::instrument(skip_all, err)]
@@ -145,8 +165,11 @@ impl<S: TonService> BaseBlockService for BlockService<S> {
     ) -> Result<Response<Self::GetTransactionsStream>, Status> {
         let msg = request.into_inner();
 
-        // TODO[akostylev0]
-        let _order = msg.order();
+        let reverse = match msg.order() {
+            TransactionsOrder::Unordered | TransactionsOrder::Asc => false,
+            TransactionsOrder::Desc => true,
+        };
+        let include_description = msg.include_description;
         let block_id = msg
             .block_id
             .context("block id is required")
@@ -158,8 +181,30 @@ impl<S: TonService> BaseBlockService for BlockService<S> {
             .map_err(|e| Status::internal(e.to_string()))?;
 
         let stream = client
-            .get_block_tx_stream(&block_id, false)
-            .map_ok(|tx| tx.into())
+            .get_block_tx_stream(&block_id, reverse)
+            .and_then(move |tx| future::ready(to_transaction(tx, include_description)))
+            .map_err(|e| Status::internal(e.to_string()))
+            .boxed();
+
+        Ok(Response::new(stream))
+    }
+
+    type SubscribeBlocksStream = BoxStream<'static, Result<SubscribeBlocksResponse, Status>>;
+
+    #[tracing::instrument(skip_all, err)]
+    async fn subscribe_blocks(
+        &self,
+        request: Request<SubscribeBlocksRequest>,
+    ) -> Result<Response<Self::SubscribeBlocksStream>, Status> {
+        let msg = request.into_inner();
+
+        let stream = self
+            .client
+            .get_masterchain_block_stream_from(msg.from_seqno)
+            .map_ok(|(block_id, shards)| SubscribeBlocksResponse {
+                block_id: Some(block_id.into()),
+                shards: shards.into_iter().map(|s| s.into()).collect(),
+            })
             .map_err(|e| Status::internal(e.to_string()))
             .boxed();
 
@@ -172,13 +217,17 @@ mod integration {
     use crate::block::BlockService;
     use crate::ton::block_service_client::BlockServiceClient;
     use crate::ton::block_service_server::BlockServiceServer;
+    use crate::ton::get_transactions_request::Order as TransactionsOrder;
+    use crate::ton::transaction_description::AccountStatus;
     use crate::ton::{
         BlockId, GetLastBlockRequest, GetTransactionIdsRequest, GetTransactionsRequest,
+        SubscribeBlocksRequest,
     };
     use futures::StreamExt;
     use testcontainers_ton::{LocalLiteServer, SharedLiteServer};
     use tokio::net::TcpListener;
-    use ton_client::TonClientBuilder;
+    use ton_client::{Client, TonClientBuilder, TonService};
+    use ton_liteserver_client::MakeLiteServerAdapter;
     use tonic::transport::Channel;
     use tonlibjson_client::MakeTonlibjsonAdapter;
 
@@ -289,6 +338,45 @@ mod integration {
         }
     }
 
+    #[tokio::test]
+    async fn should_get_shard_block_proof() {
+        let (_server, mut client) = setup_liteserver().await;
+        let last = client
+            .get_last_block(GetLastBlockRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+        let shards = client
+            .get_shards(BlockId {
+                workchain: last.workchain,
+                shard: last.shard,
+                seqno: last.seqno,
+                root_hash: None,
+                file_hash: None,
+            })
+            .await
+            .unwrap()
+            .into_inner()
+            .shards;
+        let shard = shards.first().unwrap();
+
+        let resp = client
+            .get_shard_block_proof(BlockId {
+                workchain: shard.workchain,
+                shard: shard.shard,
+                seqno: shard.seqno,
+                root_hash: Some(shard.root_hash.clone()),
+                file_hash: Some(shard.file_hash.clone()),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(resp.masterchain_id.unwrap().workchain, -1);
+        assert_eq!(resp.links.last().unwrap().id.as_ref(), Some(shard));
+        assert!(resp.links.iter().all(|link| !link.proof.is_empty()));
+    }
+
     #[tokio::test]
     async fn should_get_transaction_ids() {
         let (_server, mut client) = setup().await;
@@ -371,6 +459,7 @@ mod integration {
                     file_hash: None,
                 }),
                 order: crate::ton::get_transactions_request::Order::Asc as i32,
+                include_description: false,
             })
             .await
             .unwrap()
@@ -390,6 +479,91 @@ mod integration {
         }
     }
 
+    #[tokio::test]
+    async fn should_get_transactions_with_description() {
+        let (_server, mut client) = setup().await;
+        let last = client
+            .get_last_block(GetLastBlockRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+
+        let stream = client
+            .get_transactions(GetTransactionsRequest {
+                block_id: Some(BlockId {
+                    workchain: last.workchain,
+                    shard: last.shard,
+                    seqno: last.seqno,
+                    root_hash: None,
+                    file_hash: None,
+                }),
+                order: crate::ton::get_transactions_request::Order::Asc as i32,
+                include_description: true,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        let txs: Vec<_> = stream.take(5).collect().await;
+
+        assert!(!txs.is_empty());
+        for tx in &txs {
+            let description = tx.as_ref().unwrap().description.as_ref().unwrap();
+            assert_eq!(description.end_status(), AccountStatus::Active);
+            assert!(description.compute_ph.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn should_get_transactions_desc() {
+        let (_server, client) = setup().await;
+
+        assert_transactions_desc_reverse_asc(client).await;
+    }
+
+    #[tokio::test]
+    async fn should_get_transactions_desc_via_liteserver() {
+        let (_server, client) = setup_liteserver().await;
+
+        assert_transactions_desc_reverse_asc(client).await;
+    }
+
+    async fn assert_transactions_desc_reverse_asc(mut client: BlockServiceClient<Channel>) {
+        let last = client
+            .get_last_block(GetLastBlockRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+        let block_id = BlockId {
+            workchain: last.workchain,
+            shard: last.shard,
+            seqno: last.seqno,
+            root_hash: Some(last.root_hash),
+            file_hash: Some(last.file_hash),
+        };
+
+        let mut ids = Vec::new();
+        for order in [TransactionsOrder::Asc, TransactionsOrder::Desc] {
+            let stream = client
+                .get_transactions(GetTransactionsRequest {
+                    block_id: Some(block_id.clone()),
+                    order: order as i32,
+                    include_description: false,
+                })
+                .await
+                .unwrap()
+                .into_inner();
+            let txs: Vec<_> = stream.map(|tx| tx.unwrap().id.unwrap()).collect().await;
+
+            ids.push(txs);
+        }
+        let desc = ids.pop().unwrap();
+        let mut asc = ids.pop().unwrap();
+        asc.reverse();
+
+        assert!(!desc.is_empty());
+        assert_eq!(desc, asc);
+    }
+
     #[tokio::test]
     async fn should_have_correct_message_address_format() {
         let (_server, mut client) = setup().await;
@@ -408,6 +582,7 @@ mod integration {
                     file_hash: None,
                 }),
                 order: crate::ton::get_transactions_request::Order::Asc as i32,
+                include_description: false,
             })
             .await
             .unwrap()
@@ -452,6 +627,40 @@ mod integration {
         }
     }
 
+    #[tokio::test]
+    async fn should_subscribe_blocks_from_seqno() {
+        let (_server, mut client) = setup().await;
+        let last = client
+            .get_last_block(GetLastBlockRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+        let from_seqno = last.seqno - 2;
+
+        let stream = client
+            .subscribe_blocks(SubscribeBlocksRequest {
+                from_seqno: Some(from_seqno),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        let blocks: Vec<_> = stream.take(4).collect().await;
+
+        assert_eq!(blocks.len(), 4);
+        assert!(!blocks[0].as_ref().unwrap().shards.is_empty());
+        for (i, block) in blocks.iter().enumerate() {
+            let block = block.as_ref().unwrap();
+            let id = block.block_id.as_ref().unwrap();
+            assert_eq!(id.workchain, -1);
+            assert_eq!(id.seqno, from_seqno + i as i32);
+            assert_eq!(id.root_hash.len(), 44);
+            for shard in &block.shards {
+                assert_eq!(shard.workchain, 0);
+                assert_eq!(shard.root_hash.len(), 44);
+            }
+        }
+    }
+
     fn is_raw_address(addr: &str) -> bool {
         if addr.is_empty() {
             return true;
@@ -471,6 +680,20 @@ mod integration {
             .unwrap();
         client.wait_ready().await.unwrap();
 
+        (server, serve(client).await)
+    }
+
+    async fn setup_liteserver() -> (SharedLiteServer, BlockServiceClient<Channel>) {
+        let server = LocalLiteServer::shared().await.unwrap();
+        let mut client = TonClientBuilder::<MakeLiteServerAdapter>::from_config(server.config())
+            .build()
+            .unwrap();
+        client.wait_ready().await.unwrap();
+
+        (server, serve(client).await)
+    }
+
+    async fn serve<S: TonService>(client: Client<S>) -> BlockServiceClient<Channel> {
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let addr = listener.local_addr().unwrap();
 
@@ -488,6 +711,6 @@ mod integration {
             .await
             .unwrap();
 
-        (server, BlockServiceClient::new(channel))
+        BlockServiceClient::new(channel)
     }
 }
@@ -0,0 +1,225 @@
+use crate::helpers::extend_block_id;
+use crate::ton;
+use crate::ton::config_service_server::ConfigService as BaseConfigService;
+use crate::ton::{
+    GetConfigAllRequest, GetConfigAllResponse, GetConfigParamRequest, GetConfigParamResponse,
+};
+use anyhow::Result;
+use derive_new::new;
+use ton_client::{Client, TonService};
+use ton_liteserver_client::adapter::decode_config_param;
+use ton_tower::response::BlockIdExt;
+use tonic::{Request, Response, Status, async_trait};
+
+#[derive(new)]
+pub struct ConfigService<S: TonService> {
+    client: Client<S>,
+}
+
+#[async_trait]
+impl<S: TonService> BaseConfigService for ConfigService<S> {
+    #[tracing::instrument(skip_all, err)]
+    async fn get_config_all(
+        &self,
+        request: Request<GetConfigAllRequest>,
+    ) -> std::result::Result<Response<GetConfigAllResponse>, Status> {
+        let msg = request.into_inner();
+        let mut client = self.client.clone();
+
+        let block_id = self
+            .resolve_block_id(msg.block_id.as_ref())
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let config = client
+            .get_config_all(&block_id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(GetConfigAllResponse {
+            block_id: Some(block_id.into()),
+            config: Some(config.into()),
+        }))
+    }
+
+    #[tracing::instrument(skip_all, err)]
+    async fn get_config_param(
+        &self,
+        request: Request<GetConfigParamRequest>,
+    ) -> std::result::Result<Response<GetConfigParamResponse>, Status> {
+        let msg = request.into_inner();
+        if msg.param < 0 {
+            return Err(Status::invalid_argument(format!(
+                "config param {} is negative",
+                msg.param
+            )));
+        }
+        let mut client = self.client.clone();
+
+        let block_id = self
+            .resolve_block_id(msg.block_id.as_ref())
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let cell = client
+            .get_config_param(&block_id, msg.param)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        if cell.bytes.is_empty() {
+            return Err(Status::not_found(format!(
+                "config param {} not found",
+                msg.param
+            )));
+        }
+        let value =
+            decode_config_param(msg.param, &cell).map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(GetConfigParamResponse {
+            block_id: Some(block_id.into()),
+            param: msg.param,
+            cell: Some(cell.into()),
+            value: value.map(Into::into),
+        }))
+    }
+}
+
+impl<S: TonService> ConfigService<S> {
+    async fn resolve_block_id(&self, block_id: Option<&ton::BlockId>) -> Result<BlockIdExt> {
+        let mut client = self.client.clone();
+
+        match block_id {
+            None => Ok(client.get_masterchain_info().await?.last),
+            Some(block_id) => extend_block_id(&mut client, block_id).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod integration {
+    use crate::config::ConfigService;
+    use crate::ton::config_service_client::ConfigServiceClient;
+    use crate::ton::config_service_server::ConfigServiceServer;
+    use crate::ton::get_config_param_response::Value;
+    use crate::ton::{BlockId, GetConfigAllRequest, GetConfigParamRequest};
+    use testcontainers_ton::{LocalLiteServer, SharedLiteServer};
+    use tokio::net::TcpListener;
+    use ton_client::TonClientBuilder;
+    use tonic::transport::Channel;
+    use tonlibjson_client::MakeTonlibjsonAdapter;
+
+    #[tokio::test]
+    async fn should_get_config_all() {
+        let (_server, mut client) = setup().await;
+
+        let resp = client
+            .get_config_all(GetConfigAllRequest { block_id: None })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(resp.block_id.unwrap().workchain, -1);
+        assert!(!resp.config.unwrap().bytes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_get_config_param_validator_set() {
+        let (_server, mut client) = setup().await;
+
+        let resp = client
+            .get_config_param(GetConfigParamRequest {
+                block_id: None,
+                param: 34,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(resp.param, 34);
+        assert!(!resp.cell.unwrap().bytes.is_empty());
+        let Some(Value::ValidatorSet(validators)) = resp.value else {
+            panic!("validator set expected, got {:?}", resp.value);
+        };
+        assert!(validators.total >= 1);
+        assert_eq!(validators.list.len(), validators.total as usize);
+    }
+
+    #[tokio::test]
+    async fn should_get_config_param_on_block() {
+        let (_server, mut client) = setup().await;
+
+        let resp = client
+            .get_config_param(GetConfigParamRequest {
+                block_id: Some(BlockId {
+                    workchain: -1,
+                    shard: i64::MIN,
+                    seqno: 1,
+                    root_hash: None,
+                    file_hash: None,
+                }),
+                param: 21,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(resp.block_id.unwrap().seqno, 1);
+        assert!(matches!(resp.value, Some(Value::GasLimitsPrices(_))));
+    }
+
+    #[tokio::test]
+    async fn should_get_config_param_without_typed_value() {
+        let (_server, mut client) = setup().await;
+
+        let resp = client
+            .get_config_param(GetConfigParamRequest {
+                block_id: None,
+                param: 0,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(!resp.cell.unwrap().bytes.is_empty());
+        assert_eq!(resp.value, None);
+    }
+
+    #[tokio::test]
+    async fn should_not_find_missing_config_param() {
+        let (_server, mut client) = setup().await;
+
+        let err = client
+            .get_config_param(GetConfigParamRequest {
+                block_id: None,
+                param: 99999,
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
+    async fn setup() -> (SharedLiteServer, ConfigServiceClient<Channel>) {
+        let server = LocalLiteServer::shared().await.unwrap();
+        let mut client = TonClientBuilder::<MakeTonlibjsonAdapter>::from_config(server.config())
+            .build()
+            .unwrap();
+        client.wait_ready().await.unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(ConfigServiceServer::new(ConfigService::new(client)))
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+                .await
+                .unwrap();
+        });
+
+        let channel = Channel::from_shared(format!("http://{}", addr))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+
+        (server, ConfigServiceClient::new(channel))
+    }
+}
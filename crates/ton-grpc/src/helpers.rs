@@ -7,6 +7,18 @@ use std::ops::Bound::{Excluded, Included};
 use ton_address::SmartContractAddress;
 use ton_client::{Client, TonService};
 
+pub fn to_transaction(
+    tx: ton_tower::response::Transaction,
+    include_description: bool,
+) -> Result<ton::Transaction> {
+    let tx: ton::Transaction = tx.into();
+    if include_description {
+        tx.with_description()
+    } else {
+        Ok(tx)
+    }
+}
+
 #[tracing::instrument(skip_all, err)]
 pub async fn extend_block_id(
     client: &mut Client<impl TonService>,
@@ -1,14 +1,23 @@
 pub mod account;
 pub mod block;
+pub mod config;
 pub mod helpers;
 pub mod message;
+pub mod smc;
 #[allow(clippy::enum_variant_names)]
 pub mod ton;
+pub mod transaction;
 
 pub use account::AccountService;
 pub use block::BlockService;
+pub use config::ConfigService;
 pub use message::MessageService;
+pub use smc::SmcService;
+pub use transaction::TransactionService;
 
 pub use ton::account_service_server;
 pub use ton::block_service_server;
+pub use ton::config_service_server;
 pub use ton::message_service_server;
+pub use ton::smc_service_server;
+pub use ton::transaction_service_server;
@@ -3,15 +3,24 @@ use humantime::parse_duration;
 use metrics_exporter_prometheus::PrometheusBuilder;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
+use ton_client::pool::OutlierConfig;
+use ton_client::tx_index::TransactionIndex;
 use ton_client::{ConfigSource, PoolTransport, TonClientBuilder, TonService};
 use ton_config::{TonConfig, default_ton_config_url};
 use ton_grpc::AccountService;
 use ton_grpc::BlockService;
+use ton_grpc::ConfigService;
 use ton_grpc::MessageService;
+use ton_grpc::SmcService;
+use ton_grpc::TransactionService;
 use ton_grpc::account_service_server::AccountServiceServer;
 use ton_grpc::block_service_server::BlockServiceServer;
+use ton_grpc::config_service_server::ConfigServiceServer;
 use ton_grpc::message_service_server::MessageServiceServer;
+use ton_grpc::smc_service_server::SmcServiceServer;
+use ton_grpc::transaction_service_server::TransactionServiceServer;
 use ton_liteserver_client::MakeLiteServerAdapter;
 use tonic::codec::CompressionEncoding::Gzip;
 use tonic::transport::Server;
@@ -74,6 +83,8 @@ struct AppArgs {
 
     #[clap(long, value_enum, default_value_t = ClientImpl::Tonlibjson)]
     client: ClientImpl,
+    #[clap(long)]
+    light_client: bool,
 
     #[clap(flatten)]
     ton_config_args: TonConfigArgs,
@@ -90,10 +101,53 @@ struct AppArgs {
     #[clap(long, value_parser = parse_duration, default_value = "4096ms")]
     retry_max_delay: Duration,
 
+    #[clap(long)]
+    hedge: bool,
+    #[clap(long, default_value_t = 0.95)]
+    hedge_latency_percentile: f64,
+    #[clap(long, default_value_t = 100)]
+    hedge_min_data_points: u64,
+    #[clap(long, value_parser = parse_duration, default_value = "10s")]
+    hedge_period: Duration,
+    #[clap(long, value_parser = parse_duration, default_value = "10s")]
+    hedge_budget_ttl: Duration,
+    #[clap(long, default_value_t = 10)]
+    hedge_min_rps: u32,
+    #[clap(long, default_value_t = 0.1)]
+    hedge_withdraw_percent: f32,
+
+    #[clap(long)]
+    outlier_detection: bool,
+    #[clap(long, default_value_t = 5)]
+    outlier_consecutive_errors: u32,
+    #[clap(long, default_value_t = 0.5)]
+    outlier_error_ratio: f64,
+    #[clap(long, default_value_t = 20)]
+    outlier_min_requests: u32,
+    #[clap(long, value_parser = parse_duration, default_value = "10s")]
+    outlier_window: Duration,
+    #[clap(long, value_parser = parse_duration, default_value = "5s")]
+    outlier_base_ejection: Duration,
+    #[clap(long, value_parser = parse_duration, default_value = "300s")]
+    outlier_max_ejection: Duration,
+
+    #[clap(long)]
+    emulator: bool,
+    #[clap(long)]
+    emulator_gas_limit: Option<i64>,
+
     #[clap(long, value_parser = parse_duration, default_value = "70ms")]
     ewma_default_rtt: Duration,
     #[clap(long, value_parser = parse_duration, default_value = "1ms")]
     ewma_decay: Duration,
+
+    #[clap(long, default_value_t = 0)]
+    cache_capacity: usize,
+    #[clap(long, value_parser = parse_duration)]
+    cache_ttl: Option<Duration>,
+
+    #[clap(long)]
+    tx_index_capacity: Option<usize>,
 }
 
 #[tokio::main]
@@ -115,8 +169,19 @@ async fn main() -> anyhow::Result<()> {
     }
 
     match args.client {
+        ClientImpl::Tonlibjson if args.light_client => {
+            anyhow::bail!("--light-client is only supported with --client adnl-tcp")
+        }
         ClientImpl::Tonlibjson => serve(args, MakeTonlibjsonAdapter).await,
-        ClientImpl::AdnlTcp => serve(args, MakeLiteServerAdapter).await,
+        ClientImpl::AdnlTcp => {
+            let factory = if args.light_client {
+                MakeLiteServerAdapter::default().with_light_client()
+            } else {
+                MakeLiteServerAdapter::default()
+            };
+
+            serve(args, factory).await
+        }
     }
 }
 
@@ -136,7 +201,7 @@ where
     }
     tracing::info!("Client implementation: {:?}", &args.client);
 
-    let mut client = TonClientBuilder::<F>::with_factory_and_source(factory, config_source)
+    let mut builder = TonClientBuilder::<F>::with_factory_and_source(factory, config_source)
         .set_timeout(args.ton_timeout)
         .set_retry_budget_ttl(args.retry_budget_ttl)
         .set_retry_min_per_sec(args.retry_min_rps)
@@ -145,7 +210,33 @@ where
         .set_retry_max_delay(args.retry_max_delay)
         .set_ewma_default_rtt(args.ewma_default_rtt)
         .set_ewma_decay(args.ewma_decay)
-        .build()?;
+        .set_cache_capacity(args.cache_capacity)
+        .set_cache_ttl(args.cache_ttl)
+        .set_hedge_latency_percentile(args.hedge_latency_percentile)
+        .set_hedge_min_data_points(args.hedge_min_data_points)
+        .set_hedge_period(args.hedge_period)
+        .set_hedge_budget_ttl(args.hedge_budget_ttl)
+        .set_hedge_min_per_sec(args.hedge_min_rps)
+        .set_hedge_percent(args.hedge_withdraw_percent)
+        .set_outlier_detection(OutlierConfig {
+            consecutive_errors: args.outlier_consecutive_errors,
+            error_ratio: args.outlier_error_ratio,
+            min_requests: args.outlier_min_requests,
+            window: args.outlier_window,
+            base_ejection: args.outlier_base_ejection,
+            max_ejection: args.outlier_max_ejection,
+        })
+        .set_emulator_gas_limit(args.emulator_gas_limit);
+    if args.hedge {
+        builder = builder.enable_hedge();
+    }
+    if args.outlier_detection {
+        builder = builder.enable_outlier_detection();
+    }
+    if args.emulator {
+        builder = builder.enable_emulator();
+    }
+    let mut client = builder.build()?;
 
     client.wait_ready().await?;
     tracing::info!("Ton Client is ready");
@@ -161,7 +252,26 @@ where
     let block_service = BlockServiceServer::new(BlockService::new(client.clone()))
         .accept_compressed(Gzip)
         .send_compressed(Gzip);
-    let message_service = MessageServiceServer::new(MessageService::new(client))
+    let config_service = ConfigServiceServer::new(ConfigService::new(client.clone()))
+        .accept_compressed(Gzip)
+        .send_compressed(Gzip);
+    let message_service = MessageServiceServer::new(MessageService::new(client.clone()))
+        .accept_compressed(Gzip)
+        .send_compressed(Gzip);
+    let transaction_service = args.tx_index_capacity.map(|capacity| {
+        let index = Arc::new(TransactionIndex::new(capacity));
+        tokio::spawn({
+            let index = index.clone();
+            let client = client.clone();
+            async move { index.run(client).await }
+        });
+        tracing::info!("Transaction index capacity: {}", capacity);
+
+        TransactionServiceServer::new(TransactionService::new(client.clone(), index))
+            .accept_compressed(Gzip)
+            .send_compressed(Gzip)
+    });
+    let smc_service = SmcServiceServer::new(SmcService::new(client))
         .accept_compressed(Gzip)
         .send_compressed(Gzip);
 
@@ -172,9 +282,20 @@ where
     health_reporter
         .set_serving::<BlockServiceServer<BlockService<PoolTransport<F>>>>()
         .await;
+    health_reporter
+        .set_serving::<ConfigServiceServer<ConfigService<PoolTransport<F>>>>()
+        .await;
     health_reporter
         .set_serving::<MessageServiceServer<MessageService<PoolTransport<F>>>>()
         .await;
+    health_reporter
+        .set_serving::<SmcServiceServer<SmcService<PoolTransport<F>>>>()
+        .await;
+    if transaction_service.is_some() {
+        health_reporter
+            .set_serving::<TransactionServiceServer<TransactionService<PoolTransport<F>>>>()
+            .await;
+    }
 
     tracing::info!("Listening on {:?}", &args.listen);
 
@@ -189,7 +310,10 @@ where
         .add_service(health_server)
         .add_service(account_service)
         .add_service(block_service)
+        .add_service(config_service)
         .add_service(message_service)
+        .add_service(smc_service)
+        .add_optional_service(transaction_service)
         .serve_with_shutdown(args.listen, async move {
             tokio::signal::ctrl_c().await.unwrap();
         })
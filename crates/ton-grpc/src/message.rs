@@ -1,9 +1,17 @@
 use crate::ton::message_service_server::MessageService as BaseMessageService;
-use crate::ton::{SendRequest, SendResponse};
+use crate::ton::{
+    EstimateFeeRequest, EstimateFeeResponse, SendMessageAndWaitRequest, SendRequest, SendResponse,
+    Transaction,
+};
 use derive_new::new;
+use std::time::Duration;
+use tokio::time::error::Elapsed;
 use ton_client::{Client, TonService};
+use ton_tower::service::error::RequestError;
 use tonic::{Request, Response, Status, async_trait};
 
+const DEFAULT_SEND_MESSAGE_AND_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
 #[derive(new)]
 pub struct MessageService<S: TonService> {
     client: Client<S>,
@@ -26,14 +34,61 @@ impl<S: TonService> BaseMessageService for MessageService<S> {
 
         Ok(Response::new(SendResponse { hash }))
     }
+
+    #[tracing::instrument(skip_all, err)]
+    async fn send_message_and_wait(
+        &self,
+        request: Request<SendMessageAndWaitRequest>,
+    ) -> Result<Response<Transaction>, Status> {
+        let msg = request.into_inner();
+        let timeout = msg
+            .timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_SEND_MESSAGE_AND_WAIT_TIMEOUT);
+
+        let mut client = self.client.clone();
+        let tx = client
+            .send_message_and_wait(&msg.body, timeout)
+            .await
+            .map_err(|e| {
+                if e.downcast_ref::<Elapsed>().is_some() {
+                    Status::deadline_exceeded(format!("{e:#}"))
+                } else if RequestError::is_cause_of(&*e) {
+                    Status::invalid_argument(e.to_string())
+                } else {
+                    Status::internal(e.to_string())
+                }
+            })?;
+
+        Ok(Response::new(tx.into()))
+    }
+
+    #[tracing::instrument(skip_all, err)]
+    async fn estimate_fee(
+        &self,
+        request: Request<EstimateFeeRequest>,
+    ) -> Result<Response<EstimateFeeResponse>, Status> {
+        let msg = request.into_inner();
+
+        let mut client = self.client.clone();
+        let fee = client.estimate_fee(&msg.body).await.map_err(|e| {
+            if RequestError::is_cause_of(&*e) {
+                Status::invalid_argument(e.to_string())
+            } else {
+                Status::internal(e.to_string())
+            }
+        })?;
+
+        Ok(Response::new(fee.into()))
+    }
 }
 
 #[cfg(test)]
 mod integration {
     use crate::message::MessageService;
-    use crate::ton::SendRequest;
     use crate::ton::message_service_client::MessageServiceClient;
     use crate::ton::message_service_server::MessageServiceServer;
+    use crate::ton::{EstimateFeeRequest, SendMessageAndWaitRequest, SendRequest};
     use testcontainers_ton::{LocalLiteServer, SharedLiteServer};
     use tokio::net::TcpListener;
     use ton_client::TonClientBuilder;
@@ -56,6 +111,33 @@ mod integration {
         assert_eq!(status.code(), tonic::Code::Internal);
     }
 
+    #[tokio::test]
+    async fn should_fail_send_and_wait_invalid_message() {
+        let (_server, mut client) = setup().await;
+
+        let result = client
+            .send_message_and_wait(SendMessageAndWaitRequest {
+                body: "invalid_boc".to_string(),
+                timeout_ms: Some(1000),
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn should_fail_estimate_fee_of_invalid_message() {
+        let (_server, mut client) = setup().await;
+
+        let result = client
+            .estimate_fee(EstimateFeeRequest {
+                body: "invalid_boc".to_string(),
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+    }
+
     async fn setup() -> (SharedLiteServer, MessageServiceClient<Channel>) {
         let server = LocalLiteServer::shared().await.unwrap();
         let mut client = TonClientBuilder::<MakeTonlibjsonAdapter>::from_config(server.config())
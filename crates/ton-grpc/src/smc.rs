@@ -0,0 +1,270 @@
+#![allow(clippy::blocks_in_conditions)]
+
+use crate::helpers::extend_block_id;
+use crate::ton::run_get_method_request::Criteria;
+use crate::ton::smc_service_server::SmcService as BaseSmcService;
+use crate::ton::{RunGetMethodRequest, RunGetMethodResponse};
+use anyhow::Result;
+use derive_new::new;
+use std::str::FromStr;
+use ton_address::SmartContractAddress;
+use ton_client::{Client, TonPoolService};
+use ton_tower::response::{SmcRunResult, StackEntry};
+use tonic::{Request, Response, Status, async_trait};
+
+#[derive(new)]
+pub struct SmcService<S: TonPoolService> {
+    client: Client<S>,
+}
+
+#[async_trait]
+impl<S: TonPoolService> BaseSmcService for SmcService<S> {
+    #[tracing::instrument(skip_all, err)]
+    async fn run_get_method(
+        &self,
+        request: Request<RunGetMethodRequest>,
+    ) -> std::result::Result<Response<RunGetMethodResponse>, Status> {
+        let msg = request.into_inner();
+        let account_address = SmartContractAddress::from_str(&msg.account_address)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let stack = msg
+            .stack
+            .iter()
+            .cloned()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<StackEntry>>>()
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let result = self
+            .fetch_run_result(&account_address, &msg, stack)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(RunGetMethodResponse {
+            account_address: msg.account_address,
+            gas_used: result.gas_used,
+            exit_code: result.exit_code,
+            stack: result.stack.into_iter().map(Into::into).collect(),
+        }))
+    }
+}
+
+impl<S: TonPoolService> SmcService<S> {
+    async fn fetch_run_result(
+        &self,
+        address: &SmartContractAddress,
+        msg: &RunGetMethodRequest,
+        stack: Vec<StackEntry>,
+    ) -> Result<SmcRunResult> {
+        let mut client = self.client.clone();
+
+        let result = match &msg.criteria {
+            None => {
+                let block_id = client.get_masterchain_info().await?.last;
+
+                client
+                    .run_get_method_at_least_block(address, &msg.method, stack, &block_id)
+                    .await?
+            }
+            Some(Criteria::AtLeastBlockId(block_id)) => {
+                let block_id = extend_block_id(&mut client, block_id).await?;
+
+                client
+                    .run_get_method_at_least_block(address, &msg.method, stack, &block_id)
+                    .await?
+            }
+            Some(Criteria::BlockId(block_id)) => {
+                let block_id = extend_block_id(&mut client, block_id).await?;
+
+                client
+                    .run_get_method_on_block(address, &msg.method, stack, &block_id)
+                    .await?
+            }
+            Some(Criteria::TransactionId(tx_id)) => {
+                let block_id = client
+                    .get_account_state_by_transaction(address, tx_id.clone().into())
+                    .await?
+                    .block_id;
+
+                client
+                    .run_get_method_on_block(address, &msg.method, stack, &block_id)
+                    .await?
+            }
+        };
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod integration {
+    use crate::smc::SmcService;
+    use crate::ton::smc_service_client::SmcServiceClient;
+    use crate::ton::smc_service_server::SmcServiceServer;
+    use crate::ton::stack_entry::Value;
+    use crate::ton::{BlockId, PartialTransactionId, RunGetMethodRequest, run_get_method_request};
+    use testcontainers_ton::{LocalLiteServer, SharedLiteServer};
+    use tokio::net::TcpListener;
+    use ton_client::TonClientBuilder;
+    use tonic::transport::Channel;
+    use tonlibjson_client::MakeTonlibjsonAdapter;
+
+    const FAUCET_WALLET_ADDR: &str =
+        "-1:22f53b7d9aba2cef44755f7078b01614cd4dde2388a1729c2c386cf8f9898afe";
+    const ELECTOR_ADDR: &str =
+        "-1:3333333333333333333333333333333333333333333333333333333333333333";
+
+    #[tokio::test]
+    async fn should_run_get_method() {
+        let (_server, mut client) = setup().await;
+
+        let resp = client
+            .run_get_method(RunGetMethodRequest {
+                account_address: FAUCET_WALLET_ADDR.to_string(),
+                method: "seqno".to_string(),
+                stack: vec![],
+                criteria: None,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(resp.account_address, FAUCET_WALLET_ADDR);
+        assert_eq!(resp.exit_code, 0);
+        assert_eq!(resp.stack.len(), 1);
+        assert!(matches!(resp.stack[0].value, Some(Value::Number(_))));
+    }
+
+    #[tokio::test]
+    async fn should_run_get_method_at_least_block() {
+        let (_server, mut client) = setup().await;
+
+        let resp = client
+            .run_get_method(RunGetMethodRequest {
+                account_address: FAUCET_WALLET_ADDR.to_string(),
+                method: "get_public_key".to_string(),
+                stack: vec![],
+                criteria: Some(run_get_method_request::Criteria::AtLeastBlockId(BlockId {
+                    workchain: -1,
+                    shard: i64::MIN,
+                    seqno: 1,
+                    root_hash: None,
+                    file_hash: None,
+                })),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(resp.exit_code, 0);
+        assert_eq!(
+            resp.stack[0].value,
+            Some(Value::Number(
+                "61538797250860244891658288584886086813375283594678556485491459892974908044290"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_run_get_method_on_block() {
+        let (_server, mut client) = setup().await;
+
+        let resp = client
+            .run_get_method(RunGetMethodRequest {
+                account_address: FAUCET_WALLET_ADDR.to_string(),
+                method: "seqno".to_string(),
+                stack: vec![],
+                criteria: Some(run_get_method_request::Criteria::BlockId(BlockId {
+                    workchain: -1,
+                    shard: i64::MIN,
+                    seqno: 1,
+                    root_hash: None,
+                    file_hash: None,
+                })),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(resp.exit_code, 0);
+        assert_eq!(resp.stack[0].value, Some(Value::Number("0".to_string())));
+    }
+
+    #[tokio::test]
+    async fn should_run_get_method_by_transaction() {
+        let (server, mut client) = setup().await;
+        let mut ton = TonClientBuilder::<MakeTonlibjsonAdapter>::from_config(server.config())
+            .build()
+            .unwrap();
+        ton.wait_ready().await.unwrap();
+        let tx_id = ton
+            .get_account_state(&ELECTOR_ADDR.parse().unwrap())
+            .await
+            .unwrap()
+            .last_transaction_id
+            .unwrap();
+
+        let resp = client
+            .run_get_method(RunGetMethodRequest {
+                account_address: ELECTOR_ADDR.to_string(),
+                method: "active_election_id".to_string(),
+                stack: vec![],
+                criteria: Some(run_get_method_request::Criteria::TransactionId(
+                    PartialTransactionId {
+                        hash: tx_id.hash,
+                        lt: tx_id.lt,
+                    },
+                )),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(resp.exit_code, 0);
+        assert!(matches!(resp.stack[0].value, Some(Value::Number(_))));
+    }
+
+    #[tokio::test]
+    async fn should_fail_run_get_method_with_invalid_address() {
+        let (_server, mut client) = setup().await;
+
+        let result = client
+            .run_get_method(RunGetMethodRequest {
+                account_address: "invalid".to_string(),
+                method: "seqno".to_string(),
+                stack: vec![],
+                criteria: None,
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+    }
+
+    async fn setup() -> (SharedLiteServer, SmcServiceClient<Channel>) {
+        let server = LocalLiteServer::shared().await.unwrap();
+        let mut client = TonClientBuilder::<MakeTonlibjsonAdapter>::from_config(server.config())
+            .build()
+            .unwrap();
+        client.wait_ready().await.unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(SmcServiceServer::new(SmcService::new(client)))
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+                .await
+                .unwrap();
+        });
+
+        let channel = Channel::from_shared(format!("http://{}", addr))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+
+        (server, SmcServiceClient::new(channel))
+    }
+}
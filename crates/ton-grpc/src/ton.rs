@@ -1,5 +1,13 @@
+use crate::ton::decoded_body::Body;
 use crate::ton::get_account_state_response::AccountState;
+use crate::ton::get_config_param_response::Value as ConfigParamValue;
 use crate::ton::message::MsgData;
+use crate::ton::stack_entry::Value;
+use anyhow::anyhow;
+use ton_liteserver_client::adapter::decode_transaction;
+use ton_liteserver_client::tlb;
+use ton_liteserver_client::tlb::config_param::ConfigParam;
+use ton_liteserver_client::tlb::validator_set::ValidatorDescr;
 
 tonic::include_proto!("ton");
 
@@ -106,6 +114,123 @@ impl From<ton_tower::response::Cell> for TvmCell {
     }
 }
 
+impl From<ton_tower::response::ShardBlockProof> for ShardBlockProof {
+    fn from(value: ton_tower::response::ShardBlockProof) -> Self {
+        Self {
+            masterchain_id: Some(value.masterchain_id.into()),
+            links: value
+                .links
+                .into_iter()
+                .map(|link| ShardBlockLink {
+                    id: Some(link.id.into()),
+                    proof: link.proof,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<ton_tower::response::Library> for Library {
+    fn from(value: ton_tower::response::Library) -> Self {
+        Self {
+            hash: value.hash,
+            cell: Some(value.cell.into()),
+        }
+    }
+}
+
+impl From<ConfigParam> for ConfigParamValue {
+    fn from(value: ConfigParam) -> Self {
+        match value {
+            ConfigParam::StoragePrices(prices) => Self::StoragePrices(StoragePricesList {
+                prices: prices.into_iter().map(Into::into).collect(),
+            }),
+            ConfigParam::MasterchainGasPrices(prices) | ConfigParam::GasPrices(prices) => {
+                Self::GasLimitsPrices(prices.into())
+            }
+            ConfigParam::MasterchainMsgForwardPrices(prices)
+            | ConfigParam::MsgForwardPrices(prices) => Self::MsgForwardPrices(prices.into()),
+            ConfigParam::CurrentValidators(validators) => Self::ValidatorSet(validators.into()),
+        }
+    }
+}
+
+impl From<ton_liteserver_client::tlb::storage_prices::StoragePrices> for StoragePrices {
+    fn from(value: ton_liteserver_client::tlb::storage_prices::StoragePrices) -> Self {
+        Self {
+            utime_since: value.utime_since,
+            bit_price_ps: value.bit_price_ps,
+            cell_price_ps: value.cell_price_ps,
+            mc_bit_price_ps: value.mc_bit_price_ps,
+            mc_cell_price_ps: value.mc_cell_price_ps,
+        }
+    }
+}
+
+impl From<ton_liteserver_client::tlb::gas_limits_prices::GasLimitsPrices> for GasLimitsPrices {
+    fn from(value: ton_liteserver_client::tlb::gas_limits_prices::GasLimitsPrices) -> Self {
+        Self {
+            flat_gas_limit: value.flat_gas_limit,
+            flat_gas_price: value.flat_gas_price,
+            gas_price: value.gas_price,
+            gas_limit: value.gas_limit,
+            special_gas_limit: value.special_gas_limit,
+            gas_credit: value.gas_credit,
+            block_gas_limit: value.block_gas_limit,
+            freeze_due_limit: value.freeze_due_limit,
+            delete_due_limit: value.delete_due_limit,
+        }
+    }
+}
+
+impl From<ton_liteserver_client::tlb::msg_forward_prices::MsgForwardPrices> for MsgForwardPrices {
+    fn from(value: ton_liteserver_client::tlb::msg_forward_prices::MsgForwardPrices) -> Self {
+        Self {
+            lump_price: value.lump_price,
+            bit_price: value.bit_price,
+            cell_price: value.cell_price,
+            ihr_price_factor: value.ihr_price_factor,
+            first_frac: value.first_frac.into(),
+            next_frac: value.next_frac.into(),
+        }
+    }
+}
+
+impl From<ton_liteserver_client::tlb::validator_set::ValidatorSet> for ValidatorSet {
+    fn from(value: ton_liteserver_client::tlb::validator_set::ValidatorSet) -> Self {
+        Self {
+            utime_since: value.utime_since,
+            utime_until: value.utime_until,
+            total: value.total.into(),
+            main: value.main.into(),
+            total_weight: value.total_weight,
+            list: value
+                .list
+                .into_iter()
+                .map(|(index, descr)| {
+                    let (public_key, weight, adnl_addr) = match descr {
+                        ValidatorDescr::Validator { public_key, weight } => {
+                            (public_key, weight, None)
+                        }
+                        ValidatorDescr::ValidatorAddr {
+                            public_key,
+                            weight,
+                            adnl_addr,
+                        } => (public_key, weight, Some(hex::encode(adnl_addr))),
+                    };
+
+                    validator_set::Validator {
+                        index: index.into(),
+                        public_key: hex::encode(public_key.pubkey),
+                        weight,
+                        adnl_addr,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
 impl From<ton_tower::response::MessageData> for MsgData {
     fn from(value: ton_tower::response::MessageData) -> Self {
         match value {
@@ -123,6 +248,99 @@ impl From<ton_tower::response::MessageData> for MsgData {
     }
 }
 
+impl From<ton_tower::response::DecodedBody> for DecodedBody {
+    fn from(value: ton_tower::response::DecodedBody) -> Self {
+        let body = match value {
+            ton_tower::response::DecodedBody::Comment { text } => {
+                Body::Comment(MessageDataText { text })
+            }
+            ton_tower::response::DecodedBody::EncryptedComment { text } => {
+                Body::EncryptedComment(MessageDataEncryptedText { text })
+            }
+            ton_tower::response::DecodedBody::JettonTransfer {
+                query_id,
+                amount,
+                destination,
+                response_destination,
+                custom_payload,
+                forward_ton_amount,
+                forward_payload,
+            } => Body::JettonTransfer(MessageDataJettonTransfer {
+                query_id,
+                amount,
+                destination: destination.map(|a| a.to_raw().to_string()),
+                response_destination: response_destination.map(|a| a.to_raw().to_string()),
+                custom_payload,
+                forward_ton_amount,
+                forward_payload,
+            }),
+            ton_tower::response::DecodedBody::JettonInternalTransfer {
+                query_id,
+                amount,
+                from,
+                response_address,
+                forward_ton_amount,
+                forward_payload,
+            } => Body::JettonInternalTransfer(MessageDataJettonInternalTransfer {
+                query_id,
+                amount,
+                from: from.map(|a| a.to_raw().to_string()),
+                response_address: response_address.map(|a| a.to_raw().to_string()),
+                forward_ton_amount,
+                forward_payload,
+            }),
+            ton_tower::response::DecodedBody::JettonTransferNotification {
+                query_id,
+                amount,
+                sender,
+                forward_payload,
+            } => Body::JettonTransferNotification(MessageDataJettonTransferNotification {
+                query_id,
+                amount,
+                sender: sender.map(|a| a.to_raw().to_string()),
+                forward_payload,
+            }),
+            ton_tower::response::DecodedBody::JettonBurn {
+                query_id,
+                amount,
+                response_destination,
+                custom_payload,
+            } => Body::JettonBurn(MessageDataJettonBurn {
+                query_id,
+                amount,
+                response_destination: response_destination.map(|a| a.to_raw().to_string()),
+                custom_payload,
+            }),
+            ton_tower::response::DecodedBody::NftTransfer {
+                query_id,
+                new_owner,
+                response_destination,
+                custom_payload,
+                forward_amount,
+                forward_payload,
+            } => Body::NftTransfer(MessageDataNftTransfer {
+                query_id,
+                new_owner: new_owner.map(|a| a.to_raw().to_string()),
+                response_destination: response_destination.map(|a| a.to_raw().to_string()),
+                custom_payload,
+                forward_amount,
+                forward_payload,
+            }),
+            ton_tower::response::DecodedBody::NftOwnershipAssigned {
+                query_id,
+                prev_owner,
+                forward_payload,
+            } => Body::NftOwnershipAssigned(MessageDataNftOwnershipAssigned {
+                query_id,
+                prev_owner: prev_owner.map(|a| a.to_raw().to_string()),
+                forward_payload,
+            }),
+        };
+
+        Self { body: Some(body) }
+    }
+}
+
 impl From<ton_tower::response::Message> for Message {
     fn from(value: ton_tower::response::Message) -> Self {
         Self {
@@ -134,6 +352,7 @@ impl From<ton_tower::response::Message> for Message {
             created_lt: value.created_lt,
             body_hash: value.body_hash,
             msg_data: Some(value.msg_data.into()),
+            decoded: value.decoded.map(Into::into),
         }
     }
 }
@@ -153,6 +372,348 @@ impl From<ton_tower::response::Transaction> for Transaction {
             other_fee: value.other_fee,
             in_msg: value.in_msg.map(Into::into),
             out_msgs: value.out_msgs.into_iter().map(Into::into).collect(),
+            description: None,
+        }
+    }
+}
+
+impl Transaction {
+    /// Decodes `data` into the structured `description`.
+    pub fn with_description(mut self) -> anyhow::Result<Self> {
+        let tx = decode_transaction(&self.data)?;
+        self.description = Some(tx.into());
+
+        Ok(self)
+    }
+}
+
+impl From<tlb::transaction::Transaction> for TransactionDescription {
+    fn from(value: tlb::transaction::Transaction) -> Self {
+        use tlb::transaction_descr::TransactionDescr;
+        use transaction_description::Type;
+
+        let mut description = Self {
+            orig_status: transaction_description::AccountStatus::from(value.orig_status).into(),
+            end_status: transaction_description::AccountStatus::from(value.end_status).into(),
+            ..Default::default()
+        };
+
+        match value.description {
+            TransactionDescr::Ordinary {
+                storage_ph,
+                credit_ph,
+                compute_ph,
+                action,
+                aborted,
+                bounce,
+                destroyed,
+                ..
+            } => {
+                description.set_type(Type::Ordinary);
+                description.storage_ph = storage_ph.map(Into::into);
+                description.credit_ph = credit_ph.map(Into::into);
+                description.compute_ph = Some(compute_ph.into());
+                description.action = action.map(Into::into);
+                description.aborted = aborted;
+                description.bounce = bounce.map(Into::into);
+                description.destroyed = destroyed;
+            }
+            TransactionDescr::Storage { storage_ph } => {
+                description.set_type(Type::Storage);
+                description.storage_ph = Some(storage_ph.into());
+            }
+            TransactionDescr::TickTock {
+                storage_ph,
+                compute_ph,
+                action,
+                aborted,
+                destroyed,
+                ..
+            } => {
+                description.set_type(Type::TickTock);
+                description.storage_ph = Some(storage_ph.into());
+                description.compute_ph = Some(compute_ph.into());
+                description.action = action.map(Into::into);
+                description.aborted = aborted;
+                description.destroyed = destroyed;
+            }
+            TransactionDescr::SplitPrepare {
+                storage_ph,
+                compute_ph,
+                action,
+                aborted,
+                destroyed,
+                ..
+            } => {
+                description.set_type(Type::SplitPrepare);
+                description.storage_ph = storage_ph.map(Into::into);
+                description.compute_ph = Some(compute_ph.into());
+                description.action = action.map(Into::into);
+                description.aborted = aborted;
+                description.destroyed = destroyed;
+            }
+            TransactionDescr::SplitInstall { .. } => {
+                description.set_type(Type::SplitInstall);
+            }
+            TransactionDescr::MergePrepare {
+                storage_ph,
+                aborted,
+                ..
+            } => {
+                description.set_type(Type::MergePrepare);
+                description.storage_ph = Some(storage_ph.into());
+                description.aborted = aborted;
+            }
+            TransactionDescr::MergeInstall {
+                storage_ph,
+                credit_ph,
+                compute_ph,
+                action,
+                aborted,
+                destroyed,
+                ..
+            } => {
+                description.set_type(Type::MergeInstall);
+                description.storage_ph = storage_ph.map(Into::into);
+                description.credit_ph = credit_ph.map(Into::into);
+                description.compute_ph = Some(compute_ph.into());
+                description.action = action.map(Into::into);
+                description.aborted = aborted;
+                description.destroyed = destroyed;
+            }
+        }
+
+        description
+    }
+}
+
+impl From<tlb::account_status::AccountStatus> for transaction_description::AccountStatus {
+    fn from(value: tlb::account_status::AccountStatus) -> Self {
+        match value {
+            tlb::account_status::AccountStatus::Uninit => Self::Uninit,
+            tlb::account_status::AccountStatus::Frozen => Self::Frozen,
+            tlb::account_status::AccountStatus::Active => Self::Active,
+            tlb::account_status::AccountStatus::Nonexist => Self::Nonexist,
+        }
+    }
+}
+
+impl From<tlb::transaction_descr::AccStatusChange>
+    for transaction_description::AccountStatusChange
+{
+    fn from(value: tlb::transaction_descr::AccStatusChange) -> Self {
+        match value {
+            tlb::transaction_descr::AccStatusChange::Unchanged => Self::AcstUnchanged,
+            tlb::transaction_descr::AccStatusChange::Frozen => Self::AcstFrozen,
+            tlb::transaction_descr::AccStatusChange::Deleted => Self::AcstDeleted,
         }
     }
 }
+
+impl From<tlb::transaction_descr::TrStoragePhase> for transaction_description::StoragePhase {
+    fn from(value: tlb::transaction_descr::TrStoragePhase) -> Self {
+        Self {
+            storage_fees_collected: grams(&value.storage_fees_collected),
+            storage_fees_due: value.storage_fees_due.as_ref().map(grams),
+            status_change: transaction_description::AccountStatusChange::from(value.status_change)
+                .into(),
+        }
+    }
+}
+
+impl From<tlb::transaction_descr::TrCreditPhase> for transaction_description::CreditPhase {
+    fn from(value: tlb::transaction_descr::TrCreditPhase) -> Self {
+        Self {
+            due_fees_collected: value.due_fees_collected.as_ref().map(grams),
+            credit: grams(&value.credit.grams),
+        }
+    }
+}
+
+impl From<tlb::transaction_descr::TrComputePhase> for transaction_description::ComputePhase {
+    fn from(value: tlb::transaction_descr::TrComputePhase) -> Self {
+        use tlb::transaction_descr::{ComputeSkipReason, TrComputePhase};
+        use transaction_description::compute_phase::SkipReason;
+
+        match value {
+            TrComputePhase::Skipped { reason } => {
+                let reason = match reason {
+                    ComputeSkipReason::NoState => SkipReason::NoState,
+                    ComputeSkipReason::BadState => SkipReason::BadState,
+                    ComputeSkipReason::NoGas => SkipReason::NoGas,
+                    ComputeSkipReason::Suspended => SkipReason::Suspended,
+                };
+
+                Self {
+                    skip_reason: Some(reason.into()),
+                    ..Default::default()
+                }
+            }
+            TrComputePhase::Vm {
+                success,
+                msg_state_used,
+                account_activated,
+                gas_fees,
+                gas_used,
+                gas_limit,
+                gas_credit,
+                mode,
+                exit_code,
+                exit_arg,
+                vm_steps,
+                ..
+            } => Self {
+                skip_reason: None,
+                success,
+                msg_state_used,
+                account_activated,
+                gas_fees: grams(&gas_fees),
+                gas_used: grams(&gas_used),
+                gas_limit: grams(&gas_limit),
+                gas_credit: gas_credit.as_ref().map(grams),
+                mode: mode.into(),
+                exit_code,
+                exit_arg,
+                vm_steps,
+            },
+        }
+    }
+}
+
+impl From<tlb::transaction_descr::TrActionPhase> for transaction_description::ActionPhase {
+    fn from(value: tlb::transaction_descr::TrActionPhase) -> Self {
+        Self {
+            success: value.success,
+            valid: value.valid,
+            no_funds: value.no_funds,
+            status_change: transaction_description::AccountStatusChange::from(value.status_change)
+                .into(),
+            total_fwd_fees: value.total_fwd_fees.as_ref().map(grams),
+            total_action_fees: value.total_action_fees.as_ref().map(grams),
+            result_code: value.result_code,
+            result_arg: value.result_arg,
+            tot_actions: value.tot_actions.into(),
+            spec_actions: value.spec_actions.into(),
+            skipped_actions: value.skipped_actions.into(),
+            msgs_created: value.msgs_created.into(),
+        }
+    }
+}
+
+impl From<tlb::transaction_descr::TrBouncePhase> for transaction_description::BouncePhase {
+    fn from(value: tlb::transaction_descr::TrBouncePhase) -> Self {
+        use tlb::transaction_descr::TrBouncePhase;
+        use transaction_description::bounce_phase::Type;
+
+        match value {
+            TrBouncePhase::NegFunds => Self {
+                r#type: Type::NegFunds.into(),
+                ..Default::default()
+            },
+            TrBouncePhase::NoFunds { req_fwd_fees, .. } => Self {
+                r#type: Type::NoFunds.into(),
+                req_fwd_fees: Some(grams(&req_fwd_fees)),
+                ..Default::default()
+            },
+            TrBouncePhase::Ok {
+                msg_fees, fwd_fees, ..
+            } => Self {
+                r#type: Type::Ok.into(),
+                msg_fees: Some(grams(&msg_fees)),
+                fwd_fees: Some(grams(&fwd_fees)),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Saturates at `i64::MAX`, the same as fee fields of `Transaction` do.
+fn grams<T>(value: T) -> i64
+where
+    i64: TryFrom<T>,
+{
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+impl From<ton_client::fee_client::FeeEstimate> for EstimateFeeResponse {
+    fn from(value: ton_client::fee_client::FeeEstimate) -> Self {
+        Self {
+            storage_fee: value.storage_fee,
+            gas_fee: value.gas_fee,
+            fwd_fee: value.fwd_fee,
+            total_fee: value.total_fee,
+            compute_exit_code: value.compute_exit_code,
+            action_result_code: value.action_result_code,
+            accepted: value.accepted,
+        }
+    }
+}
+
+impl From<ton_client::trace_client::TraceNode> for TraceNode {
+    fn from(value: ton_client::trace_client::TraceNode) -> Self {
+        Self {
+            transaction: Some(value.transaction.into()),
+            children: value.children.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ton_client::trace_client::Trace> for GetTraceResponse {
+    fn from(value: ton_client::trace_client::Trace) -> Self {
+        Self {
+            root: Some(value.root.into()),
+            is_complete: value.is_complete,
+        }
+    }
+}
+
+impl From<ton_tower::response::StackEntry> for StackEntry {
+    fn from(value: ton_tower::response::StackEntry) -> Self {
+        let value = match value {
+            ton_tower::response::StackEntry::Number { number } => Value::Number(number),
+            ton_tower::response::StackEntry::Cell { bytes } => Value::Cell(TvmCell { bytes }),
+            ton_tower::response::StackEntry::Slice { bytes } => Value::Slice(TvmCell { bytes }),
+            ton_tower::response::StackEntry::Tuple { elements } => {
+                Value::Tuple(stack_entry::Tuple {
+                    elements: elements.into_iter().map(Into::into).collect(),
+                })
+            }
+            ton_tower::response::StackEntry::List { elements } => Value::List(stack_entry::List {
+                elements: elements.into_iter().map(Into::into).collect(),
+            }),
+            ton_tower::response::StackEntry::Unsupported => {
+                Value::Unsupported(stack_entry::Unsupported {})
+            }
+        };
+
+        Self { value: Some(value) }
+    }
+}
+
+impl TryFrom<StackEntry> for ton_tower::response::StackEntry {
+    type Error = anyhow::Error;
+
+    fn try_from(value: StackEntry) -> Result<Self, Self::Error> {
+        Ok(match value.value {
+            Some(Value::Number(number)) => Self::Number { number },
+            Some(Value::Cell(cell)) => Self::Cell { bytes: cell.bytes },
+            Some(Value::Slice(slice)) => Self::Slice { bytes: slice.bytes },
+            Some(Value::Tuple(tuple)) => Self::Tuple {
+                elements: tuple
+                    .elements
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            },
+            Some(Value::List(list)) => Self::List {
+                elements: list
+                    .elements
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            },
+            Some(Value::Unsupported(_)) => return Err(anyhow!("unsupported stack entry")),
+            None => return Err(anyhow!("stack entry value is required")),
+        })
+    }
+}
@@ -0,0 +1,202 @@
+use crate::ton::transaction_service_server::TransactionService as BaseTransactionService;
+use crate::ton::{GetTransactionByHashRequest, GetTransactionByInMessageHashRequest, Transaction};
+use derive_new::new;
+use std::sync::Arc;
+use ton_client::tx_index::{IndexedTransaction, TransactionIndex};
+use ton_client::{Client, TonService};
+use ton_tower::service::error::RequestError;
+use tonic::{Request, Response, Status, async_trait};
+
+#[derive(new)]
+pub struct TransactionService<S: TonService> {
+    client: Client<S>,
+    index: Arc<TransactionIndex>,
+}
+
+#[async_trait]
+impl<S: TonService> BaseTransactionService for TransactionService<S> {
+    #[tracing::instrument(skip_all, err)]
+    async fn get_transaction_by_hash(
+        &self,
+        request: Request<GetTransactionByHashRequest>,
+    ) -> Result<Response<Transaction>, Status> {
+        let msg = request.into_inner();
+        let indexed = self
+            .index
+            .get_by_hash(&msg.hash)
+            .ok_or_else(|| self.not_found("transaction", &msg.hash))?;
+
+        self.fetch_transaction(indexed).await
+    }
+
+    #[tracing::instrument(skip_all, err)]
+    async fn get_transaction_by_in_message_hash(
+        &self,
+        request: Request<GetTransactionByInMessageHashRequest>,
+    ) -> Result<Response<Transaction>, Status> {
+        let msg = request.into_inner();
+        let indexed = self
+            .index
+            .get_by_in_msg_hash(&msg.hash)
+            .ok_or_else(|| self.not_found("inbound message", &msg.hash))?;
+
+        self.fetch_transaction(indexed).await
+    }
+}
+
+impl<S: TonService> TransactionService<S> {
+    async fn fetch_transaction(
+        &self,
+        indexed: IndexedTransaction,
+    ) -> Result<Response<Transaction>, Status> {
+        let mut client = self.client.clone();
+        let tx = client
+            .get_transaction(&indexed.address, &indexed.transaction_id)
+            .await
+            .map_err(|e| {
+                if RequestError::is_cause_of(&*e) {
+                    Status::not_found(e.to_string())
+                } else {
+                    Status::unavailable(e.to_string())
+                }
+            })?
+            .ok_or_else(|| {
+                Status::not_found(format!(
+                    "indexed transaction {} is not available",
+                    indexed.transaction_id.hash
+                ))
+            })?;
+
+        Ok(Response::new(tx.into()))
+    }
+
+    fn not_found(&self, kind: &str, hash: &str) -> Status {
+        match self.index.window() {
+            Some((from, to)) => Status::not_found(format!(
+                "{kind} {hash} is not found in indexed masterchain blocks {from}..={to}"
+            )),
+            None => Status::not_found(format!("{kind} {hash} is not found, index is empty")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod integration {
+    use crate::ton::transaction_service_client::TransactionServiceClient;
+    use crate::ton::transaction_service_server::TransactionServiceServer;
+    use crate::ton::{GetTransactionByHashRequest, GetTransactionByInMessageHashRequest};
+    use crate::transaction::TransactionService;
+    use futures::TryStreamExt;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use testcontainers_ton::{LocalLiteServer, SharedLiteServer};
+    use tokio::net::TcpListener;
+    use ton_client::tx_index::TransactionIndex;
+    use ton_client::{Client, TonClientBuilder, TonService};
+    use tonic::transport::Channel;
+    use tonlibjson_client::MakeTonlibjsonAdapter;
+
+    #[tokio::test]
+    async fn should_get_transaction_by_hash() {
+        let (_server, client, index, mut transactions) = setup().await;
+        let seqno = loop {
+            if let Some((_, to)) = index.window() {
+                break to;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        };
+        let block = client
+            .clone()
+            .look_up_block_by_seqno(-1, i64::MIN, seqno)
+            .await
+            .unwrap();
+        let txs = client.get_block_tx_stream(&block, false);
+        futures::pin_mut!(txs);
+        let expected = txs.try_next().await.unwrap().unwrap();
+
+        let resp = transactions
+            .get_transaction_by_hash(GetTransactionByHashRequest {
+                hash: expected.transaction_id.hash.clone(),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        let id = resp.id.unwrap();
+        assert_eq!(id.hash, expected.transaction_id.hash);
+        assert_eq!(id.lt, expected.transaction_id.lt);
+    }
+
+    #[tokio::test]
+    async fn should_fail_get_unknown_transaction_by_hash() {
+        let (_server, _client, _index, mut transactions) = setup().await;
+
+        let result = transactions
+            .get_transaction_by_hash(GetTransactionByHashRequest {
+                hash: "unknown".to_string(),
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn should_fail_get_unknown_transaction_by_in_message_hash() {
+        let (_server, _client, _index, mut transactions) = setup().await;
+
+        let result = transactions
+            .get_transaction_by_in_message_hash(GetTransactionByInMessageHashRequest {
+                hash: "unknown".to_string(),
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().code(), tonic::Code::NotFound);
+    }
+
+    async fn setup() -> (
+        SharedLiteServer,
+        Client<impl TonService>,
+        Arc<TransactionIndex>,
+        TransactionServiceClient<Channel>,
+    ) {
+        let server = LocalLiteServer::shared().await.unwrap();
+        let mut client = TonClientBuilder::<MakeTonlibjsonAdapter>::from_config(server.config())
+            .build()
+            .unwrap();
+        client.wait_ready().await.unwrap();
+
+        let index = Arc::new(TransactionIndex::new(1024));
+        tokio::spawn({
+            let index = index.clone();
+            let client = client.clone();
+            async move { index.run(client).await }
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn({
+            let service = TransactionService::new(client.clone(), index.clone());
+            async move {
+                tonic::transport::Server::builder()
+                    .add_service(TransactionServiceServer::new(service))
+                    .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let channel = Channel::from_shared(format!("http://{}", addr))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+
+        (
+            server,
+            client,
+            index,
+            TransactionServiceClient::new(channel),
+        )
+    }
+}
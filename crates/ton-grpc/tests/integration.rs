@@ -41,6 +41,7 @@ async fn should_chain_block_transactions_to_account_state() {
         .get_transactions(GetTransactionsRequest {
             block_id: Some(block_id),
             order: ton_grpc::ton::get_transactions_request::Order::Asc as i32,
+            include_description: false,
         })
         .await
         .unwrap()
@@ -73,6 +74,7 @@ async fn should_chain_block_transactions_to_account_state() {
                 root_hash: Some(last.root_hash.clone()),
                 file_hash: Some(last.file_hash.clone()),
             })),
+            resolve_libraries: false,
         })
         .await
         .unwrap()
@@ -92,6 +94,7 @@ async fn should_chain_block_transactions_to_account_state() {
             order: ton_grpc::ton::get_account_transactions_request::Order::FromNewToOld as i32,
             from: None,
             to: None,
+            include_description: false,
         })
         .await
         .unwrap()
@@ -190,6 +193,7 @@ async fn should_chain_block_header_to_shard_transactions_to_account_cell() {
                 root_hash: Some(last.root_hash.clone()),
                 file_hash: Some(last.file_hash.clone()),
             })),
+            resolve_libraries: false,
         })
         .await
         .unwrap()
@@ -1,3 +1,5 @@
+use crate::adapter::block::{load_ref, skip_currency_collection};
+use crate::adapter::convert::decode_hash;
 use crate::client::LiteServerClient;
 use crate::tl::{
     Int256, LiteServerAccountId, LiteServerGetAccountState, LiteServerGetOneTransaction,
@@ -6,19 +8,28 @@ use crate::tl::{
 use crate::tlb::account::Account;
 use crate::tlb::account_state::AccountState as TlbAccountState;
 use crate::tlb::account_storage::AccountStorage;
-use crate::tlb::merkle_proof::MerkleProof;
+use crate::tlb::dict::HashmapEdge;
+use crate::tlb::merkle_proof::{MerkleProof, verified_root};
+use crate::tlb::merkle_update::MerkleUpdate;
 use crate::tlb::shard_state::ShardStateUnsplit;
-use anyhow::anyhow;
+use anyhow::{Context, anyhow, bail, ensure};
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as base64_standard;
 use num_bigint::BigUint;
 use std::sync::Arc;
 use ton_address::SmartContractAddress;
+use ton_tlb::shard_account::ShardAccount;
 use ton_tower::response::{AccountState, BlockIdExt, Cell as TonCell, TransactionId};
-use toner::tlb::{BagOfCellsArgs, BoC, Cell};
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::view::BitView;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::bits::ser::BitWriterExt;
+use toner::tlb::{BagOfCellsArgs, BoC, Cell, Ref};
 use tower::ServiceExt;
 
 pub(super) const DEFAULT_TX_BATCH: i32 = 16;
+// `split_depth:(#<= 30)` of DepthBalanceInfo
+const SPLIT_DEPTH_BITS: usize = 5;
 
 pub(super) async fn get_account_state_inner(
     client: LiteServerClient,
@@ -27,7 +38,7 @@ pub(super) async fn get_account_state_inner(
 ) -> anyhow::Result<AccountState> {
     let response = fetch_account_state_raw(&client, &address, block_id).await?;
 
-    account_state_from_response(response)
+    account_state_from_response(&address, response)
 }
 
 pub(super) async fn get_shard_account_cell_inner(
@@ -37,7 +48,7 @@ pub(super) async fn get_shard_account_cell_inner(
 ) -> anyhow::Result<TonCell> {
     let response = fetch_account_state_raw(&client, &address, block_id).await?;
 
-    shard_account_cell_from_response(response)
+    shard_account_cell_from_response(&address, response)
 }
 
 pub(super) fn account_state_request(
@@ -54,15 +65,15 @@ pub(super) fn account_state_request(
 }
 
 pub(super) fn account_state_from_response(
+    address: &SmartContractAddress,
     response: crate::tl::LiteServerAccountState,
 ) -> anyhow::Result<AccountState> {
-    // TODO verify ShardAccount inclusion via ShardAccounts dict traversal (needs MaybePruned)
-    verify_account_proofs(&response)?;
+    let proven = verify_account_proofs(address, &response)?;
 
     let block_id_out: BlockIdExt = response.shardblk.clone().into();
-    let sync_utime = extract_gen_utime_from_proof(&response.proof)?;
+    let sync_utime = i64::from(proven.gen_utime);
 
-    if response.state.is_empty() {
+    let Some(shard_account) = proven.shard_account else {
         return Ok(AccountState {
             balance: None,
             code: String::new(),
@@ -72,37 +83,62 @@ pub(super) fn account_state_from_response(
             block_id: block_id_out,
             sync_utime,
         });
-    }
-
-    let acc_boc = BoC::deserialize(&response.state)?;
-    let acc_root = acc_boc
-        .single_root()
-        .ok_or_else(|| anyhow!("account state: single root expected"))?
-        .clone();
+    };
 
-    let account: Account = acc_root.parse_fully(())?;
-    let (balance, code, data, frozen_hash) = account_components(&account)?;
+    let (balance, code, data, frozen_hash) = account_components(&shard_account.account)?;
 
     Ok(AccountState {
         balance,
         code,
         data,
         frozen_hash,
-        // TODO[akostylev0]: extract last_transaction_id from ShardAccount in `proof` (needs MaybePruned)
-        last_transaction_id: None,
+        last_transaction_id: Some(TransactionId {
+            lt: shard_account.last_trans_lt as i64,
+            hash: base64_standard.encode(shard_account.last_trans_hash),
+        }),
         block_id: block_id_out,
         sync_utime,
     })
 }
 
+/// Packs the proven account into a `ShardAccount` cell, as tonlib does for `getShardAccountCell`.
 pub(super) fn shard_account_cell_from_response(
+    address: &SmartContractAddress,
     response: crate::tl::LiteServerAccountState,
 ) -> anyhow::Result<TonCell> {
-    // TODO verify ShardAccount inclusion via ShardAccounts dict traversal (needs MaybePruned)
-    verify_account_proofs(&response)?;
+    let proven = verify_account_proofs(address, &response)?;
+
+    let mut builder = Cell::builder();
+    match proven.shard_account {
+        Some(shard_account) => {
+            let boc = BoC::deserialize(&response.state)?;
+            let account = boc
+                .single_root()
+                .ok_or_else(|| anyhow!("account state: single root expected"))?;
+
+            builder.store_as::<_, Ref>(account.as_ref(), ())?;
+            builder.pack(shard_account.last_trans_hash, ())?;
+            builder.pack(shard_account.last_trans_lt, ())?;
+        }
+        None => {
+            // account_none$0 = Account;
+            let mut account = Cell::builder();
+            account.pack(false, ())?;
+
+            builder.store_as::<_, Ref>(&account.into_cell(), ())?;
+            builder.pack([0u8; 32], ())?;
+            builder.pack(0u64, ())?;
+        }
+    }
+    let bytes = BoC::from_root(builder.into_cell())
+        .serialize(BagOfCellsArgs {
+            has_crc32c: true,
+            ..BagOfCellsArgs::default()
+        })
+        .map_err(|e| anyhow!("BoC serialize failed: {e}"))?;
 
     Ok(TonCell {
-        bytes: base64_standard.encode(&response.state),
+        bytes: base64_standard.encode(bytes),
     })
 }
 
@@ -115,7 +151,7 @@ async fn fetch_account_state_raw(
         .clone()
         .oneshot(account_state_request(address, block_id))
         .await
-        .map_err(|e| anyhow!(e))
+        .map_err(super::client_error)
 }
 
 pub(super) async fn lookup_block_by_transaction(
@@ -138,7 +174,7 @@ pub(super) async fn lookup_block_by_transaction(
             lt: tx.lt,
         })
         .await
-        .map_err(|e| anyhow!(e))?;
+        .map_err(super::client_error)?;
 
     // TODO verify transaction hash matches `tx.hash` via proof (TransactionRef in shard state)
     let _ = hash;
@@ -146,16 +182,20 @@ pub(super) async fn lookup_block_by_transaction(
     Ok(response.id)
 }
 
-// Verifies binding of proofs to the response by structurally parsing every Merkle proof
-// root and checking that at least one root in `proof` references shardblk.root_hash, and at
-// least one root in `shard_proof` references the requested mc block root_hash.
-// TODO[akostylev0]: traverse ShardAccounts dict inside proof to confirm account inclusion (needs MaybePruned).
-fn verify_account_proofs(response: &crate::tl::LiteServerAccountState) -> anyhow::Result<()> {
-    require_proof_binds_to(
-        &response.proof,
-        &response.shardblk.root_hash,
-        "account proof",
-    )?;
+/// The shard state of `shardblk` as far as the account proof shows it.
+struct ProvenAccountState {
+    gen_utime: u32,
+    // `None` when the address is not in the shard accounts
+    shard_account: Option<ShardAccount>,
+}
+
+// `proof` is a block proof of `shardblk` followed by a proof of its state: the block binds
+// the state hash, the state binds the `ShardAccount` found under `address`, and the
+// `ShardAccount` binds the hash of the returned `state`.
+fn verify_account_proofs(
+    address: &SmartContractAddress,
+    response: &crate::tl::LiteServerAccountState,
+) -> anyhow::Result<ProvenAccountState> {
     if !response.shard_proof.is_empty() {
         require_proof_binds_to(
             &response.shard_proof,
@@ -163,20 +203,101 @@ fn verify_account_proofs(response: &crate::tl::LiteServerAccountState) -> anyhow
             "account shard_proof",
         )?;
     }
-    Ok(())
-}
+    ensure!(
+        response.shardblk.workchain == address.workchain_id(),
+        "account proof: shard block of workchain {} for address of workchain {}",
+        response.shardblk.workchain,
+        address.workchain_id()
+    );
+
+    let boc = BoC::deserialize(&response.proof)?;
+    let (block_proof, state_proof) = match &boc.roots()[..] {
+        [block_proof, state_proof] => (block_proof, state_proof),
+        roots => bail!(
+            "account proof: block and state proofs expected, got {} roots",
+            roots.len()
+        ),
+    };
 
-fn extract_gen_utime_from_proof(proof_bytes: &[u8]) -> anyhow::Result<i64> {
-    let boc = BoC::deserialize(proof_bytes)?;
-    for root in boc.roots() {
-        let Ok(state) = root.parse_fully_as::<ShardStateUnsplit, MerkleProof<_>>(()) else {
-            continue;
-        };
-        return Ok(state.gen_utime as i64);
+    let block =
+        verified_root(block_proof, &response.shardblk.root_hash).context("account block proof")?;
+    // block#11ef55aa global_id:int32 info:^ value_flow:^ state_update:^(MERKLE_UPDATE ShardState)
+    let state_update: MerkleUpdate<Cell> = block
+        .references
+        .get(2)
+        .ok_or_else(|| anyhow!("account block proof: state update is missing"))?
+        .parse_fully(())?;
+
+    let state =
+        verified_root(state_proof, &state_update.new_hash).context("account state proof")?;
+    let state: ShardStateUnsplit = state.parse_fully(())?;
+
+    let leaf = match state.accounts.data.first().map(|b| *b) {
+        // ahme_root$1 root:^(HashmapAug 256 ShardAccount DepthBalanceInfo)
+        Some(true) => {
+            let root = load_ref(&state.accounts.references, 0, "shard accounts root")?;
+            HashmapEdge::new(root).get(address.to_internal().view_bits::<Msb0>())?
+        }
+        Some(false) => None,
+        None => bail!("shard accounts: unexpected end of cell"),
+    };
+
+    let Some(leaf) = leaf else {
+        ensure!(
+            response.state.is_empty(),
+            "account proof: account is not in the shard state"
+        );
+
+        return Ok(ProvenAccountState {
+            gen_utime: state.gen_utime,
+            shard_account: None,
+        });
+    };
+    ensure!(
+        !response.state.is_empty(),
+        "account proof: shard state has the account, but the state is empty"
+    );
+
+    // ahmn_leaf extra:DepthBalanceInfo value:ShardAccount,
+    // depth_balance$_ split_depth:(#<= 30) balance:CurrencyCollection
+    let bits = leaf
+        .bits
+        .get(SPLIT_DEPTH_BITS..)
+        .ok_or_else(|| anyhow!("shard account: unexpected end of cell"))?;
+    let (mut bits, refs) = skip_currency_collection(bits, leaf.refs)?;
+    let account_ref = refs
+        .first()
+        .ok_or_else(|| anyhow!("shard account: account reference is missing"))?;
+    let last_trans_hash: [u8; 32] = bits.unpack(())?;
+    let last_trans_lt: u64 = bits.unpack(())?;
+
+    let acc_boc = BoC::deserialize(&response.state)?;
+    let acc_root = acc_boc
+        .single_root()
+        .ok_or_else(|| anyhow!("account state: single root expected"))?;
+    ensure!(
+        acc_root.hash() == account_ref.level_hash(0).1,
+        "account proof: account hash mismatch"
+    );
+
+    let account: Account = acc_root.parse_fully(())?;
+    if let Account::Account { storage, .. } = &account {
+        ensure!(
+            storage.last_trans_lt == last_trans_lt,
+            "account proof: last transaction lt mismatch: account {}, shard account {}",
+            storage.last_trans_lt,
+            last_trans_lt
+        );
     }
-    Err(anyhow!(
-        "proof contains no ShardStateUnsplit root (expected tag 0x9023afe2)"
-    ))
+
+    Ok(ProvenAccountState {
+        gen_utime: state.gen_utime,
+        shard_account: Some(ShardAccount {
+            account,
+            last_trans_hash,
+            last_trans_lt,
+        }),
+    })
 }
 
 fn require_proof_binds_to(
@@ -257,12 +378,7 @@ fn encode_optional_cell_boc(cell: Option<&Arc<Cell>>) -> anyhow::Result<String>
 }
 
 pub(super) fn decode_tx_hash(hash_b64: &str) -> anyhow::Result<Int256> {
-    let raw = base64_standard
-        .decode(hash_b64)
-        .map_err(|e| anyhow!("invalid base64 tx hash: {}", e))?;
-    raw.as_slice()
-        .try_into()
-        .map_err(|_| anyhow!("tx hash must be 32 bytes, got {}", raw.len()))
+    decode_hash(hash_b64).context("tx hash")
 }
 
 #[cfg(test)]
@@ -335,6 +451,22 @@ mod integration {
         Ok(())
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn get_account_state_returns_proven_last_transaction() -> anyhow::Result<()> {
+        let (adapter, _server) = setup().await?;
+        let address = SmartContractAddress::from_str(FAUCET_WALLET_ADDR)?;
+
+        let state = adapter.oneshot(GetAccountState { address }).await?;
+
+        let last_transaction_id = state
+            .last_transaction_id
+            .expect("faucet wallet must have a last transaction");
+        assert!(last_transaction_id.lt > 0);
+        assert!(!last_transaction_id.hash.is_empty());
+        Ok(())
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn get_account_state_returns_recent_sync_utime() -> anyhow::Result<()> {
@@ -390,10 +522,14 @@ mod integration {
 
         let cell = adapter.oneshot(GetShardAccountCell { address }).await?;
 
-        assert!(
-            !cell.bytes.is_empty(),
-            "shard account cell must not be empty"
-        );
+        let boc = BoC::parse_base64(&cell.bytes)?;
+        let root = boc
+            .single_root()
+            .ok_or_else(|| anyhow!("shard account: single root expected"))?;
+        let shard_account: ShardAccount = root.parse_fully(())?;
+
+        assert!(matches!(shard_account.account, Account::Account { .. }));
+        assert!(shard_account.last_trans_lt > 0);
         Ok(())
     }
 
@@ -1,7 +1,42 @@
-use crate::tlb::merkle_proof::MerkleProof;
-use anyhow::anyhow;
-use toner::tlb::bits::de::unpack_bytes_fully;
-use toner::tlb::{BoC, Cell};
+use crate::tl::{LiteServerTransactionId, LiteServerTransactionId3};
+use crate::tlb::dict::{HashmapEdge, PrunedBranch};
+use crate::tlb::merkle_proof::verified_root_of_boc;
+use anyhow::{Context, anyhow, bail, ensure};
+use num_bigint::BigUint;
+use std::sync::Arc;
+use toner::tlb::Cell;
+use toner::tlb::bits::bitvec::field::BitField;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::slice::BitSlice;
+use toner::tlb::bits::bitvec::view::BitView;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::ton::currency::Grams;
+
+// acc_trans#5
+const ACCOUNT_BLOCK_TAG: u8 = 0x5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct TransactionKey {
+    pub account: [u8; 32],
+    pub lt: i64,
+    pub hash: [u8; 32],
+}
+
+impl TryFrom<&LiteServerTransactionId> for TransactionKey {
+    type Error = anyhow::Error;
+
+    fn try_from(id: &LiteServerTransactionId) -> Result<Self, Self::Error> {
+        Ok(Self {
+            account: id
+                .account
+                .ok_or_else(|| anyhow!("transaction id missing account"))?,
+            lt: id.lt.ok_or_else(|| anyhow!("transaction id missing lt"))?,
+            hash: id
+                .hash
+                .ok_or_else(|| anyhow!("transaction id missing hash"))?,
+        })
+    }
+}
 
 pub(super) fn list_block_transactions_mode(
     has_after: bool,
@@ -32,46 +67,437 @@ pub(super) fn verify_header_proof(
     proof_bytes: &[u8],
     expected_root_hash: &[u8; 32],
 ) -> anyhow::Result<()> {
-    let boc: BoC = unpack_bytes_fully(proof_bytes, ())?;
-    let root = boc
-        .single_root()
-        .ok_or_else(|| anyhow!("header proof: single root expected"))?;
+    verified_root_of_boc(proof_bytes, expected_root_hash).context("header proof")?;
 
-    let proof: MerkleProof<Cell> = root.parse_fully(())?;
-    if &proof.virtual_hash != expected_root_hash {
-        return Err(anyhow!(
-            "header proof root hash mismatch: expected {}, got {}",
-            hex::encode(expected_root_hash),
-            hex::encode(proof.virtual_hash)
-        ));
+    Ok(())
+}
+
+/// Walks `ShardAccountBlocks` of the proven `block` the same way the lite-server does for
+/// `listBlockTransactions` and checks that it yields exactly `transactions`.
+/// When the list is complete the walk must also reach the end of the block, unless the lookup
+/// past the last listed transaction runs into a branch the lite-server left pruned.
+pub(super) fn verify_block_transactions(
+    block: &Cell,
+    after: Option<&LiteServerTransactionId3>,
+    reverse: bool,
+    incomplete: bool,
+    transactions: &[TransactionKey],
+) -> anyhow::Result<()> {
+    // block#11ef55aa global_id:int32 info:^ value_flow:^ state_update:^ extra:^BlockExtra
+    let extra = load_ref(&block.references, 3, "block extra")?;
+    // block_extra in_msg_descr:^ out_msg_descr:^ account_blocks:^ShardAccountBlocks ...
+    let account_blocks = load_ref(&extra.references, 2, "account blocks")?;
+
+    let limit = if incomplete {
+        transactions.len()
+    } else {
+        transactions.len() + 1
+    };
+    // ahme_empty$0 / ahme_root$1 root:^(HashmapAug 256 AccountBlock CurrencyCollection)
+    let found = match account_blocks.data.first().map(|b| *b) {
+        Some(true) => {
+            let root = load_ref(&account_blocks.references, 0, "account blocks root")?;
+            walk_account_blocks(
+                HashmapEdge::new(root),
+                after,
+                reverse,
+                limit,
+                transactions.len(),
+            )?
+        }
+        Some(false) => Vec::new(),
+        None => bail!("account blocks: unexpected end of cell"),
+    };
+
+    if let Some((i, (expected, found))) = transactions
+        .iter()
+        .zip(&found)
+        .enumerate()
+        .find(|(_, (expected, found))| expected != found)
+    {
+        bail!(
+            "transaction #{i} {}:{} is not in the block proof, found {}:{}",
+            hex::encode(expected.account),
+            expected.lt,
+            hex::encode(found.account),
+            found.lt
+        );
     }
+    ensure!(
+        found.len() <= transactions.len(),
+        "block proof has transactions after the complete list"
+    );
+    ensure!(
+        found.len() == transactions.len(),
+        "block proof has {} of {} transactions",
+        found.len(),
+        transactions.len()
+    );
 
     Ok(())
 }
 
-// TODO verify individual transaction inclusion via ShardAccountBlocks dict traversal in proof
+fn walk_account_blocks(
+    account_blocks: HashmapEdge<'_>,
+    after: Option<&LiteServerTransactionId3>,
+    reverse: bool,
+    limit: usize,
+    listed: usize,
+) -> anyhow::Result<Vec<TransactionKey>> {
+    let mut found = Vec::new();
+    // once every listed transaction is found, a pruned branch is where the proof ends
+    let past_proof = |found: &Vec<TransactionKey>, e: &anyhow::Error| {
+        found.len() >= listed && e.is::<PrunedBranch>()
+    };
+
+    // the lite-server starts from the `after` account itself, or from the first one
+    let mut account = after.map_or([if reverse { 0xff } else { 0 }; 32], |after| after.account);
+    let mut inclusive = true;
+    while found.len() < limit {
+        let Some((key, leaf)) =
+            (match account_blocks.nearest(account.view_bits::<Msb0>(), reverse, inclusive) {
+                Err(e) if past_proof(&found, &e) => break,
+                result => result?,
+            })
+        else {
+            break;
+        };
+        account = key
+            .as_raw_slice()
+            .try_into()
+            .map_err(|_| anyhow!("account blocks: invalid key"))?;
+        inclusive = false;
+
+        // ahmn_leaf extra:CurrencyCollection value:AccountBlock
+        let (mut bits, refs) = skip_currency_collection(leaf.bits, leaf.refs)?;
+        // acc_trans#5 account_addr:bits256 transactions:(HashmapAug 64 ^Transaction CurrencyCollection)
+        let tag = bits
+            .get(..4)
+            .ok_or_else(|| anyhow!("account block: tag is missing"))?;
+        ensure!(
+            tag.load_be::<u8>() == ACCOUNT_BLOCK_TAG,
+            "account block: invalid tag"
+        );
+        bits = &bits[4..];
+        let account_addr: [u8; 32] = bits.unpack(())?;
+        ensure!(
+            account_addr == account,
+            "account block: address does not match the key"
+        );
+        let transactions = HashmapEdge { bits, refs };
+
+        // transactions strictly after `after.lt`, or all of them for the next accounts
+        let mut lt = match after {
+            Some(after) if after.account == account => after.lt as u64,
+            _ if reverse => u64::MAX,
+            _ => 0,
+        };
+        while found.len() < limit {
+            let Some((key, leaf)) =
+                (match transactions.nearest(lt.to_be_bytes().view_bits::<Msb0>(), reverse, false) {
+                    Err(e) if past_proof(&found, &e) => return Ok(found),
+                    result => result?,
+                })
+            else {
+                break;
+            };
+            lt = key.load_be();
+
+            // ahmn_leaf extra:CurrencyCollection value:^Transaction
+            let (_, refs) = skip_currency_collection(leaf.bits, leaf.refs)?;
+            let transaction = refs
+                .first()
+                .ok_or_else(|| anyhow!("account block: transaction reference is missing"))?;
+
+            found.push(TransactionKey {
+                account,
+                lt: lt as i64,
+                // the transaction itself is usually pruned, its original hash is kept
+                hash: transaction.level_hash(0).1,
+            });
+        }
+    }
+
+    Ok(found)
+}
+
+/// `currencies$_ grams:Grams other:ExtraCurrencyCollection = CurrencyCollection;`
+pub(super) fn skip_currency_collection<'a>(
+    mut bits: &'a BitSlice<u8, Msb0>,
+    refs: &'a [Arc<Cell>],
+) -> anyhow::Result<(&'a BitSlice<u8, Msb0>, &'a [Arc<Cell>])> {
+    let _: BigUint = bits.unpack_as::<_, Grams>(())?;
+    // extra currencies are `HashmapE 32 (VarUInteger 32)`, a non-empty one takes a reference
+    let has_other: bool = bits.unpack(())?;
+    if !has_other {
+        return Ok((bits, refs));
+    }
+    let refs = refs
+        .get(1..)
+        .ok_or_else(|| anyhow!("currency collection: reference is missing"))?;
+
+    Ok((bits, refs))
+}
+
+pub(super) fn load_ref<'a>(
+    refs: &'a [Arc<Cell>],
+    index: usize,
+    name: &str,
+) -> anyhow::Result<&'a Cell> {
+    let cell = refs
+        .get(index)
+        .ok_or_else(|| anyhow!("{name}: reference is missing"))?;
+    ensure!(!cell.is_exotic, "{name}: cell is pruned");
+
+    Ok(cell)
+}
+
+/// Returns the block of the proof, every cell the lite-server did not touch is pruned.
 pub(super) fn verify_block_proof(
     proof_bytes: &[u8],
     expected_root_hash: &[u8; 32],
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Cell> {
     if proof_bytes.is_empty() {
         return Err(anyhow!("empty proof"));
     }
 
-    let boc: BoC = BoC::deserialize(proof_bytes)?;
-    let root = boc
-        .single_root()
-        .ok_or_else(|| anyhow!("proof: single root expected"))?;
+    verified_root_of_boc(proof_bytes, expected_root_hash)
+}
 
-    let proof: MerkleProof<Cell> = root.parse_fully(())?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tlb::tests::BLOCK_HEX;
+    use toner::tlb::BoC;
 
-    if &proof.virtual_hash != expected_root_hash {
-        return Err(anyhow!(
-            "proof root hash mismatch: expected {}, got {}",
-            hex::encode(expected_root_hash),
-            hex::encode(proof.virtual_hash)
-        ));
+    fn given_block() -> Arc<Cell> {
+        BoC::deserialize(&hex::decode(BLOCK_HEX).unwrap())
+            .unwrap()
+            .into_single_root()
+            .unwrap()
     }
 
-    Ok(())
+    fn walk(block: &Cell, after: Option<&TransactionKey>, reverse: bool) -> Vec<TransactionKey> {
+        let extra = load_ref(&block.references, 3, "block extra").unwrap();
+        let account_blocks = load_ref(&extra.references, 2, "account blocks").unwrap();
+        let root = load_ref(&account_blocks.references, 0, "account blocks root").unwrap();
+        let after = after.map(|key| LiteServerTransactionId3 {
+            account: key.account,
+            lt: key.lt,
+        });
+
+        walk_account_blocks(
+            HashmapEdge::new(root),
+            after.as_ref(),
+            reverse,
+            usize::MAX,
+            usize::MAX,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn should_verify_complete_transaction_list() {
+        let block = given_block();
+        let transactions = walk(&block, None, false);
+
+        let result = verify_block_transactions(&block, None, false, false, &transactions);
+
+        assert!(!transactions.is_empty());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn should_walk_transactions_in_reverse_and_after() {
+        let block = given_block();
+        let transactions = walk(&block, None, false);
+
+        let mut reversed = walk(&block, None, true);
+        reversed.reverse();
+        let tail = walk(&block, Some(&transactions[0]), false);
+
+        assert_eq!(reversed, transactions);
+        assert_eq!(tail, transactions[1..]);
+    }
+
+    #[test]
+    fn should_reject_omitted_transaction() {
+        let block = given_block();
+        let mut transactions = walk(&block, None, false);
+        transactions.remove(0);
+
+        let result = verify_block_transactions(&block, None, false, false, &transactions);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_reject_truncated_complete_list() {
+        let block = given_block();
+        let mut transactions = walk(&block, None, false);
+        transactions.pop();
+
+        assert!(verify_block_transactions(&block, None, false, true, &transactions).is_ok());
+        assert!(verify_block_transactions(&block, None, false, false, &transactions).is_err());
+    }
+}
+
+#[cfg(test)]
+mod integration {
+    use crate::adapter::LiteServerAdapter;
+    use crate::client::LiteServerClient;
+    use testcontainers_ton::{LocalLiteServer, SharedLiteServer};
+    use ton_tower::request::{GetMasterchainInfo, GetTransactionIds, GetTransactions};
+    use ton_tower::response::{BlockIdExt, ShortTxId};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn should_get_complete_transaction_ids() -> anyhow::Result<()> {
+        let (adapter, _server) = setup().await?;
+        let block = given_block(&adapter).await?;
+
+        let page = adapter
+            .oneshot(GetTransactionIds {
+                block,
+                after: None,
+                reverse: false,
+                count: 1024,
+            })
+            .await?;
+
+        assert!(!page.incomplete);
+        assert!(page.transactions.len() > 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn should_page_transaction_ids_with_after() -> anyhow::Result<()> {
+        let (adapter, _server) = setup().await?;
+        let block = given_block(&adapter).await?;
+        let all = all_transaction_ids(&adapter, &block, false).await?;
+
+        let first = adapter
+            .clone()
+            .oneshot(GetTransactionIds {
+                block: block.clone(),
+                after: None,
+                reverse: false,
+                count: 1,
+            })
+            .await?;
+        let rest = adapter
+            .oneshot(GetTransactionIds {
+                block,
+                after: first.transactions.last().cloned(),
+                reverse: false,
+                count: 1024,
+            })
+            .await?;
+
+        assert!(first.incomplete);
+        assert!(!rest.incomplete);
+        assert_eq!([first.transactions, rest.transactions].concat(), all);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn should_page_transaction_ids_in_reverse() -> anyhow::Result<()> {
+        let (adapter, _server) = setup().await?;
+        let block = given_block(&adapter).await?;
+        let all = all_transaction_ids(&adapter, &block, false).await?;
+
+        let mut reversed = all_transaction_ids(&adapter, &block, true).await?;
+        reversed.reverse();
+
+        assert_eq!(reversed, all);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn should_page_transactions_with_after() -> anyhow::Result<()> {
+        let (adapter, _server) = setup().await?;
+        let block = given_block(&adapter).await?;
+        let all = all_transaction_ids(&adapter, &block, false).await?;
+
+        let first = adapter
+            .clone()
+            .oneshot(GetTransactions {
+                block: block.clone(),
+                after: None,
+                reverse: false,
+                count: 1,
+            })
+            .await?;
+        let after = first.transactions.last().map(|tx| ShortTxId {
+            account: tx.address.clone(),
+            lt: tx.transaction_id.lt,
+            hash: tx.transaction_id.hash.clone(),
+        });
+        let rest = adapter
+            .oneshot(GetTransactions {
+                block,
+                after,
+                reverse: false,
+                count: 1024,
+            })
+            .await?;
+
+        let ids: Vec<_> = [first.transactions, rest.transactions]
+            .concat()
+            .into_iter()
+            .map(|tx| (tx.address, tx.transaction_id.lt, tx.transaction_id.hash))
+            .collect();
+        let expected: Vec<_> = all
+            .into_iter()
+            .map(|tx| (tx.account, tx.lt, tx.hash))
+            .collect();
+        assert!(first.incomplete);
+        assert!(!rest.incomplete);
+        assert_eq!(ids, expected);
+
+        Ok(())
+    }
+
+    async fn all_transaction_ids(
+        adapter: &LiteServerAdapter,
+        block: &BlockIdExt,
+        reverse: bool,
+    ) -> anyhow::Result<Vec<ShortTxId>> {
+        let page = adapter
+            .clone()
+            .oneshot(GetTransactionIds {
+                block: block.clone(),
+                after: None,
+                reverse,
+                count: 1024,
+            })
+            .await?;
+        assert!(!page.incomplete);
+
+        Ok(page.transactions)
+    }
+
+    /// The last masterchain block, it always has tick-tock transactions.
+    async fn given_block(adapter: &LiteServerAdapter) -> anyhow::Result<BlockIdExt> {
+        let info = adapter
+            .clone()
+            .oneshot(GetMasterchainInfo::default())
+            .await?;
+
+        Ok(info.last)
+    }
+
+    async fn setup() -> anyhow::Result<(LiteServerAdapter, SharedLiteServer)> {
+        let server = LocalLiteServer::shared().await?;
+        let client = LiteServerClient::connect(server.addr(), server.server_key()).await?;
+
+        Ok((LiteServerAdapter::new(client), server))
+    }
 }
@@ -0,0 +1,177 @@
+use crate::tl::{
+    Int31, LiteServerConfigInfo, LiteServerGetConfigAll, LiteServerGetConfigParams,
+    TonNodeBlockIdExt,
+};
+use crate::tlb::config_param::{ConfigParam, find_config_param};
+use crate::tlb::mc_state_extra::McStateExtra;
+use crate::tlb::merkle_proof::verified_root_of_boc;
+use crate::tlb::merkle_update::MerkleUpdate;
+use crate::tlb::shard_state::ShardStateUnsplit;
+use anyhow::{Context, anyhow};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as base64_standard;
+use ton_tower::response::Cell as TonCell;
+use toner::tlb::{BagOfCellsArgs, BoC, Cell};
+
+// No mode bits: the config is taken from the state of the requested block itself
+// rather than from the previous key block (`mode.15`).
+const CONFIG_ALL_MODE: Int31 = 0;
+
+pub(super) fn config_all_request(block_id: TonNodeBlockIdExt) -> LiteServerGetConfigAll {
+    LiteServerGetConfigAll {
+        mode: CONFIG_ALL_MODE,
+        id: block_id,
+    }
+}
+
+pub(super) fn config_param_request(
+    block_id: TonNodeBlockIdExt,
+    param: Int31,
+) -> LiteServerGetConfigParams {
+    LiteServerGetConfigParams {
+        mode: CONFIG_ALL_MODE,
+        id: block_id,
+        param_list: vec![param],
+    }
+}
+
+pub(super) fn config_all_from_response(response: LiteServerConfigInfo) -> anyhow::Result<TonCell> {
+    let config = config_from_response(response)?;
+
+    serialize(config)
+}
+
+/// A param missing from the config is returned as an empty cell, as tonlib does.
+pub(super) fn config_param_from_response(
+    response: LiteServerConfigInfo,
+    param: Int31,
+) -> anyhow::Result<TonCell> {
+    let config = config_from_response(response)?;
+    let value = u32::try_from(param)
+        .ok()
+        .map(|id| find_config_param(&config, id))
+        .transpose()?
+        .flatten();
+
+    match value {
+        Some(value) => serialize(Cell::clone(&value)),
+        None => Ok(TonCell {
+            bytes: String::new(),
+        }),
+    }
+}
+
+/// Decodes a config param returned by `GetConfigParam` into its typed representation.
+/// Returns `None` for params without one.
+pub fn decode_config_param(param: i32, cell: &TonCell) -> anyhow::Result<Option<ConfigParam>> {
+    let bytes = base64_standard.decode(&cell.bytes)?;
+    let boc = BoC::deserialize(bytes)?;
+    let root = boc
+        .single_root()
+        .ok_or_else(|| anyhow!("config param {param}: single root expected"))?;
+
+    ConfigParam::from_cell(u32::try_from(param)?, root)
+}
+
+// `state_proof` is a proof of the block `id` binding the state hash,
+// `config_proof` is a proof of that state binding the config.
+fn config_from_response(response: LiteServerConfigInfo) -> anyhow::Result<Cell> {
+    let block = verified_root_of_boc(&response.state_proof, &response.id.root_hash)
+        .context("config block proof")?;
+    // block#11ef55aa global_id:int32 info:^ value_flow:^ state_update:^(MERKLE_UPDATE ShardState)
+    let state_update: MerkleUpdate<Cell> = block
+        .references
+        .get(2)
+        .ok_or_else(|| anyhow!("config block proof: state update is missing"))?
+        .parse_fully(())?;
+
+    let state = verified_root_of_boc(&response.config_proof, &state_update.new_hash)
+        .context("config proof")?;
+    let state: ShardStateUnsplit = state.parse_fully(())?;
+    let custom = state
+        .custom
+        .ok_or_else(|| anyhow!("config proof: state has no McStateExtra"))?;
+    let extra: McStateExtra = custom.parse_fully(())?;
+
+    Ok(extra.config.config)
+}
+
+fn serialize(cell: Cell) -> anyhow::Result<TonCell> {
+    let bytes = BoC::from_root(cell)
+        .serialize(BagOfCellsArgs {
+            has_crc32c: true,
+            ..BagOfCellsArgs::default()
+        })
+        .map_err(|e| anyhow!("BoC serialize failed: {e}"))?;
+
+    Ok(TonCell {
+        bytes: base64_standard.encode(bytes),
+    })
+}
+
+#[cfg(test)]
+mod integration {
+    use super::*;
+    use crate::client::LiteServerClient;
+    use crate::tl::LiteServerGetMasterchainInfo;
+    use testcontainers_ton::{LocalLiteServer, SharedLiteServer};
+    use tower::ServiceExt;
+    use tracing_test::traced_test;
+
+    #[tokio::test]
+    #[traced_test]
+    async fn should_verify_config_proof() -> anyhow::Result<()> {
+        let (client, _server) = setup().await?;
+        let response = given_config_info(&client).await?;
+
+        let config = config_from_response(response)?;
+
+        assert!(!config.references.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn should_reject_config_proof_of_another_block() -> anyhow::Result<()> {
+        let (client, _server) = setup().await?;
+        let mut response = given_config_info(&client).await?;
+        response.id.root_hash[0] ^= 0xff;
+
+        let result = config_from_response(response);
+
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn should_return_empty_cell_for_missing_param() -> anyhow::Result<()> {
+        let (client, _server) = setup().await?;
+        let response = given_config_info(&client).await?;
+
+        let cell = config_param_from_response(response, 99999)?;
+
+        assert!(cell.bytes.is_empty());
+        Ok(())
+    }
+
+    async fn given_config_info(client: &LiteServerClient) -> anyhow::Result<LiteServerConfigInfo> {
+        let mc = client
+            .clone()
+            .oneshot(LiteServerGetMasterchainInfo::default())
+            .await
+            .map_err(|e| anyhow!(e))?;
+
+        client
+            .clone()
+            .oneshot(config_all_request(mc.last))
+            .await
+            .map_err(|e| anyhow!(e))
+    }
+
+    async fn setup() -> anyhow::Result<(LiteServerClient, SharedLiteServer)> {
+        let server = LocalLiteServer::shared().await?;
+        let client = LiteServerClient::connect(server.addr(), server.server_key()).await?;
+        Ok((client, server))
+    }
+}
@@ -1,6 +1,6 @@
 use crate::tl::{
-    BoxedBool, LiteServerBlockTransactions, LiteServerMasterchainInfo, LiteServerTransactionId3,
-    TonNodeBlockId, TonNodeBlockIdExt,
+    BoxedBool, Int256, LiteServerBlockTransactions, LiteServerMasterchainInfo,
+    LiteServerTransactionId3, TonNodeBlockId, TonNodeBlockIdExt,
 };
 use crate::tlb::blk_prev_info::BlkPrevInfo;
 use crate::tlb::block_header::BlockHeader;
@@ -13,6 +13,7 @@ use base64::Engine;
 use base64::engine::general_purpose::STANDARD as base64_standard;
 use std::sync::Arc;
 use ton_address::SmartContractAddress;
+use ton_tlb::message_body::decode_body;
 use ton_tower::response::{BlockIdExt, ShortTxId};
 use toner::tlb::ser::CellSerializeExt;
 use toner::tlb::{BagOfCellsArgs, BoC, Cell};
@@ -38,20 +39,22 @@ impl TryFrom<BlockIdExt> for TonNodeBlockIdExt {
             workchain: v.workchain,
             shard: v.shard,
             seqno: v.seqno,
-            root_hash: base64_standard
-                .decode(&v.root_hash)
-                .context("failed to decode root_hash")?
-                .try_into()
-                .map_err(|_| anyhow!("root_hash must be 32 bytes"))?,
-            file_hash: base64_standard
-                .decode(&v.file_hash)
-                .context("failed to decode file_hash")?
-                .try_into()
-                .map_err(|_| anyhow!("file_hash must be 32 bytes"))?,
+            root_hash: decode_hash(&v.root_hash).context("failed to decode root_hash")?,
+            file_hash: decode_hash(&v.file_hash).context("failed to decode file_hash")?,
         })
     }
 }
 
+pub(super) fn decode_hash(hash: &str) -> anyhow::Result<Int256> {
+    let raw = base64_standard
+        .decode(hash)
+        .map_err(|e| anyhow!("invalid base64 hash: {}", e))?;
+
+    raw.as_slice()
+        .try_into()
+        .map_err(|_| anyhow!("hash must be 32 bytes, got {}", raw.len()))
+}
+
 impl From<BlockIdExt> for TonNodeBlockId {
     fn from(v: BlockIdExt) -> Self {
         Self {
@@ -194,6 +197,7 @@ fn tlb_message_to_ton_client(cell: &Arc<Cell>) -> anyhow::Result<ton_tower::resp
     let body_cell = msg.body.to_cell(()).map_err(|e| anyhow!("{e}"))?;
     let body_hash = base64_standard.encode(body_cell.hash());
 
+    let decoded = decode_body(&body_cell);
     let body = {
         let boc = BoC::from_root(Arc::new(body_cell));
         let bytes = boc
@@ -245,6 +249,7 @@ fn tlb_message_to_ton_client(cell: &Arc<Cell>) -> anyhow::Result<ton_tower::resp
             body,
             init_state: String::new(),
         },
+        decoded,
     })
 }
 
@@ -263,6 +268,17 @@ fn extract_total_fees(tx: &Transaction) -> i64 {
     biguint_to_i64(&tx.total_fees.grams)
 }
 
+/// Parses the base64 BoC from `Transaction.data` back into the TL-B transaction.
+pub fn decode_transaction(data: &str) -> anyhow::Result<Transaction> {
+    let boc = BoC::parse_base64(data).map_err(|e| anyhow!("transaction: invalid BoC: {e}"))?;
+    let root = boc
+        .single_root()
+        .ok_or_else(|| anyhow!("transaction: single root expected"))?;
+
+    root.parse_fully(())
+        .map_err(|e| anyhow!("failed to parse transaction: {e}"))
+}
+
 pub fn transaction_to_ton_client(
     workchain: i32,
     root: &Arc<Cell>,
@@ -0,0 +1,45 @@
+use crate::adapter::convert::decode_hash;
+use crate::tl::{LiteServerGetLibraries, LiteServerLibraryResult};
+use anyhow::{Context, anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as base64_standard;
+use ton_tower::response::{Cell as TonCell, Library};
+use toner::tlb::BoC;
+
+pub(super) fn libraries_request(hashes: &[String]) -> anyhow::Result<LiteServerGetLibraries> {
+    let library_list = hashes
+        .iter()
+        .map(|hash| decode_hash(hash).with_context(|| format!("library hash {hash}")))
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(LiteServerGetLibraries { library_list })
+}
+
+// The lite-server is not trusted, so every library root must hash to the requested key.
+pub(super) fn libraries_from_response(
+    response: LiteServerLibraryResult,
+) -> anyhow::Result<Vec<Library>> {
+    response
+        .result
+        .into_iter()
+        .map(|entry| {
+            let boc = BoC::deserialize(&entry.data)?;
+            let root = boc
+                .single_root()
+                .ok_or_else(|| anyhow!("library: single root expected"))?;
+            if root.hash() != entry.hash {
+                bail!(
+                    "library {}: root hash mismatch",
+                    base64_standard.encode(entry.hash)
+                );
+            }
+
+            Ok(Library {
+                hash: base64_standard.encode(entry.hash),
+                cell: TonCell {
+                    bytes: base64_standard.encode(&entry.data),
+                },
+            })
+        })
+        .collect()
+}
@@ -1,16 +1,29 @@
 use crate::adapter::LiteServerAdapter;
 use crate::client::LiteServerClient;
+use crate::light_client::LightClient;
 use adnl_tcp::client::ServerKey;
 use anyhow::anyhow;
 use base64::Engine;
 use futures::FutureExt;
 use futures::future::BoxFuture;
+use std::sync::{Arc, OnceLock};
 use std::task::{Context, Poll};
 use ton_config::TonConfig;
 use tower::Service;
 
 #[derive(Default, Debug, Clone)]
-pub struct MakeLiteServerAdapter;
+pub struct MakeLiteServerAdapter {
+    // shared by every adapter so the trusted key block only moves forward
+    light_client: Option<Arc<OnceLock<LightClient>>>,
+}
+
+impl MakeLiteServerAdapter {
+    pub fn with_light_client(mut self) -> Self {
+        self.light_client = Some(Arc::default());
+
+        self
+    }
+}
 
 impl Service<TonConfig> for MakeLiteServerAdapter {
     type Response = LiteServerAdapter;
@@ -22,6 +35,8 @@ impl Service<TonConfig> for MakeLiteServerAdapter {
     }
 
     fn call(&mut self, config: TonConfig) -> Self::Future {
+        let light_client = self.light_client.clone();
+
         async move {
             let liteserver = config
                 .liteservers
@@ -38,7 +53,19 @@ impl Service<TonConfig> for MakeLiteServerAdapter {
             let inner = LiteServerClient::connect(addr, key).await?;
             tracing::info!("connected to liteserver at {}", addr);
 
-            Ok(LiteServerAdapter::new(inner))
+            let adapter = LiteServerAdapter::new(inner);
+            let Some(light_client) = light_client else {
+                return Ok(adapter);
+            };
+            let light_client = match light_client.get() {
+                Some(light_client) => light_client.clone(),
+                None => {
+                    let init = LightClient::from_config(&config)?;
+                    light_client.get_or_init(|| init).clone()
+                }
+            };
+
+            Ok(adapter.with_light_client(light_client))
         }
         .boxed()
     }
@@ -1,21 +1,27 @@
 mod account;
 mod block;
+mod config;
 mod convert;
+mod library;
 pub mod make;
 mod message;
+mod shard;
 mod smc;
 
 use crate::adapter::convert::{
     block_header_to_ton_client, block_transactions_to_ton_client, shard_descr_to_block_id_ext,
     transaction_to_ton_client,
 };
-use crate::client::LiteServerClient;
+use crate::client::{self, LiteServerClient};
+use crate::light_client::LightClient;
 use crate::tl::{
     BoxedBool, Int256, LiteServerAccountId, LiteServerGetAccountState, LiteServerGetAllShardsInfo,
-    LiteServerGetBlockHeader, LiteServerGetMasterchainInfo,
+    LiteServerGetBlockHeader, LiteServerGetConfigAll, LiteServerGetConfigParams,
+    LiteServerGetLibraries, LiteServerGetMasterchainInfo, LiteServerGetShardBlockProof,
     LiteServerGetTransactions as LiteServerGetTransactionsRequest, LiteServerListBlockTransactions,
-    LiteServerListBlockTransactionsExt, LiteServerLookupBlock, LiteServerSendMessage,
-    TonNodeBlockId, TonNodeBlockIdExt, True,
+    LiteServerListBlockTransactionsExt, LiteServerLookupBlock, LiteServerMasterchainInfo,
+    LiteServerRunSmcMethod, LiteServerSendMessage, LiteServerTransactionId3, TonNodeBlockId,
+    TonNodeBlockIdExt, True,
 };
 use crate::tlb::block_header::BlockHeader;
 use crate::tlb::merkle_proof::MerkleProof;
@@ -30,15 +36,34 @@ use futures::{FutureExt, TryFutureExt};
 use std::task::{Context, Poll};
 use ton_tower::request::*;
 use ton_tower::response::TransactionId;
+use ton_tower::service::error::RequestError;
 use toner::tlb::BoC;
 use toner::tlb::bits::de::{unpack_bytes, unpack_bytes_fully};
 use tower::Service;
 
+pub use config::decode_config_param;
+pub use convert::decode_transaction;
+
+/// Code of the lite-server answer rejecting the query itself, e.g. a transaction hash
+/// unknown to the account. Not ready, timeout and other codes describe the server state.
+const LITE_SERVER_QUERY_ERROR_CODE: i32 = -400;
+
+/// Marks the errors caused by the request, every other error counts as a server failure.
+pub fn client_error(e: client::Error) -> anyhow::Error {
+    match e {
+        client::Error::LiteServerError(ref error) if error.code == LITE_SERVER_QUERY_ERROR_CODE => {
+            RequestError(e.into()).into()
+        }
+        e => e.into(),
+    }
+}
+
+/// Fails the call with a [`RequestError`] when a field of the request cannot be converted.
 macro_rules! ok_or_else {
     ($expr:expr) => {
         match $expr {
             Ok(value) => value,
-            Err(e) => return futures::future::err(e.into()).boxed(),
+            Err(e) => return futures::future::err(RequestError(e.into()).into()).boxed(),
         }
     };
 }
@@ -46,11 +71,22 @@ macro_rules! ok_or_else {
 #[derive(Clone)]
 pub struct LiteServerAdapter {
     inner: LiteServerClient,
+    light_client: Option<LightClient>,
 }
 
 impl LiteServerAdapter {
     pub fn new(inner: LiteServerClient) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            light_client: None,
+        }
+    }
+
+    /// Rejects masterchain heads which are not proven by the light client.
+    pub fn with_light_client(mut self, light_client: LightClient) -> Self {
+        self.light_client = Some(light_client);
+
+        self
     }
 
     pub fn inner(&self) -> &LiteServerClient {
@@ -60,6 +96,23 @@ impl LiteServerAdapter {
     pub fn into_inner(self) -> LiteServerClient {
         self.inner
     }
+
+    fn verified_masterchain_info(
+        &mut self,
+    ) -> impl Future<Output = anyhow::Result<LiteServerMasterchainInfo>> + Send + 'static {
+        let client = self.inner.clone();
+        let light_client = self.light_client.clone();
+        let info = self.inner.call(LiteServerGetMasterchainInfo::default());
+
+        async move {
+            let info = info.await.map_err(client_error)?;
+            if let Some(light_client) = light_client {
+                light_client.verify(client, &info.last).await?;
+            }
+
+            Ok(info)
+        }
+    }
 }
 
 impl Service<GetMasterchainInfo> for LiteServerAdapter {
@@ -73,11 +126,7 @@ impl Service<GetMasterchainInfo> for LiteServerAdapter {
     }
 
     fn call(&mut self, _: GetMasterchainInfo) -> Self::Future {
-        self.inner
-            .call(LiteServerGetMasterchainInfo::default())
-            .ok_into()
-            .err_into()
-            .boxed()
+        self.verified_masterchain_info().ok_into().boxed()
     }
 }
 
@@ -92,10 +141,8 @@ impl Service<Sync> for LiteServerAdapter {
     }
 
     fn call(&mut self, _: Sync) -> Self::Future {
-        self.inner
-            .call(LiteServerGetMasterchainInfo::default())
+        self.verified_masterchain_info()
             .map_ok(|info| ton_tower::response::MasterchainInfo::from(info).last)
-            .err_into()
             .boxed()
     }
 }
@@ -112,13 +159,16 @@ impl Service<LookUpBlockBySeqno> for LiteServerAdapter {
 
     fn call(&mut self, req: LookUpBlockBySeqno) -> Self::Future {
         if req.seqno <= 0 {
-            return futures::future::err(anyhow!("seqno must be greater than 0")).boxed();
+            return futures::future::err(
+                RequestError(anyhow!("seqno must be greater than 0")).into(),
+            )
+            .boxed();
         }
         self.inner
             .call(LiteServerLookupBlock::seqno(TonNodeBlockId::new(
                 req.chain, req.shard, req.seqno,
             )))
-            .err_into()
+            .map_err(client_error)
             .and_then(async |response| {
                 block::verify_header_proof(&response.header_proof, &response.id.root_hash)?;
 
@@ -140,7 +190,8 @@ impl Service<LookUpBlockByLt> for LiteServerAdapter {
 
     fn call(&mut self, req: LookUpBlockByLt) -> Self::Future {
         if req.lt <= 0 {
-            return futures::future::err(anyhow!("lt must be greater than 0")).boxed();
+            return futures::future::err(RequestError(anyhow!("lt must be greater than 0")).into())
+                .boxed();
         }
         self.inner
             .call(LiteServerLookupBlock {
@@ -149,7 +200,7 @@ impl Service<LookUpBlockByLt> for LiteServerAdapter {
                 lt: Some(req.lt),
                 utime: None,
             })
-            .err_into()
+            .map_err(client_error)
             .and_then(async |response| {
                 block::verify_header_proof(&response.header_proof, &response.id.root_hash)?;
 
@@ -172,21 +223,22 @@ impl Service<GetShards> for LiteServerAdapter {
     fn call(&mut self, req: GetShards) -> Self::Future {
         let id: TonNodeBlockIdExt = ok_or_else!(req.block_id.try_into());
         if id.workchain != -1 {
-            return futures::future::err(anyhow!("workchain must be -1")).boxed();
+            return futures::future::err(RequestError(anyhow!("workchain must be -1")).into())
+                .boxed();
         }
         let expected_root_hash = id.root_hash;
 
         self.inner
             .call(LiteServerGetAllShardsInfo::new(id))
-            .err_into()
+            .map_err(client_error)
             .and_then(async move |response| {
-                // TODO verify data inclusion in proof via ShardState traversal (needs MaybePruned)
-                block::verify_block_proof(&response.proof, &expected_root_hash)?;
+                let block = block::verify_block_proof(&response.proof, &expected_root_hash)?;
 
                 let boc: BoC = unpack_bytes(&response.data, ())?;
                 let root = boc
                     .single_root()
                     .ok_or_else(|| anyhow!("single root expected"))?;
+                shard::verify_shard_hashes(&block, root)?;
                 let shard_hashes: ShardHashes = root.parse_fully(())?;
 
                 let block_ids = shard_hashes
@@ -220,7 +272,7 @@ impl Service<GetBlockHeader> for LiteServerAdapter {
 
         self.inner
             .call(LiteServerGetBlockHeader::new(id))
-            .err_into()
+            .map_err(client_error)
             .and_then(async move |response| {
                 let boc: BoC = unpack_bytes_fully(&response.header_proof, ())?;
                 let root = boc
@@ -242,6 +294,27 @@ impl Service<GetBlockHeader> for LiteServerAdapter {
     }
 }
 
+impl Service<GetShardBlockProof> for LiteServerAdapter {
+    type Response = ton_tower::response::ShardBlockProof;
+    type Error = anyhow::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        <LiteServerClient as Service<LiteServerGetShardBlockProof>>::poll_ready(&mut self.inner, cx)
+            .map_err(Into::into)
+    }
+
+    fn call(&mut self, req: GetShardBlockProof) -> Self::Future {
+        let id: TonNodeBlockIdExt = ok_or_else!(req.id.try_into());
+
+        self.inner
+            .call(LiteServerGetShardBlockProof { id: id.clone() })
+            .map_err(client_error)
+            .and_then(async move |response| shard::verify_shard_block_proof(&id, response))
+            .boxed()
+    }
+}
+
 impl Service<GetTransactionIds> for LiteServerAdapter {
     type Response = ton_tower::response::BlockTransactions;
     type Error = anyhow::Error;
@@ -260,20 +333,33 @@ impl Service<GetTransactionIds> for LiteServerAdapter {
         let expected_root_hash = id.root_hash;
 
         let mode = block::list_block_transactions_mode(req.after.is_some(), req.reverse, true);
+        let after: Option<LiteServerTransactionId3> = req.after.map(Into::into);
+        let reverse = req.reverse;
 
         self.inner
             .call(LiteServerListBlockTransactions {
                 id,
                 mode,
                 count: req.count,
-                after: req.after.map(Into::into),
+                after: after.clone(),
                 reverse_order: if req.reverse { Some(True {}) } else { None },
                 want_proof: Some(True {}),
             })
-            .err_into()
+            .map_err(client_error)
             .and_then(async move |response| {
-                // TODO verify transaction ids against proof via ShardAccountBlocks dict (needs MaybePruned)
-                block::verify_block_proof(&response.proof, &expected_root_hash)?;
+                let block = block::verify_block_proof(&response.proof, &expected_root_hash)?;
+                let transactions = response
+                    .ids
+                    .iter()
+                    .map(block::TransactionKey::try_from)
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                block::verify_block_transactions(
+                    &block,
+                    after.as_ref(),
+                    reverse,
+                    matches!(response.incomplete, BoxedBool::BoolTrue(_)),
+                    &transactions,
+                )?;
 
                 block_transactions_to_ton_client(response)
             })
@@ -299,33 +385,49 @@ impl Service<GetTransactions> for LiteServerAdapter {
         let expected_root_hash = id.root_hash;
 
         let mode = block::list_block_transactions_mode(req.after.is_some(), req.reverse, true);
+        let after: Option<LiteServerTransactionId3> = req.after.map(Into::into);
+        let reverse = req.reverse;
 
         self.inner
             .call(LiteServerListBlockTransactionsExt {
                 id,
                 mode,
                 count: req.count,
-                after: req.after.map(Into::into),
+                after: after.clone(),
                 reverse_order: if req.reverse { Some(True {}) } else { None },
                 want_proof: Some(True {}),
             })
-            .err_into()
+            .map_err(client_error)
             .and_then(async move |response| {
                 let incomplete = matches!(response.incomplete, BoxedBool::BoolTrue(_));
                 let workchain = response.id.workchain;
 
-                block::verify_block_proof(&response.proof, &expected_root_hash)?;
+                let block = block::verify_block_proof(&response.proof, &expected_root_hash)?;
 
+                let mut keys = Vec::new();
                 let mut transactions = Vec::new();
                 if !response.transactions.is_empty() {
                     let boc: BoC = BoC::deserialize(&response.transactions)?;
+                    keys.reserve(boc.roots().len());
                     transactions.reserve(boc.roots().len());
 
                     for root in boc.into_roots() {
                         let tx: Transaction = root.parse_fully(())?;
+                        keys.push(block::TransactionKey {
+                            account: tx.account_addr,
+                            lt: tx.lt as i64,
+                            hash: root.hash(),
+                        });
                         transactions.push(transaction_to_ton_client(workchain, &root, tx)?);
                     }
                 }
+                block::verify_block_transactions(
+                    &block,
+                    after.as_ref(),
+                    reverse,
+                    incomplete,
+                    &keys,
+                )?;
 
                 Ok(ton_tower::response::BlockTransactionsExt {
                     incomplete,
@@ -351,7 +453,7 @@ impl Service<SendMessage> for LiteServerAdapter {
 
         self.inner
             .call(req)
-            .err_into()
+            .map_err(client_error)
             .and_then(async |response| response.ensure_ok())
             .boxed()
     }
@@ -373,7 +475,7 @@ impl Service<SendMessageReturningHash> for LiteServerAdapter {
 
         self.inner
             .call(req)
-            .err_into()
+            .map_err(client_error)
             .and_then(async |response| {
                 response.ensure_ok()?;
 
@@ -396,9 +498,7 @@ impl Service<GetAccountState> for LiteServerAdapter {
     fn call(&mut self, req: GetAccountState) -> Self::Future {
         let client = self.inner.clone();
 
-        self.inner
-            .call(LiteServerGetMasterchainInfo::default())
-            .err_into()
+        self.verified_masterchain_info()
             .and_then(async move |mc| {
                 account::get_account_state_inner(client, req.address, mc.last).await
             })
@@ -422,8 +522,10 @@ impl Service<GetAccountStateOnBlock> for LiteServerAdapter {
 
         self.inner
             .call(request)
-            .err_into()
-            .and_then(async |response| account::account_state_from_response(response))
+            .map_err(client_error)
+            .and_then(async move |response| {
+                account::account_state_from_response(&req.address, response)
+            })
             .boxed()
     }
 }
@@ -441,9 +543,7 @@ impl Service<GetAccountStateByTransaction> for LiteServerAdapter {
     fn call(&mut self, req: GetAccountStateByTransaction) -> Self::Future {
         let client = self.inner.clone();
 
-        self.inner
-            .call(LiteServerGetMasterchainInfo::default())
-            .err_into()
+        self.verified_masterchain_info()
             .and_then(async move |mc| {
                 let address = req.address;
                 let tx = req.transaction_id;
@@ -486,7 +586,7 @@ impl Service<GetAccountTransactions> for LiteServerAdapter {
                 lt: from.lt,
                 hash,
             })
-            .err_into()
+            .map_err(client_error)
             .and_then(async move |response| {
                 let mut transactions: Vec<ton_tower::response::Transaction> = Vec::new();
                 let mut previous_transaction_id: Option<TransactionId> = None;
@@ -535,9 +635,7 @@ impl Service<GetShardAccountCell> for LiteServerAdapter {
     fn call(&mut self, req: GetShardAccountCell) -> Self::Future {
         let client = self.inner.clone();
 
-        self.inner
-            .call(LiteServerGetMasterchainInfo::default())
-            .err_into()
+        self.verified_masterchain_info()
             .and_then(async move |mc| {
                 account::get_shard_account_cell_inner(client, req.address, mc.last).await
             })
@@ -561,8 +659,10 @@ impl Service<GetShardAccountCellOnBlock> for LiteServerAdapter {
 
         self.inner
             .call(request)
-            .err_into()
-            .and_then(async |response| account::shard_account_cell_from_response(response))
+            .map_err(client_error)
+            .and_then(async move |response| {
+                account::shard_account_cell_from_response(&req.address, response)
+            })
             .boxed()
     }
 }
@@ -580,9 +680,7 @@ impl Service<GetShardAccountCellByTransaction> for LiteServerAdapter {
     fn call(&mut self, req: GetShardAccountCellByTransaction) -> Self::Future {
         let client = self.inner.clone();
 
-        self.inner
-            .call(LiteServerGetMasterchainInfo::default())
-            .err_into()
+        self.verified_masterchain_info()
             .and_then(async move |mc| {
                 let address = req.address;
                 let tx = req.transaction_id;
@@ -607,9 +705,7 @@ impl Service<RunGetMethod> for LiteServerAdapter {
     fn call(&mut self, req: RunGetMethod) -> Self::Future {
         let client = self.inner.clone();
 
-        self.inner
-            .call(LiteServerGetMasterchainInfo::default())
-            .err_into()
+        self.verified_masterchain_info()
             .and_then(async move |mc| {
                 let address = req.address;
                 let method = req.method;
@@ -620,3 +716,119 @@ impl Service<RunGetMethod> for LiteServerAdapter {
             .boxed()
     }
 }
+
+impl Service<RunGetMethodOnBlock> for LiteServerAdapter {
+    type Response = ton_tower::response::SmcRunResult;
+    type Error = anyhow::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        <LiteServerClient as Service<LiteServerRunSmcMethod>>::poll_ready(&mut self.inner, cx)
+            .map_err(Into::into)
+    }
+
+    fn call(&mut self, req: RunGetMethodOnBlock) -> Self::Future {
+        let id: TonNodeBlockIdExt = ok_or_else!(req.block_id.try_into());
+        let client = self.inner.clone();
+
+        async move {
+            smc::run_get_method_inner(client, req.address, id, &req.method, req.stack).await
+        }
+        .boxed()
+    }
+}
+
+impl Service<GetConfigAll> for LiteServerAdapter {
+    type Response = ton_tower::response::Cell;
+    type Error = anyhow::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        <LiteServerClient as Service<LiteServerGetConfigAll>>::poll_ready(&mut self.inner, cx)
+            .map_err(Into::into)
+    }
+
+    fn call(&mut self, req: GetConfigAll) -> Self::Future {
+        let id: TonNodeBlockIdExt = ok_or_else!(req.block_id.try_into());
+
+        self.inner
+            .call(config::config_all_request(id))
+            .map_err(client_error)
+            .and_then(async |response| config::config_all_from_response(response))
+            .boxed()
+    }
+}
+
+impl Service<GetConfigParam> for LiteServerAdapter {
+    type Response = ton_tower::response::Cell;
+    type Error = anyhow::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        <LiteServerClient as Service<LiteServerGetConfigParams>>::poll_ready(&mut self.inner, cx)
+            .map_err(Into::into)
+    }
+
+    fn call(&mut self, req: GetConfigParam) -> Self::Future {
+        let param = req.param;
+        let id: TonNodeBlockIdExt = ok_or_else!(req.block_id.try_into());
+
+        self.inner
+            .call(config::config_param_request(id, param))
+            .map_err(client_error)
+            .and_then(async move |response| config::config_param_from_response(response, param))
+            .boxed()
+    }
+}
+
+impl Service<GetLibraries> for LiteServerAdapter {
+    type Response = Vec<ton_tower::response::Library>;
+    type Error = anyhow::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        <LiteServerClient as Service<LiteServerGetLibraries>>::poll_ready(&mut self.inner, cx)
+            .map_err(Into::into)
+    }
+
+    fn call(&mut self, req: GetLibraries) -> Self::Future {
+        let request = ok_or_else!(library::libraries_request(&req.hashes));
+
+        self.inner
+            .call(request)
+            .map_err(client_error)
+            .and_then(async |response| library::libraries_from_response(response))
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tl::LiteServerError;
+
+    #[test]
+    fn should_mark_rejected_query_as_request_error() {
+        let error = client_error(client::Error::LiteServerError(LiteServerError {
+            code: -400,
+            message: "transaction hash mismatch".to_string(),
+        }));
+
+        assert!(RequestError::is_cause_of(&*error));
+    }
+
+    #[test]
+    fn should_not_mark_server_state_as_request_error() {
+        for code in [651, 652, 601] {
+            let error = client_error(client::Error::LiteServerError(LiteServerError {
+                code,
+                message: "cannot load block".to_string(),
+            }));
+
+            assert!(!RequestError::is_cause_of(&*error));
+        }
+        assert!(!RequestError::is_cause_of(&*client_error(
+            client::Error::Elapsed
+        )));
+    }
+}
@@ -0,0 +1,291 @@
+use crate::adapter::block::load_ref;
+use crate::tl::{LiteServerShardBlockProof, TonNodeBlockIdExt};
+use crate::tlb::blk_prev_info::BlkPrevInfo;
+use crate::tlb::block_info::BlockInfo;
+use crate::tlb::dict::HashmapEdge;
+use crate::tlb::merkle_proof::verified_root_of_boc;
+use anyhow::{Context, anyhow, bail, ensure};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as base64_standard;
+use std::sync::Arc;
+use ton_tower::response::{ShardBlockLink, ShardBlockProof};
+use toner::tlb::Cell;
+use toner::tlb::bits::bitvec::field::BitField;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::view::BitView;
+use toner::tlb::bits::de::BitReaderExt;
+
+// masterchain_block_extra#cca5
+const MC_BLOCK_EXTRA_TAG: u16 = 0xcca5;
+// shard_descr#b or shard_descr_new#a
+const SHARD_DESCR_TAG_BITS: usize = 4;
+
+/// Checks that `shard_hashes` is the `ShardHashes` of the proven masterchain `block`.
+pub(super) fn verify_shard_hashes(block: &Cell, shard_hashes: &Cell) -> anyhow::Result<()> {
+    let proven = find_shard_hashes(block)?;
+
+    // ShardHashes is `HashmapE 32 ^(BinTree ShardDescr)`: the emptiness bit and the root
+    match (shard_hashes.data.first().map(|b| *b), proven) {
+        (Some(true), Some(proven)) => {
+            let root = shard_hashes
+                .references
+                .first()
+                .ok_or_else(|| anyhow!("shard hashes: root reference is missing"))?;
+            ensure!(
+                root.hash() == proven.level_hash(0).1,
+                "shard hashes do not match the masterchain block"
+            );
+        }
+        (Some(false), None) => {}
+        _ => bail!("shard hashes do not match the masterchain block"),
+    }
+
+    Ok(())
+}
+
+/// Checks the chain of `liteServer.shardBlockLink` from the masterchain block down to `target`:
+/// the first link is the shard top block listed in `shard_hashes` of the masterchain block, every
+/// next one is a previous block of the link before it.
+pub(super) fn verify_shard_block_proof(
+    target: &TonNodeBlockIdExt,
+    response: LiteServerShardBlockProof,
+) -> anyhow::Result<ShardBlockProof> {
+    ensure!(
+        response.masterchain_id.workchain == -1,
+        "shard block proof: masterchain block expected"
+    );
+    let (first, rest) = response
+        .links
+        .split_first()
+        .ok_or_else(|| anyhow!("shard block proof: no links"))?;
+
+    if target.workchain == -1 {
+        // a masterchain block is its own proof
+        ensure!(
+            response.links.len() == 1 && first.id == *target && response.masterchain_id == *target,
+            "shard block proof: unexpected links for a masterchain block"
+        );
+    } else {
+        let block = verified_root_of_boc(&first.proof, &response.masterchain_id.root_hash)
+            .context("shard block proof: masterchain block")?;
+        verify_shard_top_block(&block, &first.id)?;
+    }
+
+    let mut current = &first.id;
+    for link in rest {
+        let block = verified_root_of_boc(&link.proof, &current.root_hash)
+            .with_context(|| format!("shard block proof: block {}", current.seqno))?;
+        verify_prev_block(&block, current, &link.id)?;
+
+        current = &link.id;
+    }
+    ensure!(
+        current == target,
+        "shard block proof ends at {}:{:x}:{} instead of {}:{:x}:{}",
+        current.workchain,
+        current.shard,
+        current.seqno,
+        target.workchain,
+        target.shard,
+        target.seqno
+    );
+
+    Ok(ShardBlockProof {
+        masterchain_id: response.masterchain_id.into(),
+        links: response
+            .links
+            .into_iter()
+            .map(|link| ShardBlockLink {
+                id: link.id.into(),
+                proof: base64_standard.encode(&link.proof),
+            })
+            .collect(),
+    })
+}
+
+/// `id` must be the descriptor of its shard in `shard_hashes` of the masterchain `block`.
+fn verify_shard_top_block(block: &Cell, id: &TonNodeBlockIdExt) -> anyhow::Result<()> {
+    let shard_hashes =
+        find_shard_hashes(block)?.ok_or_else(|| anyhow!("masterchain block has no shards"))?;
+    ensure!(!shard_hashes.is_exotic, "shard hashes: cell is pruned");
+
+    let leaf = HashmapEdge::new(shard_hashes)
+        .get(id.workchain.to_be_bytes().view_bits::<Msb0>())?
+        .ok_or_else(|| anyhow!("workchain {} is not in shard hashes", id.workchain))?;
+    let mut node = load_ref(leaf.refs, 0, "shard binary tree")?;
+
+    // bt_leaf$0 {X:Type} leaf:X = BinTree X;
+    // bt_fork$1 {X:Type} left:^(BinTree X) right:^(BinTree X) = BinTree X;
+    let shard = id.shard as u64;
+    let mut depth: u32 = 0;
+    while node.data.first().map(|b| *b) == Some(true) {
+        ensure!(depth < 60, "shard binary tree is too deep");
+        let right = (shard >> (63 - depth)) & 1 == 1;
+        node = load_ref(&node.references, usize::from(right), "shard binary tree")?;
+        depth += 1;
+    }
+    let expected_shard = (shard & !(u64::MAX >> depth)) | (1u64 << (63 - depth));
+    ensure!(
+        shard == expected_shard,
+        "shard {:x} is not a leaf of the shard binary tree",
+        id.shard
+    );
+
+    // shard_descr#b seq_no:uint32 reg_mc_seqno:uint32 start_lt:uint64 end_lt:uint64
+    //   root_hash:bits256 file_hash:bits256 ...
+    let mut bits = node
+        .data
+        .get(1 + SHARD_DESCR_TAG_BITS..)
+        .ok_or_else(|| anyhow!("shard descr: unexpected end of cell"))?;
+    let seq_no: u32 = bits.unpack(())?;
+    let _reg_mc_seqno: u32 = bits.unpack(())?;
+    let _start_lt: u64 = bits.unpack(())?;
+    let _end_lt: u64 = bits.unpack(())?;
+    let root_hash: [u8; 32] = bits.unpack(())?;
+    let file_hash: [u8; 32] = bits.unpack(())?;
+    ensure!(
+        i64::from(seq_no) == i64::from(id.seqno)
+            && root_hash == id.root_hash
+            && file_hash == id.file_hash,
+        "block {} is not the top block of its shard",
+        id.seqno
+    );
+
+    Ok(())
+}
+
+/// `prev` must be referenced by `prev_ref` of the proven `block`.
+fn verify_prev_block(
+    block: &Cell,
+    id: &TonNodeBlockIdExt,
+    prev: &TonNodeBlockIdExt,
+) -> anyhow::Result<()> {
+    ensure!(
+        prev.workchain == id.workchain,
+        "previous block is in another workchain"
+    );
+    let info: BlockInfo = load_ref(&block.references, 0, "block info")?.parse_fully(())?;
+    ensure!(
+        i64::from(info.seq_no) == i64::from(id.seqno),
+        "block info is of block {} instead of {}",
+        info.seq_no,
+        id.seqno
+    );
+
+    let shard = id.shard as u64;
+    let low_bit = shard & shard.wrapping_neg();
+    let candidates = match info.prev_ref {
+        BlkPrevInfo::Ref(prev_ref) if info.after_split => {
+            vec![(prev_ref, (shard - low_bit) | (low_bit << 1))]
+        }
+        BlkPrevInfo::Ref(prev_ref) => vec![(prev_ref, shard)],
+        BlkPrevInfo::RefPair(left, right) => vec![
+            (left, shard - (low_bit >> 1)),
+            (right, shard + (low_bit >> 1)),
+        ],
+    };
+
+    let found = candidates.iter().any(|(prev_ref, prev_shard)| {
+        i64::from(prev_ref.seq_no) == i64::from(prev.seqno)
+            && prev_ref.root_hash == prev.root_hash
+            && prev_ref.file_hash == prev.file_hash
+            && *prev_shard == prev.shard as u64
+    });
+    ensure!(
+        found,
+        "block {} is not a previous block of {}",
+        prev.seqno,
+        id.seqno
+    );
+
+    Ok(())
+}
+
+/// Returns the root of `shard_hashes` of a masterchain block, `None` when it is empty.
+fn find_shard_hashes(block: &Cell) -> anyhow::Result<Option<&Arc<Cell>>> {
+    // block#11ef55aa ... extra:^BlockExtra
+    // block_extra ... custom:(Maybe ^McBlockExtra)
+    let extra = load_ref(&block.references, 3, "block extra")?;
+    let custom = load_ref(&extra.references, 3, "masterchain block extra")?;
+
+    // masterchain_block_extra#cca5 key_block:(## 1) shard_hashes:ShardHashes ...
+    ensure!(
+        custom.data.len() > 17 && custom.data[..16].load_be::<u16>() == MC_BLOCK_EXTRA_TAG,
+        "masterchain block extra: invalid tag"
+    );
+    if !custom.data[17] {
+        return Ok(None);
+    }
+
+    custom
+        .references
+        .first()
+        .map(Some)
+        .ok_or_else(|| anyhow!("masterchain block extra: shard hashes reference is missing"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::convert::shard_descr_to_block_id_ext;
+    use crate::tlb::shard_hashes::ShardHashes;
+    use crate::tlb::tests::BLOCK_HEX;
+    use toner::tlb::BoC;
+    use toner::tlb::bits::bitvec::vec::BitVec;
+
+    fn given_block() -> Arc<Cell> {
+        BoC::deserialize(&hex::decode(BLOCK_HEX).unwrap())
+            .unwrap()
+            .into_single_root()
+            .unwrap()
+    }
+
+    fn shard_hashes_of(block: &Cell) -> Cell {
+        let root = find_shard_hashes(block).unwrap();
+
+        Cell {
+            is_exotic: false,
+            data: BitVec::repeat(root.is_some(), 1),
+            references: root.into_iter().cloned().collect(),
+        }
+    }
+
+    #[test]
+    fn should_verify_shard_hashes_of_the_block() {
+        let block = given_block();
+        let shard_hashes = shard_hashes_of(&block);
+
+        assert!(verify_shard_hashes(&block, &shard_hashes).is_ok());
+    }
+
+    #[test]
+    fn should_verify_shard_top_block() {
+        let block = given_block();
+        let shard_hashes: ShardHashes = shard_hashes_of(&block).parse_fully(()).unwrap();
+        let (workchain, shards) = shard_hashes.iter().next().unwrap();
+        let id: TonNodeBlockIdExt = shard_descr_to_block_id_ext(*workchain as i32, &shards[0])
+            .try_into()
+            .unwrap();
+        let mut other = id.clone();
+        other.root_hash = [0; 32];
+
+        assert!(verify_shard_top_block(&block, &id).is_ok());
+        assert!(verify_shard_top_block(&block, &other).is_err());
+    }
+
+    #[test]
+    fn should_reject_foreign_shard_hashes() {
+        let block = given_block();
+        let shard_hashes = Cell {
+            is_exotic: false,
+            data: BitVec::repeat(true, 1),
+            references: vec![Arc::new(Cell {
+                is_exotic: false,
+                data: BitVec::repeat(false, 8),
+                references: vec![],
+            })],
+        };
+
+        assert!(verify_shard_hashes(&block, &shard_hashes).is_err());
+    }
+}
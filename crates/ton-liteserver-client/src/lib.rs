@@ -1,5 +1,6 @@
 pub mod adapter;
 pub mod client;
+pub mod light_client;
 pub mod make;
 pub mod tl;
 pub mod tlb;
@@ -7,4 +8,5 @@ pub mod wait_seqno;
 
 pub use adapter::{LiteServerAdapter, make::MakeLiteServerAdapter};
 pub use client::LiteServerClient;
+pub use light_client::LightClient;
 pub use make::MakeLiteServerClient;
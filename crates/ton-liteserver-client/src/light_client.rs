@@ -0,0 +1,714 @@
+use crate::client::LiteServerClient;
+use crate::tl::{
+    BoxedBool, Int31, LiteServerBlockLinkBack, LiteServerBlockLinkForward,
+    LiteServerBoxedBlockLink, LiteServerGetBlockProof, LiteServerSignatureSet, TonNodeBlockIdExt,
+};
+use crate::tlb::block_header::BlockHeader;
+use crate::tlb::config_param::find_config_param;
+use crate::tlb::dict::HashmapEdge;
+use crate::tlb::ext_blk_ref::ExtBlkRef;
+use crate::tlb::mc_state_extra::McStateExtra;
+use crate::tlb::merkle_proof::verified_root_of_boc;
+use crate::tlb::merkle_update::MerkleUpdate;
+use crate::tlb::shard_state::ShardStateUnsplit;
+use crate::tlb::validator_set::{ValidatorDescr, ValidatorSet};
+use anyhow::{Context, anyhow, bail, ensure};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use ton_config::TonConfig;
+use ton_tower::response::BlockIdExt;
+use toner::tlb::Cell;
+use toner::tlb::bits::bitvec::field::BitField;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::view::BitView;
+use toner::tlb::bits::de::BitReaderExt;
+use tower::ServiceExt;
+
+const MASTERCHAIN_ID: i32 = -1;
+// `target_block` is set
+const BLOCK_PROOF_MODE: Int31 = 1;
+// `pub.ed25519 key:int256 = PublicKey`, the node id is the hash of the boxed key
+const PUB_ED25519_TAG: [u8; 4] = [0xc6, 0xb4, 0x13, 0x48];
+// `ton.blockId root_cell_hash:int256 file_hash:int256 = ton.BlockId` is what validators sign
+const TON_BLOCK_ID_TAG: [u8; 4] = [0x70, 0x6e, 0x0b, 0xc5];
+const CURRENT_VALIDATORS_PARAM: u32 = 34;
+
+/// Masterchain light client.
+///
+/// Starts from a block trusted out of band (the config `init_block`) and moves the trusted key
+/// block forward only through `liteServer.getBlockProof` chains: forward links must be signed
+/// by more than 2/3 of the masterchain validators of the previous key block, backward links
+/// must be found in `prev_blocks` of an already trusted state.
+/// Clones share the trusted state.
+#[derive(Debug, Clone)]
+pub struct LightClient {
+    state: Arc<Mutex<TrustedState>>,
+}
+
+#[derive(Debug)]
+struct TrustedState {
+    key_block: TonNodeBlockIdExt,
+    last_block: Option<TonNodeBlockIdExt>,
+}
+
+impl LightClient {
+    pub fn new(init_block: TonNodeBlockIdExt) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(TrustedState {
+                key_block: init_block,
+                last_block: None,
+            })),
+        }
+    }
+
+    pub fn from_config(config: &TonConfig) -> anyhow::Result<Self> {
+        let block = config
+            .init_block()
+            .ok_or_else(|| anyhow!("ton config does not contain an init block"))?;
+        ensure!(
+            block.workchain == MASTERCHAIN_ID,
+            "init block must be a masterchain block"
+        );
+
+        Ok(Self::new(
+            BlockIdExt {
+                workchain: block.workchain,
+                shard: block.shard,
+                seqno: block.seqno,
+                root_hash: block.root_hash,
+                file_hash: block.file_hash,
+            }
+            .try_into()?,
+        ))
+    }
+
+    pub fn trusted_key_block(&self) -> TonNodeBlockIdExt {
+        self.lock().key_block.clone()
+    }
+
+    /// Checks that `target` is reachable by a valid proof chain from the trusted key block,
+    /// advancing the trusted key block along the way.
+    pub async fn verify(
+        &self,
+        client: LiteServerClient,
+        target: &TonNodeBlockIdExt,
+    ) -> anyhow::Result<()> {
+        let mut known = {
+            let state = self.lock();
+            if state.last_block.as_ref() == Some(target) || state.key_block == *target {
+                return Ok(());
+            }
+
+            state.key_block.clone()
+        };
+
+        loop {
+            let proof = client
+                .clone()
+                .oneshot(LiteServerGetBlockProof {
+                    mode: BLOCK_PROOF_MODE,
+                    known_block: known.clone(),
+                    target_block: Some(target.clone()),
+                })
+                .await?;
+            ensure!(
+                proof.from == known,
+                "block proof starts at {} instead of the trusted block {}",
+                proof.from.seqno,
+                known.seqno
+            );
+
+            let mut current = known;
+            for step in &proof.steps {
+                let (to, to_key_block) = verify_link(&current, step)
+                    .with_context(|| format!("block proof link from {}", current.seqno))?;
+                if to_key_block {
+                    self.advance_key_block(&to);
+                }
+                current = to;
+            }
+            ensure!(
+                proof.to == current,
+                "block proof ends at {} instead of {}",
+                current.seqno,
+                proof.to.seqno
+            );
+
+            if matches!(proof.complete, BoxedBool::BoolTrue(_)) {
+                ensure!(
+                    current == *target,
+                    "block proof ends at {} instead of the target {}",
+                    current.seqno,
+                    target.seqno
+                );
+                self.lock().last_block = Some(current);
+
+                return Ok(());
+            }
+            ensure!(
+                !proof.steps.is_empty(),
+                "block proof is incomplete and empty"
+            );
+
+            known = current;
+        }
+    }
+
+    fn advance_key_block(&self, block: &TonNodeBlockIdExt) {
+        let mut state = self.lock();
+        if block.seqno > state.key_block.seqno {
+            tracing::debug!(seqno = block.seqno, "trusted key block advanced");
+            state.key_block = block.clone();
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TrustedState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Returns the block the link leads to and whether it is a key block.
+fn verify_link(
+    trusted: &TonNodeBlockIdExt,
+    link: &LiteServerBoxedBlockLink,
+) -> anyhow::Result<(TonNodeBlockIdExt, bool)> {
+    let (from, to, to_key_block, dest_proof) = match link {
+        LiteServerBoxedBlockLink::LiteServerBlockLinkBack(link) => {
+            verify_backward_link(link)?;
+            (&link.from, &link.to, &link.to_key_block, &link.dest_proof)
+        }
+        LiteServerBoxedBlockLink::LiteServerBlockLinkForward(link) => {
+            verify_forward_link(link)?;
+            (&link.from, &link.to, &link.to_key_block, &link.dest_proof)
+        }
+    };
+    ensure!(from == trusted, "link does not start at the trusted block");
+    ensure!(
+        from.workchain == MASTERCHAIN_ID && to.workchain == MASTERCHAIN_ID,
+        "link leaves the masterchain"
+    );
+
+    let to_key_block = matches!(to_key_block, BoxedBool::BoolTrue(_));
+    if dest_proof.is_empty() {
+        ensure!(
+            !to_key_block,
+            "key block {} without a header proof",
+            to.seqno
+        );
+    } else {
+        verify_dest_proof(to, dest_proof, to_key_block)?;
+    }
+
+    Ok((to.clone(), to_key_block))
+}
+
+/// `to` is older than `from`: it must be listed in `prev_blocks` of the state of `from`.
+fn verify_backward_link(link: &LiteServerBlockLinkBack) -> anyhow::Result<()> {
+    ensure!(
+        link.to.seqno < link.from.seqno,
+        "backward link goes forward"
+    );
+
+    let block = verified_root_of_boc(&link.proof, &link.from.root_hash)?;
+    let state_update: MerkleUpdate<Cell> = block
+        .references
+        .get(2)
+        .ok_or_else(|| anyhow!("block proof: state update is missing"))?
+        .parse_fully(())?;
+
+    let state = verified_root_of_boc(&link.state_proof, &state_update.new_hash)?;
+    let state: ShardStateUnsplit = state.parse_fully(())?;
+    let extra: McStateExtra = state
+        .custom
+        .ok_or_else(|| anyhow!("state proof: state has no McStateExtra"))?
+        .parse_fully(())?;
+
+    // flags:(## 16) validator_info:(uint32, uint32, Bool) prev_blocks:OldMcBlocksInfo
+    let prev_blocks = match extra.info.data.get(16 + 65).map(|b| *b) {
+        Some(true) => extra
+            .info
+            .references
+            .first()
+            .ok_or_else(|| anyhow!("state proof: prev_blocks reference is missing"))?,
+        _ => bail!("state proof: prev_blocks is empty"),
+    };
+
+    let seqno = u32::try_from(link.to.seqno)?;
+    let leaf = HashmapEdge::new(prev_blocks)
+        .get(seqno.to_be_bytes().view_bits::<Msb0>())?
+        .ok_or_else(|| anyhow!("block {seqno} is not in prev_blocks"))?;
+
+    // ahmn_leaf extra:KeyMaxLt value:KeyExtBlkRef, KeyMaxLt is key:Bool max_end_lt:uint64
+    let mut bits = leaf
+        .bits
+        .get(65..)
+        .ok_or_else(|| anyhow!("prev_blocks: unexpected end of cell"))?;
+    let is_key_block: bool = bits.unpack(())?;
+    let blk_ref: ExtBlkRef = bits.unpack(())?;
+    ensure!(
+        blk_ref.seq_no == seqno
+            && blk_ref.root_hash == link.to.root_hash
+            && blk_ref.file_hash == link.to.file_hash,
+        "block {seqno} does not match prev_blocks"
+    );
+    if matches!(link.to_key_block, BoxedBool::BoolTrue(_)) {
+        ensure!(is_key_block, "block {seqno} is not a key block");
+    }
+
+    Ok(())
+}
+
+/// `to` is newer than the key block `from`: it must be signed by its masterchain validators.
+fn verify_forward_link(link: &LiteServerBlockLinkForward) -> anyhow::Result<()> {
+    ensure!(
+        link.to.seqno > link.from.seqno,
+        "forward link goes backward"
+    );
+
+    let config = key_block_config(&link.from, &link.config_proof)?;
+    let validators = find_config_param(&config, CURRENT_VALIDATORS_PARAM)?
+        .ok_or_else(|| anyhow!("config proof: validator set is missing"))?;
+    let validators = ValidatorSet::from_cell(&validators)?;
+
+    // the header names the validator set and the catchain which signed the block
+    ensure!(
+        !link.dest_proof.is_empty(),
+        "forward link to {} without a header proof",
+        link.to.seqno
+    );
+    let header = verified_root_of_boc(&link.dest_proof, &link.to.root_hash)?;
+    let header: BlockHeader = header.parse_fully(())?;
+
+    verify_signatures(
+        &validators,
+        &link.to,
+        header.info.gen_validator_list_hash_short,
+        header.info.gen_catchain_seqno,
+        &link.signatures,
+    )
+}
+
+fn key_block_config(from: &TonNodeBlockIdExt, config_proof: &[u8]) -> anyhow::Result<Arc<Cell>> {
+    // the zerostate has no block, the config is proven against the state itself
+    if from.seqno == 0 {
+        let state = verified_root_of_boc(config_proof, &from.root_hash)?;
+        let state: ShardStateUnsplit = state.parse_fully(())?;
+        let extra: McStateExtra = state
+            .custom
+            .ok_or_else(|| anyhow!("config proof: state has no McStateExtra"))?
+            .parse_fully(())?;
+
+        return Ok(Arc::new(extra.config.config));
+    }
+
+    let block = verified_root_of_boc(config_proof, &from.root_hash)?;
+    // block_extra in_msg_descr:^ out_msg_descr:^ account_blocks:^ ... custom:(Maybe ^McBlockExtra)
+    let custom = block
+        .references
+        .get(3)
+        .and_then(|extra| extra.references.get(3))
+        .ok_or_else(|| anyhow!("config proof: McBlockExtra is missing"))?;
+
+    // masterchain_block_extra#cca5 key_block:(## 1) ... config:key_block?ConfigParams,
+    // the config is the last reference of a key block
+    let is_key_block =
+        custom.data.len() > 16 && custom.data[..16].load_be::<u16>() == 0xcca5 && custom.data[16];
+    ensure!(is_key_block, "block {} is not a key block", from.seqno);
+
+    custom
+        .references
+        .last()
+        .cloned()
+        .ok_or_else(|| anyhow!("config proof: config is missing"))
+}
+
+fn verify_signatures(
+    validators: &ValidatorSet,
+    block: &TonNodeBlockIdExt,
+    validator_set_hash: u32,
+    catchain_seqno: u32,
+    signatures: &LiteServerSignatureSet,
+) -> anyhow::Result<()> {
+    ensure!(
+        signatures.validator_set_hash as u32 == validator_set_hash,
+        "block {} is signed by validator set {:x} instead of {:x}",
+        block.seqno,
+        signatures.validator_set_hash,
+        validator_set_hash
+    );
+    ensure!(
+        signatures.catchain_seqno as u32 == catchain_seqno,
+        "block {} is signed in catchain {} instead of {}",
+        block.seqno,
+        signatures.catchain_seqno,
+        catchain_seqno
+    );
+
+    // masterchain blocks are signed by the `main` validators with the greatest weight,
+    // which are the first ones in the list
+    let main: HashMap<[u8; 32], (VerifyingKey, u64)> = validators
+        .list
+        .values()
+        .take(usize::from(validators.main))
+        .map(|descr| {
+            let (public_key, weight) = match descr {
+                ValidatorDescr::Validator { public_key, weight }
+                | ValidatorDescr::ValidatorAddr {
+                    public_key, weight, ..
+                } => (public_key.pubkey, *weight),
+            };
+
+            Ok((
+                node_id_short(&public_key),
+                (VerifyingKey::from_bytes(&public_key)?, weight),
+            ))
+        })
+        .collect::<anyhow::Result<_>>()?;
+    let total_weight: u128 = main.values().map(|(_, weight)| u128::from(*weight)).sum();
+
+    let mut message = TON_BLOCK_ID_TAG.to_vec();
+    message.extend_from_slice(&block.root_hash);
+    message.extend_from_slice(&block.file_hash);
+
+    let mut signed = HashMap::new();
+    for signature in &signatures.signatures {
+        let (key, weight) = main
+            .get(&signature.node_id_short)
+            .ok_or_else(|| anyhow!("signature of an unknown validator"))?;
+        key.verify(&message, &Signature::from_slice(&signature.signature)?)?;
+
+        signed.insert(signature.node_id_short, u128::from(*weight));
+    }
+    let signed_weight: u128 = signed.values().sum();
+
+    ensure!(
+        signed_weight * 3 > total_weight * 2,
+        "block {} is signed by {} of {} validator weight",
+        block.seqno,
+        signed_weight,
+        total_weight
+    );
+
+    Ok(())
+}
+
+fn verify_dest_proof(
+    block: &TonNodeBlockIdExt,
+    dest_proof: &[u8],
+    is_key_block: bool,
+) -> anyhow::Result<()> {
+    let header = verified_root_of_boc(dest_proof, &block.root_hash)?;
+    let header: BlockHeader = header.parse_fully(())?;
+    ensure!(
+        i64::from(header.info.seq_no) == i64::from(block.seqno),
+        "header proof is for block {} instead of {}",
+        header.info.seq_no,
+        block.seqno
+    );
+    ensure!(
+        header.info.key_block == is_key_block,
+        "block {} key block flag mismatch",
+        block.seqno
+    );
+
+    Ok(())
+}
+
+fn node_id_short(public_key: &[u8; 32]) -> [u8; 32] {
+    Sha256::default()
+        .chain_update(PUB_ED25519_TAG)
+        .chain_update(public_key)
+        .finalize()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tlb::validator_set::SigPubKey;
+    use ed25519_dalek::{Signer, SigningKey};
+    use std::collections::BTreeMap;
+
+    const VALIDATOR_SET_HASH: u32 = 0xdeadbeef;
+    const CATCHAIN_SEQNO: u32 = 7;
+
+    fn block_id(seqno: i32) -> TonNodeBlockIdExt {
+        TonNodeBlockIdExt {
+            workchain: MASTERCHAIN_ID,
+            shard: i64::MIN,
+            seqno,
+            root_hash: [1; 32],
+            file_hash: [2; 32],
+        }
+    }
+
+    fn validators(keys: &[SigningKey], main: u16) -> ValidatorSet {
+        ValidatorSet {
+            utime_since: 0,
+            utime_until: u32::MAX,
+            total: keys.len() as u16,
+            main,
+            total_weight: None,
+            list: keys
+                .iter()
+                .enumerate()
+                .map(|(i, key)| {
+                    let descr = ValidatorDescr::Validator {
+                        public_key: SigPubKey {
+                            pubkey: key.verifying_key().to_bytes(),
+                        },
+                        weight: 10,
+                    };
+
+                    (i as u16, descr)
+                })
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    fn sign(keys: &[SigningKey], block: &TonNodeBlockIdExt) -> LiteServerSignatureSet {
+        let mut message = TON_BLOCK_ID_TAG.to_vec();
+        message.extend_from_slice(&block.root_hash);
+        message.extend_from_slice(&block.file_hash);
+
+        LiteServerSignatureSet {
+            validator_set_hash: VALIDATOR_SET_HASH as i32,
+            catchain_seqno: CATCHAIN_SEQNO as i32,
+            signatures: keys
+                .iter()
+                .map(|key| crate::tl::LiteServerSignature {
+                    node_id_short: node_id_short(&key.verifying_key().to_bytes()),
+                    signature: key.sign(&message).to_bytes().to_vec(),
+                })
+                .collect(),
+        }
+    }
+
+    fn keys(count: u8) -> Vec<SigningKey> {
+        (1..=count)
+            .map(|i| SigningKey::from_bytes(&[i; 32]))
+            .collect()
+    }
+
+    #[test]
+    fn should_accept_block_signed_by_main_validators() {
+        let keys = keys(4);
+        let block = block_id(10);
+
+        let result = verify_signatures(
+            &validators(&keys, 4),
+            &block,
+            VALIDATOR_SET_HASH,
+            CATCHAIN_SEQNO,
+            &sign(&keys[..3], &block),
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn should_reject_block_signed_by_two_thirds() {
+        let keys = keys(3);
+        let block = block_id(10);
+
+        let result = verify_signatures(
+            &validators(&keys, 3),
+            &block,
+            VALIDATOR_SET_HASH,
+            CATCHAIN_SEQNO,
+            &sign(&keys[..2], &block),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_reject_signature_of_validator_outside_main() {
+        let keys = keys(4);
+        let block = block_id(10);
+
+        let result = verify_signatures(
+            &validators(&keys, 3),
+            &block,
+            VALIDATOR_SET_HASH,
+            CATCHAIN_SEQNO,
+            &sign(&keys[1..], &block),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_reject_signature_of_another_block() {
+        let keys = keys(3);
+        let block = block_id(10);
+        let mut other = block_id(10);
+        other.root_hash = [3; 32];
+
+        let result = verify_signatures(
+            &validators(&keys, 3),
+            &other,
+            VALIDATOR_SET_HASH,
+            CATCHAIN_SEQNO,
+            &sign(&keys, &block),
+        );
+
+        assert!(result.is_err());
+    }
+    #[test]
+    fn should_reject_signatures_of_another_validator_set() {
+        let keys = keys(3);
+        let block = block_id(10);
+
+        let result = verify_signatures(
+            &validators(&keys, 3),
+            &block,
+            VALIDATOR_SET_HASH + 1,
+            CATCHAIN_SEQNO,
+            &sign(&keys, &block),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_reject_signatures_of_another_catchain() {
+        let keys = keys(3);
+        let block = block_id(10);
+
+        let result = verify_signatures(
+            &validators(&keys, 3),
+            &block,
+            VALIDATOR_SET_HASH,
+            CATCHAIN_SEQNO + 1,
+            &sign(&keys, &block),
+        );
+
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod integration {
+    use super::*;
+    use crate::tl::{LiteServerGetMasterchainInfo, LiteServerPartialBlockProof};
+    use testcontainers_ton::{LocalLiteServer, SharedLiteServer};
+    use tracing_test::traced_test;
+
+    #[tokio::test]
+    #[traced_test]
+    async fn should_verify_last_block_from_zero_state() -> anyhow::Result<()> {
+        let (client, _server) = setup().await?;
+        let (zero_state, last) = given_zero_state_and_last(&client).await?;
+        let light_client = LightClient::new(zero_state);
+
+        light_client.verify(client, &last).await?;
+
+        assert!(light_client.trusted_key_block().seqno <= last.seqno);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn should_reject_link_to_tampered_block() -> anyhow::Result<()> {
+        let (client, _server) = setup().await?;
+        let proof = given_block_proof(&client).await?;
+        let mut link = proof.steps[0].clone();
+        match &mut link {
+            LiteServerBoxedBlockLink::LiteServerBlockLinkBack(link) => link.to.root_hash[0] ^= 0xff,
+            LiteServerBoxedBlockLink::LiteServerBlockLinkForward(link) => {
+                link.to.root_hash[0] ^= 0xff
+            }
+        }
+
+        let result = verify_link(&proof.from, &link);
+
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn should_reject_link_from_untrusted_block() -> anyhow::Result<()> {
+        let (client, _server) = setup().await?;
+        let proof = given_block_proof(&client).await?;
+        let mut untrusted = proof.from.clone();
+        untrusted.root_hash[0] ^= 0xff;
+
+        let result = verify_link(&untrusted, &proof.steps[0]);
+
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn should_reject_forward_link_without_signatures() -> anyhow::Result<()> {
+        let (client, _server) = setup().await?;
+        let proof = given_block_proof(&client).await?;
+        let Some(LiteServerBoxedBlockLink::LiteServerBlockLinkForward(link)) =
+            proof.steps.iter().find(|step| {
+                matches!(
+                    step,
+                    LiteServerBoxedBlockLink::LiteServerBlockLinkForward(_)
+                )
+            })
+        else {
+            bail!("block proof has no forward link");
+        };
+        let from = link.from.clone();
+        let mut link = link.clone();
+        link.signatures.signatures.clear();
+
+        let result = verify_link(
+            &from,
+            &LiteServerBoxedBlockLink::LiteServerBlockLinkForward(link),
+        );
+
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    async fn given_zero_state_and_last(
+        client: &LiteServerClient,
+    ) -> anyhow::Result<(TonNodeBlockIdExt, TonNodeBlockIdExt)> {
+        let info = client
+            .clone()
+            .oneshot(LiteServerGetMasterchainInfo::default())
+            .await?;
+        let zero_state = TonNodeBlockIdExt {
+            workchain: info.init.workchain,
+            shard: i64::MIN,
+            seqno: 0,
+            root_hash: info.init.root_hash,
+            file_hash: info.init.file_hash,
+        };
+
+        Ok((zero_state, info.last))
+    }
+
+    async fn given_block_proof(
+        client: &LiteServerClient,
+    ) -> anyhow::Result<LiteServerPartialBlockProof> {
+        let (zero_state, last) = given_zero_state_and_last(client).await?;
+        let proof = client
+            .clone()
+            .oneshot(LiteServerGetBlockProof {
+                mode: BLOCK_PROOF_MODE,
+                known_block: zero_state,
+                target_block: Some(last),
+            })
+            .await?;
+        ensure!(!proof.steps.is_empty(), "block proof is empty");
+
+        Ok(proof)
+    }
+
+    async fn setup() -> anyhow::Result<(LiteServerClient, SharedLiteServer)> {
+        let server = LocalLiteServer::shared().await?;
+        let client = LiteServerClient::connect(server.addr(), server.server_key()).await?;
+        Ok((client, server))
+    }
+}
@@ -0,0 +1,79 @@
+use crate::tlb::dict::HashmapEdge;
+use crate::tlb::gas_limits_prices::GasLimitsPrices;
+use crate::tlb::msg_forward_prices::MsgForwardPrices;
+use crate::tlb::storage_prices::StoragePrices;
+use crate::tlb::validator_set::ValidatorSet;
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::vec::BitVec;
+use toner::tlb::bits::bitvec::view::BitView;
+use toner::tlb::bits::de::unpack_fully;
+use toner::tlb::hashmap::Hashmap;
+use toner::tlb::{Cell, Data};
+
+/// Looks up `id` in the `Hashmap 32 ^Cell` config dictionary. Only the path to the key is read,
+/// so the dictionary may come from a proof with every other param pruned.
+pub fn find_config_param(config: &Cell, id: u32) -> Result<Option<Arc<Cell>>> {
+    let leaf = HashmapEdge::new(config).get(id.to_be_bytes().view_bits::<Msb0>())?;
+
+    Ok(leaf.and_then(|leaf| leaf.refs.first().cloned()))
+}
+
+/// Typed value of the config params that are commonly needed to estimate fees.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigParam {
+    /// ```tlb
+    /// _ (Hashmap 32 StoragePrices) = ConfigParam 18;
+    /// ```
+    StoragePrices(Vec<StoragePrices>),
+    /// ```tlb
+    /// config_mc_gas_prices#_ GasLimitsPrices = ConfigParam 20;
+    /// ```
+    MasterchainGasPrices(GasLimitsPrices),
+    /// ```tlb
+    /// config_gas_prices#_ GasLimitsPrices = ConfigParam 21;
+    /// ```
+    GasPrices(GasLimitsPrices),
+    /// ```tlb
+    /// config_mc_fwd_prices#_ MsgForwardPrices = ConfigParam 24;
+    /// ```
+    MasterchainMsgForwardPrices(MsgForwardPrices),
+    /// ```tlb
+    /// config_fwd_prices#_ MsgForwardPrices = ConfigParam 25;
+    /// ```
+    MsgForwardPrices(MsgForwardPrices),
+    /// ```tlb
+    /// _ cur_validators:ValidatorSet = ConfigParam 34;
+    /// ```
+    CurrentValidators(ValidatorSet),
+}
+
+impl ConfigParam {
+    /// Decodes the value cell of param `id`; `None` means the param has no typed representation.
+    pub fn from_cell(id: u32, cell: &Cell) -> Result<Option<Self>> {
+        let bits = cell.data.as_bitslice();
+
+        let param = match id {
+            18 => Self::StoragePrices(
+                cell.parse_fully_as::<HashMap<BitVec<u8, Msb0>, StoragePrices>, Hashmap<Data>>((
+                    32,
+                    (),
+                ))?
+                .into_iter()
+                .collect::<BTreeMap<_, _>>()
+                .into_values()
+                .collect(),
+            ),
+            20 => Self::MasterchainGasPrices(unpack_fully(bits, ())?),
+            21 => Self::GasPrices(unpack_fully(bits, ())?),
+            24 => Self::MasterchainMsgForwardPrices(unpack_fully(bits, ())?),
+            25 => Self::MsgForwardPrices(unpack_fully(bits, ())?),
+            34 => Self::CurrentValidators(ValidatorSet::from_cell(cell)?),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(param))
+    }
+}
@@ -0,0 +1,287 @@
+use anyhow::{Result, anyhow, bail};
+use std::cmp::Ordering;
+use std::sync::Arc;
+use toner::tlb::Cell;
+use toner::tlb::bits::bitvec::field::BitField;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::slice::BitSlice;
+use toner::tlb::bits::bitvec::vec::BitVec;
+
+type Bits = BitSlice<u8, Msb0>;
+
+/// A lookup reached a branch the merkle proof does not include.
+#[derive(Debug, thiserror::Error)]
+#[error("hashmap fork: branch is pruned")]
+pub struct PrunedBranch;
+
+/// ```tlb
+/// hm_edge#_ {n:#} {X:Type} {l:#} {m:#} label:(HmLabel ~l n)
+///           {n = (~m) + l} node:(HashmapNode m X) = Hashmap n X;
+///
+/// hmn_leaf#_ {X:Type} value:X = HashmapNode 0 X;
+/// hmn_fork#_ {n:#} {X:Type} left:^(Hashmap n X)
+///            right:^(Hashmap n X) = HashmapNode (n + 1) X;
+/// ```
+///
+/// Only for lookups in merkle proofs: unlike `toner::tlb::hashmap::Hashmap`, the edge is walked
+/// lazily, so lookups only touch the cells on the path to the key while every other branch is
+/// pruned. Complete dictionaries are parsed with the `toner` types.
+#[derive(Debug, Clone, Copy)]
+pub struct HashmapEdge<'a> {
+    pub bits: &'a Bits,
+    pub refs: &'a [Arc<Cell>],
+}
+
+impl<'a> HashmapEdge<'a> {
+    pub fn new(cell: &'a Cell) -> Self {
+        Self {
+            bits: cell.data.as_bitslice(),
+            refs: &cell.references,
+        }
+    }
+
+    /// Returns the leaf for `key`: the bits right after the last label and the leaf references.
+    pub fn get(self, key: &Bits) -> Result<Option<HashmapEdge<'a>>> {
+        let mut edge = self;
+        let mut key = key;
+
+        loop {
+            let (label, rest) = read_label(edge.bits, key.len())?;
+            if !key.starts_with(label.as_bitslice()) {
+                return Ok(None);
+            }
+
+            key = &key[label.len()..];
+            if key.is_empty() {
+                return Ok(Some(HashmapEdge {
+                    bits: rest,
+                    refs: edge.refs,
+                }));
+            }
+
+            edge = edge.child(key[0])?;
+            key = &key[1..];
+        }
+    }
+
+    /// Returns the leaf with the nearest key after `key`, or before it when `reverse`.
+    /// `key` itself is returned only when `inclusive`.
+    ///
+    /// Only the branches that may hold the answer are loaded, like `lookup_nearest_key` does.
+    pub fn nearest(
+        self,
+        key: &Bits,
+        reverse: bool,
+        inclusive: bool,
+    ) -> Result<Option<(BitVec<u8, Msb0>, HashmapEdge<'a>)>> {
+        let (mut label, rest) = read_label(self.bits, key.len())?;
+        match label.as_bitslice().cmp(&key[..label.len()]) {
+            Ordering::Equal => {}
+            // the whole edge lies on the wanted side of `key`
+            Ordering::Greater if !reverse => return self.bound(key.len(), false).map(Some),
+            Ordering::Less if reverse => return self.bound(key.len(), true).map(Some),
+            _ => return Ok(None),
+        }
+
+        let key = &key[label.len()..];
+        if key.is_empty() {
+            return Ok(inclusive.then_some((
+                label,
+                HashmapEdge {
+                    bits: rest,
+                    refs: self.refs,
+                },
+            )));
+        }
+
+        let branch = key[0];
+        let found = match self.child(branch)?.nearest(&key[1..], reverse, inclusive)? {
+            Some(found) => Some((branch, found)),
+            // the sibling is entirely after (or before when `reverse`) the key
+            None if branch == reverse => {
+                Some((!branch, self.child(!branch)?.bound(key.len() - 1, reverse)?))
+            }
+            None => None,
+        };
+
+        Ok(found.map(|(branch, (suffix, leaf))| {
+            label.push(branch);
+            label.extend_from_bitslice(&suffix);
+
+            (label, leaf)
+        }))
+    }
+
+    /// Returns the leaf with the smallest key, the greatest one when `max`.
+    fn bound(self, key_len: usize, max: bool) -> Result<(BitVec<u8, Msb0>, HashmapEdge<'a>)> {
+        let mut edge = self;
+        let mut key = BitVec::new();
+
+        loop {
+            let (label, rest) = read_label(edge.bits, key_len - key.len())?;
+            key.extend_from_bitslice(label.as_bitslice());
+            if key.len() == key_len {
+                return Ok((
+                    key,
+                    HashmapEdge {
+                        bits: rest,
+                        refs: edge.refs,
+                    },
+                ));
+            }
+
+            edge = edge.child(max)?;
+            key.push(max);
+        }
+    }
+
+    fn child(self, branch: bool) -> Result<HashmapEdge<'a>> {
+        let next = self
+            .refs
+            .get(usize::from(branch))
+            .ok_or_else(|| anyhow!("hashmap fork: reference is missing"))?;
+        if next.is_exotic {
+            return Err(PrunedBranch.into());
+        }
+
+        Ok(HashmapEdge::new(next))
+    }
+}
+
+/// ```tlb
+/// hml_short$0 {m:#} {n:#} len:(Unary ~n) {n <= m} s:(n * Bit) = HmLabel ~n m;
+/// hml_long$10 {m:#} n:(#<= m) s:(n * Bit) = HmLabel ~n m;
+/// hml_same$11 {m:#} v:Bit n:(#<= m) = HmLabel ~n m;
+/// ```
+fn read_label(bits: &Bits, max_len: usize) -> Result<(BitVec<u8, Msb0>, &Bits)> {
+    // `#<= m` takes as many bits as `m` itself
+    let width = (usize::BITS - max_len.leading_zeros()) as usize;
+
+    let tag = (bits.first().map(|b| *b), bits.get(1).map(|b| *b));
+    let (label, rest) = match tag {
+        (Some(false), _) => {
+            let len = bits[1..].leading_ones();
+            let (_, rest) = split(&bits[1..], len + 1)?;
+            let (label, rest) = split(rest, len)?;
+
+            (label.to_bitvec(), rest)
+        }
+        (Some(true), Some(false)) => {
+            let (len, rest) = read_len(&bits[2..], width)?;
+            let (label, rest) = split(rest, len)?;
+
+            (label.to_bitvec(), rest)
+        }
+        (Some(true), Some(true)) => {
+            let (value, rest) = split(&bits[2..], 1)?;
+            let (len, rest) = read_len(rest, width)?;
+
+            (BitVec::repeat(value[0], len), rest)
+        }
+        _ => bail!("hashmap label: unexpected end of cell"),
+    };
+
+    if label.len() > max_len {
+        bail!(
+            "hashmap label: {} bits exceed {} left",
+            label.len(),
+            max_len
+        );
+    }
+
+    Ok((label, rest))
+}
+
+fn read_len(bits: &Bits, width: usize) -> Result<(usize, &Bits)> {
+    let (len, rest) = split(bits, width)?;
+    let len = if width == 0 { 0 } else { len.load_be() };
+
+    Ok((len, rest))
+}
+
+fn split(bits: &Bits, at: usize) -> Result<(&Bits, &Bits)> {
+    if bits.len() < at {
+        bail!("hashmap label: unexpected end of cell");
+    }
+
+    Ok(bits.split_at(at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toner::tlb::bits::bitvec::bitvec;
+    use toner::tlb::bits::bitvec::view::BitView;
+
+    fn cell(data: BitVec<u8, Msb0>, references: Vec<Arc<Cell>>) -> Arc<Cell> {
+        Arc::new(Cell {
+            is_exotic: false,
+            data,
+            references,
+        })
+    }
+
+    // Hashmap 8 (uint8) with keys 0x01 -> 10 and 0x81 -> 20
+    fn dict() -> Arc<Cell> {
+        // hml_short with 7 bits: 0 1111111 0 + label bits
+        let leaf = |suffix: &[u8], value: u8| {
+            let mut data = bitvec![u8, Msb0; 0];
+            data.extend(std::iter::repeat_n(true, suffix.len()));
+            data.push(false);
+            data.extend(suffix.iter().map(|b| *b == 1));
+            data.extend(value.view_bits::<Msb0>());
+            cell(data, vec![])
+        };
+
+        cell(
+            // hml_short$0 with the empty label
+            bitvec![u8, Msb0; 0, 0],
+            vec![
+                leaf(&[0, 0, 0, 0, 0, 0, 1], 10),
+                leaf(&[0, 0, 0, 0, 0, 0, 1], 20),
+            ],
+        )
+    }
+
+    #[test]
+    fn get_existing_key() {
+        let dict = dict();
+
+        let leaf = HashmapEdge::new(&dict)
+            .get(0x81u8.view_bits::<Msb0>())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(leaf.bits.load_be::<u8>(), 20);
+    }
+
+    #[test]
+    fn get_missing_key() {
+        let dict = dict();
+
+        let leaf = HashmapEdge::new(&dict)
+            .get(0x02u8.view_bits::<Msb0>())
+            .unwrap();
+
+        assert!(leaf.is_none());
+    }
+
+    #[test]
+    fn nearest_key_in_both_directions() {
+        let dict = dict();
+        let nearest = |key: u8, reverse: bool, inclusive: bool| {
+            HashmapEdge::new(&dict)
+                .nearest(key.view_bits::<Msb0>(), reverse, inclusive)
+                .unwrap()
+                .map(|(key, leaf)| (key.load_be::<u8>(), leaf.bits.load_be::<u8>()))
+        };
+
+        assert_eq!(nearest(0x00, false, false), Some((0x01, 10)));
+        assert_eq!(nearest(0x01, false, false), Some((0x81, 20)));
+        assert_eq!(nearest(0x01, false, true), Some((0x01, 10)));
+        assert_eq!(nearest(0x81, false, false), None);
+        assert_eq!(nearest(0xff, true, false), Some((0x81, 20)));
+        assert_eq!(nearest(0x81, true, false), Some((0x01, 10)));
+        assert_eq!(nearest(0x01, true, false), None);
+    }
+}
@@ -0,0 +1,139 @@
+use toner::tlb::Error;
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+
+/// ```tlb
+/// gas_prices#dd gas_price:uint64 gas_limit:uint64 gas_credit:uint64
+///   block_gas_limit:uint64 freeze_due_limit:uint64 delete_due_limit:uint64
+///   = GasLimitsPrices;
+///
+/// gas_prices_ext#de gas_price:uint64 gas_limit:uint64 special_gas_limit:uint64 gas_credit:uint64
+///   block_gas_limit:uint64 freeze_due_limit:uint64 delete_due_limit:uint64
+///   = GasLimitsPrices;
+///
+/// gas_flat_pfx#d1 flat_gas_limit:uint64 flat_gas_price:uint64 other:GasLimitsPrices
+///   = GasLimitsPrices;
+/// ```
+///
+/// All three constructors are flattened the same way the node does it: `special_gas_limit`
+/// falls back to `gas_limit` and the flat prefix is zero when absent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GasLimitsPrices {
+    pub flat_gas_limit: u64,
+    pub flat_gas_price: u64,
+    pub gas_price: u64,
+    pub gas_limit: u64,
+    pub special_gas_limit: u64,
+    pub gas_credit: u64,
+    pub block_gas_limit: u64,
+    pub freeze_due_limit: u64,
+    pub delete_due_limit: u64,
+}
+
+impl<'de> BitUnpack<'de> for GasLimitsPrices {
+    type Args = ();
+
+    fn unpack<R>(reader: &mut R, _: Self::Args) -> Result<Self, R::Error>
+    where
+        R: BitReader<'de> + ?Sized,
+    {
+        let mut result = Self::default();
+
+        let mut tag: u8 = reader.unpack(())?;
+        if tag == 0xd1 {
+            result.flat_gas_limit = reader.unpack(())?;
+            result.flat_gas_price = reader.unpack(())?;
+            tag = reader.unpack(())?;
+        }
+
+        result.gas_price = reader.unpack(())?;
+        result.gas_limit = reader.unpack(())?;
+        result.special_gas_limit = match tag {
+            0xdd => result.gas_limit,
+            0xde => reader.unpack(())?,
+            tag => {
+                return Err(Error::custom(format!(
+                    "unsupported GasLimitsPrices tag: {tag:#x}"
+                )));
+            }
+        };
+        result.gas_credit = reader.unpack(())?;
+        result.block_gas_limit = reader.unpack(())?;
+        result.freeze_due_limit = reader.unpack(())?;
+        result.delete_due_limit = reader.unpack(())?;
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toner::tlb::bits::bitvec::order::Msb0;
+    use toner::tlb::bits::bitvec::vec::BitVec;
+    use toner::tlb::bits::bitvec::view::BitView;
+    use toner::tlb::bits::de::unpack_fully;
+
+    fn bits(tags_and_values: &[(u8, &[u64])]) -> BitVec<u8, Msb0> {
+        let mut bits = BitVec::new();
+        for (tag, values) in tags_and_values {
+            bits.extend(tag.view_bits::<Msb0>());
+            for value in *values {
+                bits.extend(value.to_be_bytes().view_bits::<Msb0>());
+            }
+        }
+        bits
+    }
+
+    #[test]
+    fn unpack_gas_prices() {
+        let bits = bits(&[(0xdd, &[1, 2, 3, 4, 5, 6])]);
+
+        let actual: GasLimitsPrices = unpack_fully(&bits, ()).unwrap();
+
+        assert_eq!(
+            actual,
+            GasLimitsPrices {
+                flat_gas_limit: 0,
+                flat_gas_price: 0,
+                gas_price: 1,
+                gas_limit: 2,
+                special_gas_limit: 2,
+                gas_credit: 3,
+                block_gas_limit: 4,
+                freeze_due_limit: 5,
+                delete_due_limit: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn unpack_flat_gas_prices_ext() {
+        let bits = bits(&[(0xd1, &[100, 40000]), (0xde, &[1, 2, 3, 4, 5, 6, 7])]);
+
+        let actual: GasLimitsPrices = unpack_fully(&bits, ()).unwrap();
+
+        assert_eq!(
+            actual,
+            GasLimitsPrices {
+                flat_gas_limit: 100,
+                flat_gas_price: 40000,
+                gas_price: 1,
+                gas_limit: 2,
+                special_gas_limit: 3,
+                gas_credit: 4,
+                block_gas_limit: 5,
+                freeze_due_limit: 6,
+                delete_due_limit: 7,
+            }
+        );
+    }
+
+    #[test]
+    fn unpack_unknown_tag() {
+        let bits = bits(&[(0xdf, &[1, 2, 3, 4, 5, 6])]);
+
+        let actual: Result<GasLimitsPrices, _> = unpack_fully(&bits, ());
+
+        assert!(actual.is_err());
+    }
+}
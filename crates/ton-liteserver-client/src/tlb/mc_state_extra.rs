@@ -0,0 +1,49 @@
+use num_bigint::BigUint;
+use toner::tlb::Cell;
+use toner::tlb::Ref;
+use toner::ton::currency::Grams;
+use toner_tlb_macros::CellDeserialize;
+
+/// ```tlb
+/// masterchain_state_extra#cc26
+///   shard_hashes:ShardHashes
+///   config:ConfigParams
+///   ^[ flags:(## 16) { flags <= 1 }
+///      validator_info:ValidatorInfo
+///      prev_blocks:OldMcBlocksInfo
+///      after_key_block:Bool
+///      last_key_block:(Maybe ExtBlkRef)
+///      block_create_stats:(flags . 0)?BlockCreateStats ]
+///   global_balance:CurrencyCollection
+/// = McStateExtra;
+/// ```
+///
+/// Only the config is typed: lite-server config proofs prune every other branch.
+#[derive(Debug, Clone, CellDeserialize)]
+#[tlb(tag = "0xcc26")]
+pub struct McStateExtra {
+    // TODO[akostylev0]: typed ShardHashes once MaybePruned is available
+    #[tlb(cell, as = "Option<Ref>")]
+    pub shard_hashes: Option<Cell>,
+    pub config: ConfigParams,
+    // TODO[akostylev0]: typed struct for the inline tuple
+    //   (flags, validator_info, prev_blocks, after_key_block, last_key_block, block_create_stats)
+    #[tlb(cell, as = "Ref")]
+    pub info: Cell,
+    #[tlb(bits, as = "Grams")]
+    pub global_balance_grams: BigUint,
+    // TODO[akostylev0]: ExtraCurrencyCollection once MaybePruned is available
+    #[tlb(cell, as = "Option<Ref>")]
+    pub global_balance_other: Option<Cell>,
+}
+
+/// ```tlb
+/// _ config_addr:bits256 config:^(Hashmap 32 ^Cell) = ConfigParams;
+/// ```
+#[derive(Debug, Clone, CellDeserialize)]
+pub struct ConfigParams {
+    #[tlb(bits)]
+    pub config_addr: [u8; 32],
+    #[tlb(cell, as = "Ref")]
+    pub config: Cell,
+}
@@ -1,8 +1,9 @@
+use anyhow::{anyhow, ensure};
 use toner::tlb::bits::{NBits, de::BitReaderExt};
 use toner::tlb::de::{
     CellDeserialize, CellDeserializeAs, CellDeserializeOwned, CellParser, CellParserError,
 };
-use toner::tlb::{Cell, Error, Ref};
+use toner::tlb::{BoC, Cell, Error, Ref};
 
 /// ```tlb
 /// !merkle_proof#03 {X:Type} virtual_hash:bits256 depth:uint16 virtual_root:^X = MERKLE_PROOF X;
@@ -78,10 +79,33 @@ where
     }
 }
 
+/// Returns the virtual root of the merkle proof `root` checked against `expected_hash`.
+pub fn verified_root(root: &Cell, expected_hash: &[u8; 32]) -> anyhow::Result<Cell> {
+    let proof: MerkleProof<Cell> = root.parse_fully(())?;
+    ensure!(
+        &proof.virtual_hash == expected_hash,
+        "proof root hash mismatch: expected {}, got {}",
+        hex::encode(expected_hash),
+        hex::encode(proof.virtual_hash)
+    );
+
+    Ok(proof.virtual_root)
+}
+
+/// Same as [`verified_root`] for a proof serialized as a single root BoC.
+pub fn verified_root_of_boc(bytes: &[u8], expected_hash: &[u8; 32]) -> anyhow::Result<Cell> {
+    let boc = BoC::deserialize(bytes)?;
+    let root = boc
+        .single_root()
+        .ok_or_else(|| anyhow!("proof: single root expected"))?;
+
+    verified_root(root, expected_hash)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tlb::block_header::BlockHeader;
-    use crate::tlb::merkle_proof::MerkleProof;
+    use crate::tlb::merkle_proof::{MerkleProof, verified_root};
     use crate::tlb::tests::BLOCK_HEADER_MERKLE_PROOF_HEX;
     use std::sync::Arc;
     use toner::tlb::bits::de::unpack_bytes;
@@ -109,6 +133,27 @@ mod tests {
         assert_eq!(header.global_id, -239);
     }
 
+    #[test]
+    fn test_verified_root_ok() {
+        let root = given_block_header_root_cell();
+        let expected_hash =
+            hex::decode("9b3184087274bb28db6a90ce88e0d3918bdebf723f89fc121a1e77d02e34cf5f")
+                .unwrap();
+
+        let virtual_root = verified_root(&root, expected_hash.as_slice().try_into().unwrap());
+
+        assert!(virtual_root.is_ok());
+    }
+
+    #[test]
+    fn test_verified_root_rejects_other_hash() {
+        let root = given_block_header_root_cell();
+
+        let virtual_root = verified_root(&root, &[0; 32]);
+
+        assert!(virtual_root.is_err());
+    }
+
     fn given_block_header_root_cell() -> Arc<Cell> {
         let data = hex::decode(BLOCK_HEADER_MERKLE_PROOF_HEX).unwrap();
 
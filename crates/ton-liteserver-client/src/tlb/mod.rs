@@ -1,8 +1,10 @@
-pub mod account;
+pub use ton_tlb::{
+    account, account_state, account_status, account_storage, currency_collection,
+    extra_currency_collection, hash_update, msg_address_int, storage_extra_info, storage_info,
+    storage_used, transaction, transaction_descr, vm_cont, vm_stack,
+};
+
 pub mod account_block;
-pub mod account_state;
-pub mod account_status;
-pub mod account_storage;
 pub mod blk_master_info;
 pub mod blk_prev_info;
 pub mod block;
@@ -10,18 +12,19 @@ mod block_extra;
 pub mod block_header;
 pub mod block_id_ext;
 pub mod block_info;
-mod currency_collection;
+pub mod config_param;
+pub mod dict;
 pub mod ext_blk_ref;
-mod extra_currency_collection;
 pub mod future_split_merge;
+pub mod gas_limits_prices;
 pub mod global_version;
-mod hash_update;
 mod in_msg;
 mod in_msg_descr;
+pub mod mc_state_extra;
 pub mod merkle_proof;
 pub mod merkle_update;
-pub mod msg_address_int;
 mod msg_envelope;
+pub mod msg_forward_prices;
 mod out_msg;
 mod out_msg_descr;
 pub mod shard_account_blocks;
@@ -29,12 +32,7 @@ pub mod shard_descr;
 pub mod shard_hashes;
 pub mod shard_ident;
 pub mod shard_state;
-mod storage_extra_info;
-pub mod storage_info;
-pub mod storage_used;
+pub mod storage_prices;
 #[cfg(test)]
 pub(crate) mod tests;
-pub mod transaction;
-pub mod transaction_descr;
-pub mod vm_cont;
-pub mod vm_stack;
+pub mod validator_set;
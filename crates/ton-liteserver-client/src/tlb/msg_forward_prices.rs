@@ -0,0 +1,16 @@
+use toner_tlb_macros::BitUnpack;
+
+/// ```tlb
+/// msg_forward_prices#ea lump_price:uint64 bit_price:uint64 cell_price:uint64
+///   ihr_price_factor:uint32 first_frac:uint16 next_frac:uint16 = MsgForwardPrices;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BitUnpack)]
+#[tlb(tag = "0xea")]
+pub struct MsgForwardPrices {
+    pub lump_price: u64,
+    pub bit_price: u64,
+    pub cell_price: u64,
+    pub ihr_price_factor: u32,
+    pub first_frac: u16,
+    pub next_frac: u16,
+}
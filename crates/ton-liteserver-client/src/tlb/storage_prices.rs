@@ -0,0 +1,48 @@
+use toner_tlb_macros::BitUnpack;
+
+/// ```tlb
+/// storage_prices#cc utime_since:uint32 bit_price_ps:uint64 cell_price_ps:uint64
+///   mc_bit_price_ps:uint64 mc_cell_price_ps:uint64 = StoragePrices;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BitUnpack)]
+#[tlb(tag = "0xcc")]
+pub struct StoragePrices {
+    pub utime_since: u32,
+    pub bit_price_ps: u64,
+    pub cell_price_ps: u64,
+    pub mc_bit_price_ps: u64,
+    pub mc_cell_price_ps: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toner::tlb::bits::bitvec::order::Msb0;
+    use toner::tlb::bits::bitvec::vec::BitVec;
+    use toner::tlb::bits::bitvec::view::BitView;
+    use toner::tlb::bits::de::unpack_fully;
+
+    #[test]
+    fn unpack_storage_prices() {
+        let mut bits = BitVec::<u8, Msb0>::new();
+        bits.extend(0xccu8.view_bits::<Msb0>());
+        bits.extend(1u32.to_be_bytes().view_bits::<Msb0>());
+        bits.extend(2u64.to_be_bytes().view_bits::<Msb0>());
+        bits.extend(3u64.to_be_bytes().view_bits::<Msb0>());
+        bits.extend(4u64.to_be_bytes().view_bits::<Msb0>());
+        bits.extend(5u64.to_be_bytes().view_bits::<Msb0>());
+
+        let actual: StoragePrices = unpack_fully(&bits, ()).unwrap();
+
+        assert_eq!(
+            actual,
+            StoragePrices {
+                utime_since: 1,
+                bit_price_ps: 2,
+                cell_price_ps: 3,
+                mc_bit_price_ps: 4,
+                mc_cell_price_ps: 5,
+            }
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+use toner::tlb::bits::bitvec::field::BitField;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::vec::BitVec;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::hashmap::{Hashmap, HashmapE};
+use toner::tlb::{Cell, Data, Error};
+use toner_tlb_macros::BitUnpack;
+
+/// ```tlb
+/// ed25519_pubkey#8e81278a pubkey:bits256 = SigPubKey;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BitUnpack)]
+#[tlb(tag = "0x8e81278a")]
+pub struct SigPubKey {
+    pub pubkey: [u8; 32],
+}
+
+/// ```tlb
+/// validator#53 public_key:SigPubKey weight:uint64 = ValidatorDescr;
+/// validator_addr#73 public_key:SigPubKey weight:uint64 adnl_addr:bits256 = ValidatorDescr;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BitUnpack)]
+pub enum ValidatorDescr {
+    #[tlb(tag = "0x53")]
+    Validator { public_key: SigPubKey, weight: u64 },
+    #[tlb(tag = "0x73")]
+    ValidatorAddr {
+        public_key: SigPubKey,
+        weight: u64,
+        adnl_addr: [u8; 32],
+    },
+}
+
+/// ```tlb
+/// validators#11 utime_since:uint32 utime_until:uint32
+///   total:(## 16) main:(## 16) { main <= total } { main >= 1 }
+///   list:(Hashmap 16 ValidatorDescr) = ValidatorSet;
+/// validators_ext#12 utime_since:uint32 utime_until:uint32
+///   total:(## 16) main:(## 16) { main <= total } { main >= 1 }
+///   total_weight:uint64 list:(HashmapE 16 ValidatorDescr) = ValidatorSet;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorSet {
+    pub utime_since: u32,
+    pub utime_until: u32,
+    pub total: u16,
+    pub main: u16,
+    pub total_weight: Option<u64>,
+    pub list: BTreeMap<u16, ValidatorDescr>,
+}
+
+impl ValidatorSet {
+    pub fn from_cell(cell: &Cell) -> Result<Self> {
+        Ok(cell.parse_fully(())?)
+    }
+}
+
+impl<'de> CellDeserialize<'de> for ValidatorSet {
+    type Args = ();
+
+    fn parse(parser: &mut CellParser<'de>, _: Self::Args) -> Result<Self, CellParserError<'de>> {
+        let tag: u8 = parser.unpack(())?;
+        let utime_since = parser.unpack(())?;
+        let utime_until = parser.unpack(())?;
+        let total = parser.unpack(())?;
+        let main = parser.unpack(())?;
+
+        let (total_weight, list): (_, HashMap<BitVec<u8, Msb0>, ValidatorDescr>) = match tag {
+            0x11 => (None, parser.parse_as::<_, Hashmap<Data>>((16, ()))?),
+            0x12 => (
+                Some(parser.unpack(())?),
+                parser.parse_as::<_, HashmapE<Data>>((16, ()))?,
+            ),
+            tag => {
+                return Err(Error::custom(format!(
+                    "unsupported ValidatorSet tag: {tag:#x}"
+                )));
+            }
+        };
+
+        Ok(Self {
+            utime_since,
+            utime_until,
+            total,
+            main,
+            total_weight,
+            list: list
+                .into_iter()
+                .map(|(index, descr)| (index.load_be(), descr))
+                .collect(),
+        })
+    }
+}
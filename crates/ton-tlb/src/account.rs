@@ -1,6 +1,6 @@
-use crate::tlb::account_storage::AccountStorage;
-use crate::tlb::msg_address_int::MsgAddressInt;
-use crate::tlb::storage_info::StorageInfo;
+use crate::account_storage::AccountStorage;
+use crate::msg_address_int::MsgAddressInt;
+use crate::storage_info::StorageInfo;
 use toner_tlb_macros::CellDeserialize;
 
 /// ```tlb
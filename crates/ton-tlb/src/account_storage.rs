@@ -1,5 +1,5 @@
-use crate::tlb::account_state::AccountState;
-use crate::tlb::currency_collection::CurrencyCollection;
+use crate::account_state::AccountState;
+use crate::currency_collection::CurrencyCollection;
 use toner_tlb_macros::CellDeserialize;
 
 /// ```tlb
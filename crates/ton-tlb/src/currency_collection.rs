@@ -1,4 +1,4 @@
-use crate::tlb::extra_currency_collection::ExtraCurrencyCollection;
+use crate::extra_currency_collection::ExtraCurrencyCollection;
 use num_bigint::BigUint;
 use toner::ton::currency::Grams;
 use toner_tlb_macros::CellDeserialize;
@@ -48,8 +48,8 @@ impl<'de, X> BitUnpack<'de> for HashUpdate<X> {
 
 #[cfg(test)]
 mod tests {
-    use crate::tlb::block::Block;
-    use crate::tlb::hash_update::HashUpdate;
+    use crate::account::Account;
+    use crate::hash_update::HashUpdate;
     use toner::tlb::bits::bitvec::bitvec;
     use toner::tlb::bits::bitvec::order::Msb0;
     use toner::tlb::bits::bitvec::view::BitView;
@@ -62,7 +62,7 @@ mod tests {
         bits.extend([1u8; 32].view_bits::<Msb0>());
         bits.extend([2u8; 32].view_bits::<Msb0>());
 
-        let result: HashUpdate<Block> = unpack_fully(&bits, ()).unwrap();
+        let result: HashUpdate<Account> = unpack_fully(&bits, ()).unwrap();
 
         assert_eq!(
             result,
@@ -0,0 +1,18 @@
+pub mod account;
+pub mod account_state;
+pub mod account_status;
+pub mod account_storage;
+pub mod currency_collection;
+pub mod extra_currency_collection;
+pub mod hash_update;
+pub mod message_body;
+pub mod msg_address_int;
+pub mod shard_account;
+pub mod stack;
+pub mod storage_extra_info;
+pub mod storage_info;
+pub mod storage_used;
+pub mod transaction;
+pub mod transaction_descr;
+pub mod vm_cont;
+pub mod vm_stack;
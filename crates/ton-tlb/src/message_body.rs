@@ -0,0 +1,432 @@
+use anyhow::{Result, anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as base64_standard;
+use num_bigint::BigUint;
+use std::sync::Arc;
+use ton_address::SmartContractAddress;
+use ton_tower::response::DecodedBody;
+use toner::tlb::bits::bitvec::bits;
+use toner::tlb::bits::bitvec::field::BitField;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::slice::BitSlice;
+use toner::tlb::bits::de::{BitReaderExt, BitUnpack};
+use toner::tlb::{BagOfCellsArgs, BoC, Cell};
+use toner::ton::MsgAddress;
+use toner::ton::currency::Grams;
+
+/// Turns a message body into one of the typed [`DecodedBody`] variants.
+///
+/// Both backends run the same [`DEFAULT_DECODERS`] over raw bodies,
+/// so the output does not depend on which one served the request.
+pub trait MessageBodyDecoder: Send + Sync {
+    /// Returns `None` when the body is not recognized, so the next decoder may try.
+    fn decode(&self, body: &Cell) -> Option<DecodedBody>;
+}
+
+pub const DEFAULT_DECODERS: &[&dyn MessageBodyDecoder] =
+    &[&CommentDecoder, &JettonDecoder, &NftDecoder];
+
+/// Runs [`DEFAULT_DECODERS`] over the body, `None` means the body is not recognized.
+pub fn decode_body(body: &Cell) -> Option<DecodedBody> {
+    decode_body_with(DEFAULT_DECODERS, body)
+}
+
+pub fn decode_body_with(decoders: &[&dyn MessageBodyDecoder], body: &Cell) -> Option<DecodedBody> {
+    decoders.iter().find_map(|decoder| decoder.decode(body))
+}
+
+/// Same as [`decode_body`] for a base64 encoded BoC.
+pub fn decode_body_base64(body: &str) -> Option<DecodedBody> {
+    let boc = BoC::parse_base64(body).ok()?;
+
+    decode_body(boc.single_root()?)
+}
+
+/// Text comments the same way tonlib reports them: the text is a base64 of the comment bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct CommentDecoder;
+
+impl CommentDecoder {
+    const COMMENT: u32 = 0;
+    const ENCRYPTED_COMMENT: u32 = 0x2167da4b;
+}
+
+impl MessageBodyDecoder for CommentDecoder {
+    fn decode(&self, body: &Cell) -> Option<DecodedBody> {
+        let mut reader = BodyReader::new(body);
+        let op = reader.op().ok()?;
+        if op != Self::COMMENT && op != Self::ENCRYPTED_COMMENT {
+            return None;
+        }
+
+        let text = base64_standard.encode(reader.snake_bytes().ok()?);
+
+        Some(if op == Self::COMMENT {
+            DecodedBody::Comment { text }
+        } else {
+            DecodedBody::EncryptedComment { text }
+        })
+    }
+}
+
+/// TEP-74 jetton wallet messages.
+#[derive(Debug, Clone, Copy)]
+pub struct JettonDecoder;
+
+impl JettonDecoder {
+    const TRANSFER: u32 = 0x0f8a7ea5;
+    const INTERNAL_TRANSFER: u32 = 0x178d4519;
+    const TRANSFER_NOTIFICATION: u32 = 0x7362d09c;
+    const BURN: u32 = 0x595f07bc;
+
+    fn try_decode(body: &Cell) -> Result<Option<DecodedBody>> {
+        let mut reader = BodyReader::new(body);
+
+        let data = match reader.op()? {
+            // transfer#0f8a7ea5 query_id:uint64 amount:(VarUInteger 16) destination:MsgAddress
+            //   response_destination:MsgAddress custom_payload:(Maybe ^Cell)
+            //   forward_ton_amount:(VarUInteger 16) forward_payload:(Either Cell ^Cell)
+            Self::TRANSFER => DecodedBody::JettonTransfer {
+                query_id: reader.unpack()?,
+                amount: reader.amount()?,
+                destination: reader.address()?,
+                response_destination: reader.address()?,
+                custom_payload: reader.maybe_ref()?,
+                forward_ton_amount: reader.amount()?,
+                forward_payload: reader.either_cell()?,
+            },
+            // internal_transfer#178d4519 query_id:uint64 amount:(VarUInteger 16) from:MsgAddress
+            //   response_address:MsgAddress forward_ton_amount:(VarUInteger 16)
+            //   forward_payload:(Either Cell ^Cell)
+            Self::INTERNAL_TRANSFER => DecodedBody::JettonInternalTransfer {
+                query_id: reader.unpack()?,
+                amount: reader.amount()?,
+                from: reader.address()?,
+                response_address: reader.address()?,
+                forward_ton_amount: reader.amount()?,
+                forward_payload: reader.either_cell()?,
+            },
+            // transfer_notification#7362d09c query_id:uint64 amount:(VarUInteger 16)
+            //   sender:MsgAddress forward_payload:(Either Cell ^Cell)
+            Self::TRANSFER_NOTIFICATION => DecodedBody::JettonTransferNotification {
+                query_id: reader.unpack()?,
+                amount: reader.amount()?,
+                sender: reader.address()?,
+                forward_payload: reader.either_cell()?,
+            },
+            // burn#595f07bc query_id:uint64 amount:(VarUInteger 16)
+            //   response_destination:MsgAddress custom_payload:(Maybe ^Cell)
+            Self::BURN => DecodedBody::JettonBurn {
+                query_id: reader.unpack()?,
+                amount: reader.amount()?,
+                response_destination: reader.address()?,
+                custom_payload: reader.maybe_ref()?,
+            },
+            _ => return Ok(None),
+        };
+
+        Ok(Some(data))
+    }
+}
+
+impl MessageBodyDecoder for JettonDecoder {
+    fn decode(&self, body: &Cell) -> Option<DecodedBody> {
+        Self::try_decode(body).ok().flatten()
+    }
+}
+
+/// TEP-62 NFT item messages.
+#[derive(Debug, Clone, Copy)]
+pub struct NftDecoder;
+
+impl NftDecoder {
+    const TRANSFER: u32 = 0x5fcc3d14;
+    const OWNERSHIP_ASSIGNED: u32 = 0x05138d91;
+
+    fn try_decode(body: &Cell) -> Result<Option<DecodedBody>> {
+        let mut reader = BodyReader::new(body);
+
+        let data = match reader.op()? {
+            // transfer#5fcc3d14 query_id:uint64 new_owner:MsgAddress response_destination:MsgAddress
+            //   custom_payload:(Maybe ^Cell) forward_amount:(VarUInteger 16)
+            //   forward_payload:(Either Cell ^Cell)
+            Self::TRANSFER => DecodedBody::NftTransfer {
+                query_id: reader.unpack()?,
+                new_owner: reader.address()?,
+                response_destination: reader.address()?,
+                custom_payload: reader.maybe_ref()?,
+                forward_amount: reader.amount()?,
+                forward_payload: reader.either_cell()?,
+            },
+            // ownership_assigned#05138d91 query_id:uint64 prev_owner:MsgAddress
+            //   forward_payload:(Either Cell ^Cell)
+            Self::OWNERSHIP_ASSIGNED => DecodedBody::NftOwnershipAssigned {
+                query_id: reader.unpack()?,
+                prev_owner: reader.address()?,
+                forward_payload: reader.either_cell()?,
+            },
+            _ => return Ok(None),
+        };
+
+        Ok(Some(data))
+    }
+}
+
+impl MessageBodyDecoder for NftDecoder {
+    fn decode(&self, body: &Cell) -> Option<DecodedBody> {
+        Self::try_decode(body).ok().flatten()
+    }
+}
+
+struct BodyReader<'a> {
+    bits: &'a BitSlice<u8, Msb0>,
+    refs: &'a [Arc<Cell>],
+}
+
+impl<'a> BodyReader<'a> {
+    fn new(cell: &'a Cell) -> Self {
+        Self {
+            bits: cell.data.as_bitslice(),
+            refs: &cell.references,
+        }
+    }
+
+    fn unpack<T>(&mut self) -> Result<T>
+    where
+        T: BitUnpack<'a, Args = ()>,
+    {
+        Ok(self.bits.unpack(())?)
+    }
+
+    fn op(&mut self) -> Result<u32> {
+        self.unpack()
+    }
+
+    fn amount(&mut self) -> Result<String> {
+        let amount: BigUint = self.bits.unpack_as::<_, Grams>(())?;
+
+        Ok(amount.to_string())
+    }
+
+    /// `MsgAddress`, only `addr_none$00` is reported as `None`.
+    fn address(&mut self) -> Result<Option<SmartContractAddress>> {
+        if self.bits.starts_with(bits![u8, Msb0; 0, 0]) {
+            self.bits = &self.bits[2..];
+
+            return Ok(None);
+        }
+        let address: MsgAddress = self.unpack()?;
+
+        Ok(Some(SmartContractAddress::raw(
+            address.workchain_id,
+            address.address,
+        )))
+    }
+
+    fn next_ref(&mut self) -> Result<&'a Arc<Cell>> {
+        let (first, rest) = self
+            .refs
+            .split_first()
+            .ok_or_else(|| anyhow!("message body: reference is missing"))?;
+        self.refs = rest;
+
+        Ok(first)
+    }
+
+    /// `Maybe ^Cell`
+    fn maybe_ref(&mut self) -> Result<Option<String>> {
+        let present: bool = self.unpack()?;
+        if !present {
+            return Ok(None);
+        }
+
+        serialize(self.next_ref()?.clone()).map(Some)
+    }
+
+    /// `Either Cell ^Cell`, an empty inline cell is reported as `None`.
+    fn either_cell(&mut self) -> Result<Option<String>> {
+        let is_ref: bool = self.unpack()?;
+        if is_ref {
+            return serialize(self.next_ref()?.clone()).map(Some);
+        }
+
+        if self.bits.is_empty() && self.refs.is_empty() {
+            return Ok(None);
+        }
+        let inline = Cell {
+            is_exotic: false,
+            data: self.bits.to_bitvec(),
+            references: self.refs.to_vec(),
+        };
+        self.bits = &self.bits[self.bits.len()..];
+        self.refs = &[];
+
+        serialize(Arc::new(inline)).map(Some)
+    }
+
+    /// Bytes of a snake string: the rest of this cell followed by the chain of first references.
+    fn snake_bytes(&mut self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        let mut bits = self.bits;
+        let mut refs = self.refs;
+
+        loop {
+            if bits.len() % 8 != 0 {
+                bail!("message body: snake string is not byte aligned");
+            }
+            bytes.extend(bits.chunks(8).map(|byte| byte.load_be::<u8>()));
+
+            let Some(next) = refs.first() else {
+                break;
+            };
+            bits = next.data.as_bitslice();
+            refs = &next.references;
+        }
+
+        Ok(bytes)
+    }
+}
+
+fn serialize(cell: Arc<Cell>) -> Result<String> {
+    let bytes = BoC::from_root(cell)
+        .serialize(BagOfCellsArgs {
+            has_crc32c: true,
+            ..BagOfCellsArgs::default()
+        })
+        .map_err(|e| anyhow!("BoC serialize failed: {e}"))?;
+
+    Ok(base64_standard.encode(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toner::tlb::bits::bitvec::vec::BitVec;
+    use toner::tlb::bits::bitvec::view::BitView;
+
+    fn cell(data: BitVec<u8, Msb0>, references: Vec<Arc<Cell>>) -> Cell {
+        Cell {
+            is_exotic: false,
+            data,
+            references,
+        }
+    }
+
+    fn push<T: BitView<Store = u8>>(data: &mut BitVec<u8, Msb0>, value: T) {
+        data.extend_from_bitslice(value.view_bits::<Msb0>());
+    }
+
+    // addr_std$10 anycast:nothing$0 workchain_id:int8 address:bits256
+    fn push_address(data: &mut BitVec<u8, Msb0>, workchain_id: i8, address: [u8; 32]) {
+        data.extend([true, false, false]);
+        push(data, workchain_id.to_be_bytes());
+        push(data, address);
+    }
+
+    // VarUInteger 16 with a single byte value
+    fn push_amount(data: &mut BitVec<u8, Msb0>, amount: u8) {
+        data.extend([false, false, false, true]);
+        push(data, [amount]);
+    }
+
+    #[test]
+    fn decode_text_comment() {
+        let mut data = BitVec::new();
+        push(&mut data, 0u32.to_be_bytes());
+        push(&mut data, *b"hello");
+
+        let decoded = decode_body(&cell(data, vec![]));
+
+        assert_eq!(
+            decoded,
+            Some(DecodedBody::Comment {
+                text: base64_standard.encode(b"hello")
+            })
+        );
+    }
+
+    #[test]
+    fn decode_snake_comment() {
+        let mut tail = BitVec::new();
+        push(&mut tail, *b" world");
+        let mut data = BitVec::new();
+        push(&mut data, 0u32.to_be_bytes());
+        push(&mut data, *b"hello");
+
+        let decoded = decode_body(&cell(data, vec![Arc::new(cell(tail, vec![]))]));
+
+        assert_eq!(
+            decoded,
+            Some(DecodedBody::Comment {
+                text: base64_standard.encode(b"hello world")
+            })
+        );
+    }
+
+    #[test]
+    fn decode_jetton_transfer() {
+        let mut data = BitVec::new();
+        push(&mut data, 0x0f8a7ea5u32.to_be_bytes());
+        push(&mut data, 7u64.to_be_bytes());
+        push_amount(&mut data, 100);
+        push_address(&mut data, 0, [1; 32]);
+        // addr_none$00
+        data.extend([false, false]);
+        // custom_payload: nothing$0
+        data.push(false);
+        push_amount(&mut data, 1);
+        // forward_payload: left$0 with an empty inline cell
+        data.push(false);
+
+        let decoded = decode_body(&cell(data, vec![]));
+
+        assert_eq!(
+            decoded,
+            Some(DecodedBody::JettonTransfer {
+                query_id: 7,
+                amount: "100".to_owned(),
+                destination: Some(SmartContractAddress::raw(0, [1; 32])),
+                response_destination: None,
+                custom_payload: None,
+                forward_ton_amount: "1".to_owned(),
+                forward_payload: None,
+            })
+        );
+    }
+
+    #[test]
+    fn decode_zero_address_as_address() {
+        let mut data = BitVec::new();
+        push(&mut data, 0x05138d91u32.to_be_bytes());
+        push(&mut data, 1u64.to_be_bytes());
+        push_address(&mut data, 0, [0; 32]);
+        data.push(false);
+
+        let decoded = decode_body(&cell(data, vec![]));
+
+        assert_eq!(
+            decoded,
+            Some(DecodedBody::NftOwnershipAssigned {
+                query_id: 1,
+                prev_owner: Some(SmartContractAddress::raw(0, [0; 32])),
+                forward_payload: None,
+            })
+        );
+    }
+
+    #[test]
+    fn keep_unknown_op_raw() {
+        let mut data = BitVec::new();
+        push(&mut data, 0xdeadbeefu32.to_be_bytes());
+
+        assert_eq!(decode_body(&cell(data, vec![])), None);
+    }
+
+    #[test]
+    fn keep_truncated_body_raw() {
+        let mut data = BitVec::new();
+        push(&mut data, 0x0f8a7ea5u32.to_be_bytes());
+
+        assert_eq!(decode_body(&cell(data, vec![])), None);
+    }
+}
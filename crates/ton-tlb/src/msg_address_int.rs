@@ -62,7 +62,7 @@ impl<'de> BitUnpack<'de> for MsgAddressInt {
 
 #[cfg(test)]
 mod tests {
-    use crate::tlb::msg_address_int::MsgAddressInt;
+    use crate::msg_address_int::MsgAddressInt;
     use toner::tlb::bits::bitvec::bitvec;
     use toner::tlb::bits::bitvec::order::Msb0;
     use toner::tlb::bits::bitvec::view::BitView;
@@ -0,0 +1,16 @@
+use crate::account::Account;
+use toner_tlb_macros::CellDeserialize;
+
+/// ```tlb
+/// account_descr$_ account:^Account last_trans_hash:bits256
+///   last_trans_lt:uint64 = ShardAccount;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, CellDeserialize)]
+pub struct ShardAccount {
+    #[tlb(cell, as = "toner::tlb::Ref")]
+    pub account: Account,
+    #[tlb(bits)]
+    pub last_trans_hash: [u8; 32],
+    #[tlb(bits)]
+    pub last_trans_lt: u64,
+}
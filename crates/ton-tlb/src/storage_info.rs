@@ -1,5 +1,5 @@
-use crate::tlb::storage_extra_info::StorageExtraInfo;
-use crate::tlb::storage_used::StorageUsed;
+use crate::storage_extra_info::StorageExtraInfo;
+use crate::storage_used::StorageUsed;
 use num_bigint::BigUint;
 use toner::ton::currency::Grams;
 use toner_tlb_macros::BitUnpack;
@@ -1,8 +1,8 @@
-use crate::tlb::account::Account;
-use crate::tlb::account_status::AccountStatus;
-use crate::tlb::currency_collection::CurrencyCollection;
-use crate::tlb::hash_update::HashUpdate;
-use crate::tlb::transaction_descr::TransactionDescr;
+use crate::account::Account;
+use crate::account_status::AccountStatus;
+use crate::currency_collection::CurrencyCollection;
+use crate::hash_update::HashUpdate;
+use crate::transaction_descr::TransactionDescr;
 use std::collections::HashMap;
 use std::sync::Arc;
 use toner::tlb::bits::NBits;
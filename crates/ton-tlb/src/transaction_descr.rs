@@ -1,6 +1,6 @@
-use crate::tlb::currency_collection::CurrencyCollection;
-use crate::tlb::storage_used::StorageUsed;
-use crate::tlb::transaction::Transaction;
+use crate::currency_collection::CurrencyCollection;
+use crate::storage_used::StorageUsed;
+use crate::transaction::Transaction;
 
 use num_bigint::BigUint;
 use toner::tlb::bits::{NBits, VarInt};
@@ -150,10 +150,10 @@ pub enum TransactionDescr {
 #[derive(Debug, Clone, PartialEq, Eq, BitUnpack)]
 pub struct TrStoragePhase {
     #[tlb(bits, as = "Grams")]
-    storage_fees_collected: BigUint,
+    pub storage_fees_collected: BigUint,
     #[tlb(bits, as = "Option<Grams>")]
-    storage_fees_due: Option<BigUint>,
-    status_change: AccStatusChange,
+    pub storage_fees_due: Option<BigUint>,
+    pub status_change: AccStatusChange,
 }
 
 /// ```tlb
@@ -163,8 +163,8 @@ pub struct TrStoragePhase {
 #[derive(Debug, Clone, PartialEq, Eq, Default, CellDeserialize)]
 pub struct TrCreditPhase {
     #[tlb(bits, as = "Option<Grams>")]
-    due_fees_collected: Option<BigUint>,
-    credit: CurrencyCollection,
+    pub due_fees_collected: Option<BigUint>,
+    pub credit: CurrencyCollection,
 }
 
 /// ```tlb
@@ -261,22 +261,22 @@ pub enum TrComputePhase {
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, BitUnpack)]
 pub struct TrActionPhase {
-    success: bool,
-    valid: bool,
-    no_funds: bool,
-    status_change: AccStatusChange,
+    pub success: bool,
+    pub valid: bool,
+    pub no_funds: bool,
+    pub status_change: AccStatusChange,
     #[tlb(bits, as = "Option<Grams>")]
-    total_fwd_fees: Option<BigUint>,
+    pub total_fwd_fees: Option<BigUint>,
     #[tlb(bits, as = "Option<Grams>")]
-    total_action_fees: Option<BigUint>,
-    result_code: i32,
-    result_arg: Option<i32>,
-    tot_actions: u16,
-    spec_actions: u16,
-    skipped_actions: u16,
-    msgs_created: u16,
-    action_list_hash: [u8; 32],
-    tot_msg_size: StorageUsed,
+    pub total_action_fees: Option<BigUint>,
+    pub result_code: i32,
+    pub result_arg: Option<i32>,
+    pub tot_actions: u16,
+    pub spec_actions: u16,
+    pub skipped_actions: u16,
+    pub msgs_created: u16,
+    pub action_list_hash: [u8; 32],
+    pub tot_msg_size: StorageUsed,
 }
 
 /// ```tlb
@@ -1,4 +1,4 @@
-use crate::tlb::vm_stack::{VmCellSlice, VmStack, VmStackValue};
+use crate::vm_stack::{VmCellSlice, VmStack, VmStackValue};
 use toner::tlb::Same;
 use toner::tlb::bits::NBits;
 use toner::tlb::hashmap::HashmapE;
@@ -110,7 +110,7 @@ pub enum VmCont {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tlb::vm_stack::VmStackValue;
+    use crate::vm_stack::VmStackValue;
     use toner::tlb::Cell;
     use toner::tlb::bits::ser::BitWriterExt;
     use toner::tlb::ser::CellSerializeExt;
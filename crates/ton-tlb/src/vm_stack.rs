@@ -1,4 +1,4 @@
-use crate::tlb::vm_cont::VmCont;
+use crate::vm_cont::VmCont;
 use num_bigint::BigUint;
 use toner::tlb::Cell;
 use toner::tlb::bits::NBits;
@@ -2,14 +2,14 @@ use crate::Request;
 use crate::response::*;
 use ton_address::SmartContractAddress;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct GetMasterchainInfo {}
 
 impl Request for GetMasterchainInfo {
     type Response = MasterchainInfo;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LookUpBlockBySeqno {
     pub chain: i32,
     pub shard: i64,
@@ -20,7 +20,7 @@ impl Request for LookUpBlockBySeqno {
     type Response = BlockIdExt;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LookUpBlockByLt {
     pub chain: i32,
     pub shard: i64,
@@ -31,7 +31,7 @@ impl Request for LookUpBlockByLt {
     type Response = BlockIdExt;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetShards {
     pub block_id: BlockIdExt,
 }
@@ -40,7 +40,7 @@ impl Request for GetShards {
     type Response = Vec<BlockIdExt>;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetBlockHeader {
     pub id: BlockIdExt,
 }
@@ -49,7 +49,7 @@ impl Request for GetBlockHeader {
     type Response = BlockHeader;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetTransactionIds {
     pub block: BlockIdExt,
     pub after: Option<ShortTxId>,
@@ -61,7 +61,7 @@ impl Request for GetTransactionIds {
     type Response = BlockTransactions;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetTransactions {
     pub block: BlockIdExt,
     pub after: Option<ShortTxId>,
@@ -73,14 +73,14 @@ impl Request for GetTransactions {
     type Response = BlockTransactionsExt;
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Sync {}
 
 impl Request for Sync {
     type Response = BlockIdExt;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SendMessage {
     pub body: String,
 }
@@ -89,7 +89,7 @@ impl Request for SendMessage {
     type Response = ();
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SendMessageReturningHash {
     pub body: String,
 }
@@ -98,7 +98,7 @@ impl Request for SendMessageReturningHash {
     type Response = String;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetAccountState {
     pub address: SmartContractAddress,
 }
@@ -107,7 +107,7 @@ impl Request for GetAccountState {
     type Response = AccountState;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetAccountStateOnBlock {
     pub address: SmartContractAddress,
     pub block_id: BlockIdExt,
@@ -117,7 +117,7 @@ impl Request for GetAccountStateOnBlock {
     type Response = AccountState;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetAccountStateByTransaction {
     pub address: SmartContractAddress,
     pub transaction_id: TransactionId,
@@ -127,7 +127,7 @@ impl Request for GetAccountStateByTransaction {
     type Response = AccountState;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetAccountTransactions {
     pub address: SmartContractAddress,
     pub from: TransactionId,
@@ -137,7 +137,7 @@ impl Request for GetAccountTransactions {
     type Response = Transactions;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetShardAccountCell {
     pub address: SmartContractAddress,
 }
@@ -146,7 +146,7 @@ impl Request for GetShardAccountCell {
     type Response = Cell;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetShardAccountCellOnBlock {
     pub address: SmartContractAddress,
     pub block_id: BlockIdExt,
@@ -156,7 +156,7 @@ impl Request for GetShardAccountCellOnBlock {
     type Response = Cell;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetShardAccountCellByTransaction {
     pub address: SmartContractAddress,
     pub transaction_id: TransactionId,
@@ -166,7 +166,7 @@ impl Request for GetShardAccountCellByTransaction {
     type Response = Cell;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RunGetMethod {
     pub address: SmartContractAddress,
     pub method: String,
@@ -176,3 +176,52 @@ pub struct RunGetMethod {
 impl Request for RunGetMethod {
     type Response = SmcRunResult;
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RunGetMethodOnBlock {
+    pub address: SmartContractAddress,
+    pub block_id: BlockIdExt,
+    pub method: String,
+    pub stack: Vec<StackEntry>,
+}
+
+impl Request for RunGetMethodOnBlock {
+    type Response = SmcRunResult;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GetConfigAll {
+    pub block_id: BlockIdExt,
+}
+
+impl Request for GetConfigAll {
+    type Response = Cell;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GetConfigParam {
+    pub block_id: BlockIdExt,
+    pub param: i32,
+}
+
+impl Request for GetConfigParam {
+    type Response = Cell;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GetLibraries {
+    pub hashes: Vec<String>,
+}
+
+impl Request for GetLibraries {
+    type Response = Vec<Library>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GetShardBlockProof {
+    pub id: BlockIdExt,
+}
+
+impl Request for GetShardBlockProof {
+    type Response = ShardBlockProof;
+}
@@ -26,7 +26,7 @@ impl From<BlockIdExt> for BlockId {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TransactionId {
     pub lt: i64,
     pub hash: String,
@@ -67,7 +67,7 @@ pub struct BlockHeader {
     pub prev_blocks: Vec<BlockIdExt>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ShortTxId {
     pub account: SmartContractAddress,
     pub lt: i64,
@@ -115,6 +115,7 @@ pub struct Message {
     pub created_lt: i64,
     pub body_hash: String,
     pub msg_data: MessageData,
+    pub decoded: Option<DecodedBody>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -125,11 +126,87 @@ pub enum MessageData {
     EncryptedText { text: String },
 }
 
+/// A body recognized by the `ton_tlb::message_body` decoders, reported next to the raw [`MessageData`].
+///
+/// Amounts are decimal strings as `VarUInteger 16` does not fit into `i64`,
+/// payloads are base64 encoded BoCs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedBody {
+    Comment {
+        text: String,
+    },
+    EncryptedComment {
+        text: String,
+    },
+    JettonTransfer {
+        query_id: u64,
+        amount: String,
+        destination: Option<SmartContractAddress>,
+        response_destination: Option<SmartContractAddress>,
+        custom_payload: Option<String>,
+        forward_ton_amount: String,
+        forward_payload: Option<String>,
+    },
+    JettonInternalTransfer {
+        query_id: u64,
+        amount: String,
+        from: Option<SmartContractAddress>,
+        response_address: Option<SmartContractAddress>,
+        forward_ton_amount: String,
+        forward_payload: Option<String>,
+    },
+    JettonTransferNotification {
+        query_id: u64,
+        amount: String,
+        sender: Option<SmartContractAddress>,
+        forward_payload: Option<String>,
+    },
+    JettonBurn {
+        query_id: u64,
+        amount: String,
+        response_destination: Option<SmartContractAddress>,
+        custom_payload: Option<String>,
+    },
+    NftTransfer {
+        query_id: u64,
+        new_owner: Option<SmartContractAddress>,
+        response_destination: Option<SmartContractAddress>,
+        custom_payload: Option<String>,
+        forward_amount: String,
+        forward_payload: Option<String>,
+    },
+    NftOwnershipAssigned {
+        query_id: u64,
+        prev_owner: Option<SmartContractAddress>,
+        forward_payload: Option<String>,
+    },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Cell {
     pub bytes: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Library {
+    pub hash: String,
+    pub cell: Cell,
+}
+
+/// Chain of proofs from the masterchain block that committed a shard block down to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShardBlockProof {
+    pub masterchain_id: BlockIdExt,
+    pub links: Vec<ShardBlockLink>,
+}
+
+/// `proof` is a base64 BoC binding `id` to the previous link, the first one to the masterchain block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShardBlockLink {
+    pub id: BlockIdExt,
+    pub proof: String,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct SmcRunResult {
     pub gas_used: i64,
@@ -137,7 +214,7 @@ pub struct SmcRunResult {
     pub stack: Vec<StackEntry>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum StackEntry {
     Slice { bytes: String },
     Cell { bytes: String },
@@ -0,0 +1,310 @@
+mod request;
+
+use crate::service::key::RequestKey;
+use pin_project::pin_project;
+use quick_cache::sync::Cache as Store;
+use std::any::Any;
+use std::future::Future;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::load::Load;
+use tower::{Layer, Service};
+
+/// Requests whose response never changes, e.g. everything scoped to a fixed `BlockIdExt`.
+pub trait Cacheable {
+    const IS_CACHEABLE: bool;
+}
+
+type Key = RequestKey;
+
+#[derive(Clone)]
+struct Entry {
+    value: Arc<dyn Any + Send + Sync>,
+    expires_at: Option<Instant>,
+}
+
+#[derive(Clone)]
+struct Shared {
+    store: Arc<Store<Key, Entry>>,
+    ttl: Option<Duration>,
+}
+
+impl Shared {
+    fn get<T: Clone + 'static>(&self, key: &Key) -> Option<T> {
+        let entry = self.store.get(key)?;
+        if entry.expires_at.is_some_and(|at| at <= Instant::now()) {
+            self.store.remove(key);
+
+            return None;
+        }
+
+        entry.value.downcast_ref::<T>().cloned()
+    }
+
+    fn insert<T: Send + Sync + 'static>(&self, key: Key, value: T) {
+        let entry = Entry {
+            value: Arc::new(value),
+            expires_at: self.ttl.map(|ttl| Instant::now() + ttl),
+        };
+
+        self.store.insert(key, entry);
+    }
+}
+
+#[derive(Clone)]
+pub struct CacheLayer {
+    shared: Shared,
+}
+
+impl CacheLayer {
+    pub fn new(capacity: usize, ttl: Option<Duration>) -> Self {
+        metrics::describe_counter!(
+            "ton_cache_hit_total",
+            "Number of requests served from cache"
+        );
+        metrics::describe_counter!(
+            "ton_cache_miss_total",
+            "Number of cacheable requests passed to the inner service"
+        );
+
+        Self {
+            shared: Shared {
+                store: Arc::new(Store::new(capacity)),
+                ttl,
+            },
+        }
+    }
+}
+
+impl<S> Layer<S> for CacheLayer {
+    type Service = Cache<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Cache {
+            inner,
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Cache<S> {
+    inner: S,
+    shared: Shared,
+}
+
+impl<S> Cache<S> {
+    pub fn new(inner: S, capacity: usize, ttl: Option<Duration>) -> Self {
+        CacheLayer::new(capacity, ttl).layer(inner)
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S, Request> Service<Request> for Cache<S>
+where
+    Request: Cacheable + Hash + Eq + Clone + Send + Sync + 'static,
+    S: Service<Request>,
+    S::Response: Clone + Send + Sync + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future, S::Response>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        if !Request::IS_CACHEABLE {
+            return ResponseFuture::miss(self.inner.call(req), None);
+        }
+
+        let req_type = std::any::type_name::<Request>();
+        let key = RequestKey::new(req.clone());
+
+        if let Some(response) = self.shared.get(&key) {
+            metrics::counter!("ton_cache_hit_total", "request_type" => req_type).increment(1);
+
+            return ResponseFuture::hit(response);
+        }
+        metrics::counter!("ton_cache_miss_total", "request_type" => req_type).increment(1);
+
+        ResponseFuture::miss(self.inner.call(req), Some((self.shared.clone(), key)))
+    }
+}
+
+impl<S> Load for Cache<S>
+where
+    S: Load,
+{
+    type Metric = S::Metric;
+
+    fn load(&self) -> Self::Metric {
+        self.inner.load()
+    }
+}
+
+#[pin_project]
+pub struct ResponseFuture<F, T> {
+    #[pin]
+    inner: Inner<F, T>,
+}
+
+#[pin_project(project = InnerProj)]
+enum Inner<F, T> {
+    Hit {
+        response: Option<T>,
+    },
+    Miss {
+        #[pin]
+        fut: F,
+        store: Option<(Shared, Key)>,
+    },
+}
+
+impl<F, T> ResponseFuture<F, T> {
+    fn hit(response: T) -> Self {
+        ResponseFuture {
+            inner: Inner::Hit {
+                response: Some(response),
+            },
+        }
+    }
+
+    fn miss(fut: F, store: Option<(Shared, Key)>) -> Self {
+        ResponseFuture {
+            inner: Inner::Miss { fut, store },
+        }
+    }
+}
+
+impl<F, T, E> Future for ResponseFuture<F, T>
+where
+    F: Future<Output = Result<T, E>>,
+    T: Clone + Send + Sync + 'static,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.project();
+        match me.inner.project() {
+            InnerProj::Hit { response } => {
+                Poll::Ready(Ok(response.take().expect("polled after ready")))
+            }
+            InnerProj::Miss { fut, store } => {
+                let result = std::task::ready!(fut.poll(cx));
+                if let (Ok(response), Some((shared, key))) = (&result, store.take()) {
+                    shared.insert(key, response.clone());
+                }
+
+                Poll::Ready(result)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::mock;
+    use std::future::{Ready, ready};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn serves_repeated_request_from_cache() {
+        let inner = answering(1, |req: Immutable| Ok(req.0));
+        let mut svc = Cache::new(inner, 16, None);
+
+        let first = (&mut svc).oneshot(Immutable(1)).await.unwrap();
+        let second = (&mut svc).oneshot(Immutable(1)).await.unwrap();
+
+        assert_eq!(first, 1);
+        assert_eq!(second, 1);
+    }
+
+    #[tokio::test]
+    async fn keys_cache_by_request() {
+        let inner = answering(2, |req: Immutable| Ok(req.0));
+        let mut svc = Cache::new(inner, 16, None);
+
+        (&mut svc).oneshot(Immutable(1)).await.unwrap();
+        let other = (&mut svc).oneshot(Immutable(2)).await.unwrap();
+
+        assert_eq!(other, 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_cache_non_cacheable_requests() {
+        let inner = answering(2, |req: Mutable| Ok(req.0));
+        let mut svc = Cache::new(inner, 16, None);
+
+        (&mut svc).oneshot(Mutable(1)).await.unwrap();
+        (&mut svc).oneshot(Mutable(1)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn does_not_cache_errors() {
+        let inner = answering(2, |_: Immutable| Err(anyhow::anyhow!("failed")));
+        let mut svc = Cache::new(inner, 16, None);
+
+        let first = (&mut svc).oneshot(Immutable(1)).await;
+        let second = (&mut svc).oneshot(Immutable(1)).await;
+
+        assert!(first.is_err());
+        assert!(second.is_err());
+    }
+
+    #[tokio::test]
+    async fn expires_entries_after_ttl() {
+        let inner = answering(2, |req: Immutable| Ok(req.0));
+        let mut svc = Cache::new(inner, 16, Some(Duration::ZERO));
+
+        (&mut svc).oneshot(Immutable(1)).await.unwrap();
+        (&mut svc).oneshot(Immutable(1)).await.unwrap();
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Immutable(u32);
+
+    impl Cacheable for Immutable {
+        const IS_CACHEABLE: bool = true;
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Mutable(u32);
+
+    impl Cacheable for Mutable {
+        const IS_CACHEABLE: bool = false;
+    }
+
+    mock! {
+        Service<R: 'static> {}
+
+        impl<R: 'static> Service<R> for Service<R> {
+            type Response = u32;
+            type Error = anyhow::Error;
+            type Future = Ready<Result<u32, anyhow::Error>>;
+
+            fn poll_ready<'a>(&mut self, _cx: &mut Context<'a>) -> Poll<Result<(), anyhow::Error>>;
+            fn call(&mut self, _req: R) -> Ready<Result<u32, anyhow::Error>>;
+        }
+    }
+
+    fn answering<R: 'static>(
+        calls: usize,
+        answer: impl Fn(R) -> anyhow::Result<u32> + Send + 'static,
+    ) -> MockService<R> {
+        let mut svc = MockService::new();
+        svc.expect_poll_ready().returning(|_| Poll::Ready(Ok(())));
+        svc.expect_call()
+            .times(calls)
+            .returning(move |req| ready(answer(req)));
+        svc
+    }
+}
@@ -0,0 +1,41 @@
+use crate::request::*;
+use crate::service::cache::Cacheable;
+
+macro_rules! impl_cacheable {
+    ($value:expr; $($ty:ty),+ $(,)?) => {
+        $(
+            impl Cacheable for $ty {
+                const IS_CACHEABLE: bool = $value;
+            }
+        )+
+    };
+}
+
+impl_cacheable!(true;
+    GetShards,
+    GetBlockHeader,
+    GetTransactionIds,
+    GetTransactions,
+    GetAccountStateOnBlock,
+    GetShardAccountCellOnBlock,
+    RunGetMethodOnBlock,
+    GetConfigAll,
+    GetConfigParam,
+);
+
+impl_cacheable!(false;
+    GetMasterchainInfo,
+    Sync,
+    LookUpBlockBySeqno,
+    LookUpBlockByLt,
+    GetAccountState,
+    GetAccountStateByTransaction,
+    GetAccountTransactions,
+    GetShardAccountCell,
+    GetShardAccountCellByTransaction,
+    RunGetMethod,
+    GetLibraries,
+    GetShardBlockProof,
+    SendMessage,
+    SendMessageReturningHash,
+);
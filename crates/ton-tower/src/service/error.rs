@@ -1,3 +1,4 @@
+use crate::service::singleflight::SharedError;
 use futures::TryFutureExt;
 use futures::future::MapErr;
 use std::task::{Context, Poll};
@@ -12,6 +13,32 @@ pub enum Error {
     Tower(#[from] tower::BoxError),
 }
 
+/// The request itself is at fault, e.g. it names an unknown block or a malformed address,
+/// so any other server would answer it the same way.
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct RequestError(pub anyhow::Error);
+
+impl RequestError {
+    /// Looks through the whole chain, including the errors wrapped by [`Error`] whose
+    /// transparent `source` skips the wrapped error itself.
+    pub fn is_cause_of(error: &(dyn std::error::Error + 'static)) -> bool {
+        std::iter::successors(Some(error), |e| e.source()).any(|e| {
+            if e.is::<RequestError>() {
+                return true;
+            }
+
+            match e.downcast_ref::<Error>() {
+                Some(Error::Custom(inner)) => Self::is_cause_of(&**inner),
+                Some(Error::Tower(inner)) => Self::is_cause_of(&**inner),
+                None => e
+                    .downcast_ref::<SharedError>()
+                    .is_some_and(|shared| Self::is_cause_of(shared.inner())),
+            }
+        })
+    }
+}
+
 #[derive(Default)]
 pub struct ErrorLayer;
 
@@ -61,3 +88,42 @@ where
         self.inner.load()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::{BoxError, ServiceExt};
+
+    #[tokio::test]
+    async fn keeps_request_error_through_error_service() {
+        let inner = tower::service_fn(|()| async {
+            Err::<(), anyhow::Error>(RequestError(anyhow::anyhow!("unknown block")).into())
+        });
+
+        let error = ErrorService::new(inner).oneshot(()).await.unwrap_err();
+
+        assert!(RequestError::is_cause_of(&*error));
+    }
+
+    #[tokio::test]
+    async fn keeps_request_error_through_boxed_error() {
+        let inner = tower::service_fn(|()| async {
+            Err::<(), BoxError>(RequestError(anyhow::anyhow!("unknown block")).into())
+        });
+
+        let error = ErrorService::new(inner).oneshot(()).await.unwrap_err();
+
+        assert!(RequestError::is_cause_of(&*error));
+    }
+
+    #[tokio::test]
+    async fn does_not_mark_other_errors() {
+        let inner = tower::service_fn(|()| async {
+            Err::<(), BoxError>(anyhow::anyhow!("not ready").into())
+        });
+
+        let error = ErrorService::new(inner).oneshot(()).await.unwrap_err();
+
+        assert!(!RequestError::is_cause_of(&*error));
+    }
+}
@@ -0,0 +1,89 @@
+use std::any::Any;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Identity of a request, used to key per-request state such as cached responses.
+///
+/// Keys of different request types never compare equal, so one map holds keys of every request.
+#[derive(Clone)]
+pub struct RequestKey(Arc<dyn DynKey>);
+
+impl RequestKey {
+    pub fn new<R>(req: R) -> Self
+    where
+        R: Hash + Eq + Send + Sync + 'static,
+    {
+        Self(Arc::new(req))
+    }
+}
+
+impl PartialEq for RequestKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.dyn_eq(other.0.as_ref())
+    }
+}
+
+impl Eq for RequestKey {}
+
+impl Hash for RequestKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Any::type_id(self.0.as_any()).hash(state);
+        self.0.dyn_hash(state);
+    }
+}
+
+trait DynKey: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    fn dyn_eq(&self, other: &dyn DynKey) -> bool;
+
+    fn dyn_hash(&self, state: &mut dyn Hasher);
+}
+
+impl<T> DynKey for T
+where
+    T: Hash + Eq + Send + Sync + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn dyn_eq(&self, other: &dyn DynKey) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        self.hash(&mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[derive(PartialEq, Eq, Hash)]
+    struct First(u32);
+
+    #[derive(PartialEq, Eq, Hash)]
+    struct Second(u32);
+
+    #[test]
+    fn equal_requests_share_a_key() {
+        let keys: HashSet<_> = [RequestKey::new(First(1)), RequestKey::new(First(1))].into();
+
+        assert_eq!(keys.len(), 1);
+    }
+
+    #[test]
+    fn keys_differ_by_value_and_type() {
+        let keys: HashSet<_> = [
+            RequestKey::new(First(1)),
+            RequestKey::new(First(2)),
+            RequestKey::new(Second(1)),
+        ]
+        .into();
+
+        assert_eq!(keys.len(), 3);
+    }
+}
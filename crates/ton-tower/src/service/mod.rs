@@ -1,6 +1,9 @@
+pub mod cache;
 pub mod error;
+pub mod key;
 pub mod metric;
 pub mod reconnect;
 pub mod retry;
 pub mod shared;
+pub mod singleflight;
 pub mod timeout;
@@ -28,6 +28,11 @@ impl_retryable!(true;
     GetShardAccountCellOnBlock,
     GetShardAccountCellByTransaction,
     RunGetMethod,
+    RunGetMethodOnBlock,
+    GetConfigAll,
+    GetConfigParam,
+    GetLibraries,
+    GetShardBlockProof,
 );
 
 impl_retryable!(false;
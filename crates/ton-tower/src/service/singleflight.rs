@@ -0,0 +1,326 @@
+use crate::service::key::RequestKey;
+use crate::service::retry::Retryable;
+use futures::future::{BoxFuture, Shared, WeakShared};
+use futures::{FutureExt, TryFutureExt};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tower::load::Load;
+use tower::{BoxError, Layer, Service};
+
+type Key = RequestKey;
+type Flight<T> = Shared<BoxFuture<'static, Result<T, SharedError>>>;
+type WeakFlight<T> = WeakShared<BoxFuture<'static, Result<T, SharedError>>>;
+type Flights = Arc<Mutex<FlightMap>>;
+
+/// Flights are held by their waiters only, the map keeps weak handles to join them.
+#[derive(Default)]
+struct FlightMap {
+    next_id: u64,
+    flights: HashMap<Key, (u64, Box<dyn Any + Send>)>,
+}
+
+/// Removes the flight once it completes or its last waiter is dropped.
+struct FlightGuard {
+    flights: Flights,
+    key: Key,
+    id: u64,
+}
+
+impl Drop for FlightGuard {
+    fn drop(&mut self) {
+        let mut map = self.flights.lock().unwrap();
+        if map
+            .flights
+            .get(&self.key)
+            .is_some_and(|(id, _)| *id == self.id)
+        {
+            map.flights.remove(&self.key);
+        }
+    }
+}
+
+/// Error of a coalesced call, handed out to every waiter.
+#[derive(Debug, Clone)]
+pub struct SharedError(Arc<BoxError>);
+
+impl Display for SharedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl SharedError {
+    pub fn inner(&self) -> &(dyn std::error::Error + 'static) {
+        &**self.0
+    }
+}
+
+impl std::error::Error for SharedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.inner())
+    }
+}
+
+#[derive(Default)]
+pub struct SingleflightLayer;
+
+impl SingleflightLayer {
+    pub fn new() -> Self {
+        metrics::describe_counter!(
+            "ton_singleflight_joined_total",
+            "Number of requests merged into an identical in-flight request"
+        );
+
+        Self
+    }
+}
+
+impl<S> Layer<S> for SingleflightLayer {
+    type Service = Singleflight<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Singleflight::new(inner)
+    }
+}
+
+/// Merges identical concurrent requests into one call of the inner service.
+///
+/// Only idempotent requests, i.e. [`Retryable`] ones, are merged.
+#[derive(Clone)]
+pub struct Singleflight<S> {
+    inner: S,
+    flights: Flights,
+}
+
+impl<S> Singleflight<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            flights: Flights::default(),
+        }
+    }
+}
+
+impl<S, Request> Service<Request> for Singleflight<S>
+where
+    Request: Retryable + Hash + Eq + Clone + Send + Sync + 'static,
+    S: Service<Request>,
+    S::Response: Clone + Send + Sync + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<S::Response, BoxError>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        if !Request::IS_RETRYABLE {
+            return self.inner.call(req).map_err(Into::into).boxed();
+        }
+
+        let key = RequestKey::new(req.clone());
+        let mut map = self.flights.lock().unwrap();
+        if let Some(flight) = map
+            .flights
+            .get(&key)
+            .and_then(|(_, flight)| flight.downcast_ref::<WeakFlight<S::Response>>())
+            .and_then(WeakShared::upgrade)
+        {
+            let req_type = std::any::type_name::<Request>();
+            metrics::counter!("ton_singleflight_joined_total", "request_type" => req_type)
+                .increment(1);
+
+            return flight.map_err(Into::into).boxed();
+        }
+
+        let id = map.next_id;
+        map.next_id += 1;
+        let response = self.inner.call(req);
+        let flight: Flight<S::Response> = {
+            let guard = FlightGuard {
+                flights: Arc::clone(&self.flights),
+                key: key.clone(),
+                id,
+            };
+
+            async move {
+                let _guard = guard;
+
+                response.await.map_err(|e| SharedError(Arc::new(e.into())))
+            }
+            .boxed()
+            .shared()
+        };
+        let weak = flight.downgrade().expect("flight is not polled yet");
+        map.flights.insert(key, (id, Box::new(weak)));
+
+        flight.map_err(Into::into).boxed()
+    }
+}
+
+impl<S> Load for Singleflight<S>
+where
+    S: Load,
+{
+    type Metric = S::Metric;
+
+    fn load(&self) -> Self::Metric {
+        self.inner.load()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::error::RequestError;
+    use futures::channel::oneshot;
+    use mockall::mock;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn merges_identical_in_flight_requests() {
+        let (tx, inner) = gated(1);
+        let mut svc = Singleflight::new(inner);
+
+        let first = svc.ready().await.unwrap().call(Idempotent(1));
+        let second = svc.ready().await.unwrap().call(Idempotent(1));
+        tx.send(7).unwrap();
+
+        assert_eq!(first.await.unwrap(), 7);
+        assert_eq!(second.await.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn does_not_merge_different_requests() {
+        let (tx, inner) = gated(2);
+        let mut svc = Singleflight::new(inner);
+
+        let first = svc.ready().await.unwrap().call(Idempotent(1));
+        let second = svc.ready().await.unwrap().call(Idempotent(2));
+        tx.send(7).unwrap();
+
+        assert!(first.await.is_ok());
+        assert!(second.await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn does_not_merge_non_retryable_requests() {
+        let (tx, inner) = gated(2);
+        let mut svc = Singleflight::new(inner);
+
+        let first = svc.ready().await.unwrap().call(SideEffect(1));
+        let second = svc.ready().await.unwrap().call(SideEffect(1));
+        tx.send(7).unwrap();
+
+        assert!(first.await.is_ok());
+        assert!(second.await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn calls_inner_again_after_completion() {
+        let (tx, inner) = gated(2);
+        let mut svc = Singleflight::new(inner);
+        tx.send(7).unwrap();
+
+        (&mut svc).oneshot(Idempotent(1)).await.unwrap();
+        (&mut svc).oneshot(Idempotent(1)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn shares_error_with_every_waiter() {
+        let (tx, inner) = gated(1);
+        let mut svc = Singleflight::new(inner);
+
+        let first = svc.ready().await.unwrap().call(Idempotent(1));
+        let second = svc.ready().await.unwrap().call(Idempotent(1));
+        tx.send(0).unwrap();
+
+        assert_eq!(first.await.unwrap_err().to_string(), "failed");
+        assert_eq!(second.await.unwrap_err().to_string(), "failed");
+    }
+
+    #[tokio::test]
+    async fn keeps_request_error_in_shared_error() {
+        let inner = tower::service_fn(|_: Idempotent| async {
+            Err::<u32, anyhow::Error>(RequestError(anyhow::anyhow!("unknown block")).into())
+        });
+
+        let error = Singleflight::new(inner)
+            .oneshot(Idempotent(1))
+            .await
+            .unwrap_err();
+
+        assert!(RequestError::is_cause_of(&*error));
+    }
+
+    #[tokio::test]
+    async fn cancels_flight_when_every_waiter_is_dropped() {
+        let (tx, inner) = gated(2);
+        let mut svc = Singleflight::new(inner);
+
+        let first = svc.ready().await.unwrap().call(Idempotent(1));
+        let second = svc.ready().await.unwrap().call(Idempotent(1));
+        drop(first);
+        drop(second);
+        let third = svc.ready().await.unwrap().call(Idempotent(1));
+        tx.send(7).unwrap();
+
+        assert_eq!(third.await.unwrap(), 7);
+        assert!(svc.flights.lock().unwrap().flights.is_empty());
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Idempotent(u32);
+
+    impl Retryable for Idempotent {
+        const IS_RETRYABLE: bool = true;
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct SideEffect(u32);
+
+    impl Retryable for SideEffect {
+        const IS_RETRYABLE: bool = false;
+    }
+
+    mock! {
+        Service<R: 'static> {}
+
+        impl<R: 'static> Service<R> for Service<R> {
+            type Response = u32;
+            type Error = anyhow::Error;
+            type Future = BoxFuture<'static, Result<u32, anyhow::Error>>;
+
+            fn poll_ready<'a>(&mut self, _cx: &mut Context<'a>) -> Poll<Result<(), anyhow::Error>>;
+            fn call(&mut self, _req: R) -> BoxFuture<'static, Result<u32, anyhow::Error>>;
+        }
+    }
+
+    /// Answers every call with the value sent to the gate, fails on zero.
+    fn gated<R: 'static>(calls: usize) -> (oneshot::Sender<u32>, MockService<R>) {
+        let (tx, rx) = oneshot::channel();
+        let gate = rx.shared();
+
+        let mut svc = MockService::new();
+        svc.expect_poll_ready().returning(|_| Poll::Ready(Ok(())));
+        svc.expect_call().times(calls).returning(move |_| {
+            gate.clone()
+                .map(|value| match value {
+                    Ok(0) => Err(anyhow::anyhow!("failed")),
+                    Ok(value) => Ok(value),
+                    Err(e) => Err(e.into()),
+                })
+                .boxed()
+        });
+
+        (tx, svc)
+    }
+}
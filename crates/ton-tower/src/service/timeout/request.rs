@@ -13,6 +13,11 @@ impl ToTimeout for GetShardAccountCell {}
 impl ToTimeout for GetShardAccountCellOnBlock {}
 impl ToTimeout for GetShardAccountCellByTransaction {}
 impl ToTimeout for RunGetMethod {}
+impl ToTimeout for RunGetMethodOnBlock {}
+impl ToTimeout for GetConfigAll {}
+impl ToTimeout for GetConfigParam {}
+impl ToTimeout for GetLibraries {}
+impl ToTimeout for GetShardBlockProof {}
 impl ToTimeout for LookUpBlockBySeqno {}
 impl ToTimeout for LookUpBlockByLt {}
 impl ToTimeout for GetShards {}
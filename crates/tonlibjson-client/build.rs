@@ -130,11 +130,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             vec!["Clone", "Serialize", "new", "Hash", "Eq", "PartialEq"],
         )
         .configure("blocks.getShards", vec!["Clone", "Serialize", "new"])
+        .configure(
+            "blocks.getShardBlockProof",
+            vec!["Clone", "Serialize", "new"],
+        )
         .configure("blocks.getTransactions", vec!["Clone", "Serialize", "new"])
         .configure("raw.sendMessage", vec!["Serialize", "new"])
         .configure("raw.sendMessageReturnHash", vec!["Serialize", "new"])
         .configure("smc.load", vec!["Clone", "Serialize", "new"])
         .configure("smc.runGetMethod", vec!["Clone", "Serialize", "new"])
+        .configure("smc.getLibraries", vec!["Clone", "Serialize", "new"])
+        .configure("getConfigAll", vec!["Clone", "Serialize", "new"])
+        .configure("getConfigParam", vec!["Clone", "Serialize", "new"])
         .configure_full(
             "raw.getTransactionsV2",
             configure_type()
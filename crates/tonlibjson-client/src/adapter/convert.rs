@@ -7,6 +7,7 @@ use base64::Engine;
 use base64::engine::general_purpose::STANDARD as base64;
 use std::str::FromStr;
 use ton_address::SmartContractAddress;
+use ton_tlb::message_body::decode_body_base64;
 use ton_tower::response::ShortTxId;
 
 impl From<tl::TonBlockIdExt> for ton_tower::response::BlockIdExt {
@@ -221,6 +222,11 @@ impl TryFrom<tl::RawMessage> for ton_tower::response::Message {
             .account_address
             .map(|s| SmartContractAddress::from_str(&s))
             .transpose()?;
+        // tonlib only recognizes comments itself, the rest is up to the shared decoders
+        let decoded = match &v.msg_data {
+            tl::MsgBoxedData::MsgDataRaw(d) => decode_body_base64(&d.body),
+            _ => None,
+        };
         Ok(Self {
             hash: v.hash,
             source,
@@ -231,6 +237,7 @@ impl TryFrom<tl::RawMessage> for ton_tower::response::Message {
             created_lt: v.created_lt,
             body_hash: v.body_hash,
             msg_data: v.msg_data.into(),
+            decoded,
         })
     }
 }
@@ -261,6 +268,31 @@ impl From<tl::TvmCell> for ton_tower::response::Cell {
     }
 }
 
+impl From<tl::BlocksShardBlockProof> for ton_tower::response::ShardBlockProof {
+    fn from(v: tl::BlocksShardBlockProof) -> Self {
+        Self {
+            masterchain_id: v.mc_id.into(),
+            links: v
+                .links
+                .into_iter()
+                .map(|link| ton_tower::response::ShardBlockLink {
+                    id: link.id.into(),
+                    proof: link.proof,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<tl::SmcLibraryEntry> for ton_tower::response::Library {
+    fn from(v: tl::SmcLibraryEntry) -> Self {
+        Self {
+            hash: v.hash,
+            cell: ton_tower::response::Cell { bytes: v.data },
+        }
+    }
+}
+
 impl From<tl::SmcRunResult> for ton_tower::response::SmcRunResult {
     fn from(v: tl::SmcRunResult) -> Self {
         Self {
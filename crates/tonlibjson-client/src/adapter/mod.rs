@@ -2,13 +2,14 @@ mod convert;
 
 use crate::client::TonlibjsonClient;
 use crate::tl::{
-    AccountAddress, BlocksGetBlockHeader, BlocksGetMasterchainInfo, BlocksGetShards,
-    BlocksGetTransactions, BlocksGetTransactionsExt, BlocksLookupBlock,
+    AccountAddress, BlocksGetBlockHeader, BlocksGetMasterchainInfo, BlocksGetShardBlockProof,
+    BlocksGetShards, BlocksGetTransactions, BlocksGetTransactionsExt, BlocksLookupBlock,
+    GetConfigAll as TlGetConfigAll, GetConfigParam as TlGetConfigParam,
     GetShardAccountCell as TlGetShardAccountCell,
     GetShardAccountCellByTransaction as TlGetShardAccountCellByTransaction, InternalTransactionId,
     RawGetAccountState, RawGetAccountStateByTransaction, RawGetTransactionsV2, RawSendMessage,
-    RawSendMessageReturnHash, SmcBoxedMethodId, SmcLoad, SmcRunGetMethod, Sync as TlSync,
-    TonBlockId, TvmBoxedStackEntry,
+    RawSendMessageReturnHash, SmcBoxedMethodId, SmcGetLibraries, SmcLoad, SmcRunGetMethod,
+    Sync as TlSync, TonBlockId, TonBlockIdExt, TvmBoxedStackEntry,
 };
 use anyhow::anyhow;
 use futures::future::BoxFuture;
@@ -16,9 +17,11 @@ use futures::{FutureExt, TryFutureExt, future};
 use std::task::{Context, Poll};
 use ton_tower::request::{
     GetAccountState, GetAccountStateByTransaction, GetAccountStateOnBlock, GetAccountTransactions,
-    GetBlockHeader, GetMasterchainInfo, GetShardAccountCell, GetShardAccountCellByTransaction,
-    GetShardAccountCellOnBlock, GetShards, GetTransactionIds, GetTransactions, LookUpBlockByLt,
-    LookUpBlockBySeqno, RunGetMethod, SendMessage, SendMessageReturningHash, Sync,
+    GetBlockHeader, GetConfigAll, GetConfigParam, GetLibraries, GetMasterchainInfo,
+    GetShardAccountCell, GetShardAccountCellByTransaction, GetShardAccountCellOnBlock,
+    GetShardBlockProof, GetShards, GetTransactionIds, GetTransactions, LookUpBlockByLt,
+    LookUpBlockBySeqno, RunGetMethod, RunGetMethodOnBlock, SendMessage, SendMessageReturningHash,
+    Sync,
 };
 use tower::{Service, ServiceExt};
 pub mod make;
@@ -133,6 +136,26 @@ impl Service<GetBlockHeader> for TonlibjsonAdapter {
     }
 }
 
+impl Service<GetShardBlockProof> for TonlibjsonAdapter {
+    type Response = ton_tower::response::ShardBlockProof;
+    type Error = anyhow::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        <TonlibjsonClient as Service<BlocksGetShardBlockProof>>::poll_ready(&mut self.inner, cx)
+    }
+
+    fn call(&mut self, req: GetShardBlockProof) -> Self::Future {
+        // mode 0: prove from the masterchain block that committed the shard block, `from` is unused
+        let id: TonBlockIdExt = req.id.into();
+
+        self.inner
+            .call(BlocksGetShardBlockProof::new(id.clone(), 0, id))
+            .map_ok(Into::into)
+            .boxed()
+    }
+}
+
 impl Service<GetTransactionIds> for TonlibjsonAdapter {
     type Response = ton_tower::response::BlockTransactions;
     type Error = anyhow::Error;
@@ -359,6 +382,69 @@ impl Service<GetShardAccountCellOnBlock> for TonlibjsonAdapter {
     }
 }
 
+impl Service<GetConfigAll> for TonlibjsonAdapter {
+    type Response = ton_tower::response::Cell;
+    type Error = anyhow::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        <TonlibjsonClient as Service<crate::tl::WithBlock<TlGetConfigAll>>>::poll_ready(
+            &mut self.inner,
+            cx,
+        )
+    }
+
+    fn call(&mut self, req: GetConfigAll) -> Self::Future {
+        self.inner
+            .call(crate::tl::WithBlock::new(
+                req.block_id.into(),
+                TlGetConfigAll::new(0),
+            ))
+            .map_ok(|info| info.config.into())
+            .boxed()
+    }
+}
+
+impl Service<GetConfigParam> for TonlibjsonAdapter {
+    type Response = ton_tower::response::Cell;
+    type Error = anyhow::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        <TonlibjsonClient as Service<crate::tl::WithBlock<TlGetConfigParam>>>::poll_ready(
+            &mut self.inner,
+            cx,
+        )
+    }
+
+    fn call(&mut self, req: GetConfigParam) -> Self::Future {
+        self.inner
+            .call(crate::tl::WithBlock::new(
+                req.block_id.into(),
+                TlGetConfigParam::new(0, req.param),
+            ))
+            .map_ok(|info| info.config.into())
+            .boxed()
+    }
+}
+
+impl Service<GetLibraries> for TonlibjsonAdapter {
+    type Response = Vec<ton_tower::response::Library>;
+    type Error = anyhow::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        <TonlibjsonClient as Service<SmcGetLibraries>>::poll_ready(&mut self.inner, cx)
+    }
+
+    fn call(&mut self, req: GetLibraries) -> Self::Future {
+        self.inner
+            .call(SmcGetLibraries::new(req.hashes))
+            .map_ok(|result| result.result.into_iter().map(Into::into).collect())
+            .boxed()
+    }
+}
+
 impl Service<GetShardAccountCellByTransaction> for TonlibjsonAdapter {
     type Response = ton_tower::response::Cell;
     type Error = anyhow::Error;
@@ -410,3 +496,36 @@ impl Service<RunGetMethod> for TonlibjsonAdapter {
         .boxed()
     }
 }
+
+impl Service<RunGetMethodOnBlock> for TonlibjsonAdapter {
+    type Response = ton_tower::response::SmcRunResult;
+    type Error = anyhow::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        <TonlibjsonClient as Service<crate::tl::WithBlock<SmcLoad>>>::poll_ready(
+            &mut self.inner,
+            cx,
+        )
+    }
+
+    fn call(&mut self, req: RunGetMethodOnBlock) -> Self::Future {
+        let method = SmcBoxedMethodId::by_name(&req.method);
+        let stack: Vec<TvmBoxedStackEntry> = req.stack.into_iter().map(Into::into).collect();
+
+        let load = self.inner.call(crate::tl::WithBlock::new(
+            req.block_id.into(),
+            SmcLoad::new(AccountAddress::new(&req.address)),
+        ));
+        let inner = self.inner.clone();
+        async move {
+            let info = load.await?;
+            let resp = inner
+                .oneshot(SmcRunGetMethod::new(info.id, method, stack))
+                .await?;
+
+            Ok(resp.into())
+        }
+        .boxed()
+    }
+}
@@ -16,6 +16,7 @@ use std::time::Duration;
 use tokio::sync::{oneshot, watch};
 use tokio_util::sync::{CancellationToken, DropGuard};
 use ton_config::TonConfig;
+use ton_tower::service::error::RequestError;
 use tower::Service;
 use uuid::Uuid;
 
@@ -280,7 +281,13 @@ where
                     response
                         .data
                         .inspect_err(|error| tracing::trace!("Error occurred: {:?}", error))
-                        .map_err(anyhow::Error::from)
+                        .map_err(|error| {
+                            if error.is_request_error() {
+                                RequestError(error.into()).into()
+                            } else {
+                                anyhow::Error::from(error)
+                            }
+                        })
                         .and_then(|data| {
                             serde_json::from_value::<R>(data).map_err(anyhow::Error::from)
                         }),
@@ -248,6 +248,12 @@ impl TonError {
     pub fn is_disconnected(&self) -> bool {
         self.code == 500 && self.message == "LITE_SERVER_NETWORK"
     }
+
+    /// Invalid arguments and queries rejected by the lite-server, which tonlib reports as
+    /// `LITE_SERVER_UNKNOWN`. Not ready, timeout and other lite-server errors are server state.
+    pub fn is_request_error(&self) -> bool {
+        (400..500).contains(&self.code) || self.message.starts_with("LITE_SERVER_UNKNOWN")
+    }
 }
 
 impl Display for TonError {
@@ -480,4 +486,19 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn request_errors_are_told_apart_from_network_errors() {
+        let error = |code: i32, message: &str| TonError {
+            code,
+            message: message.to_owned(),
+        };
+
+        assert!(error(400, "INVALID_ACCOUNT_ADDRESS").is_request_error());
+        assert!(error(500, "LITE_SERVER_UNKNOWN: transaction hash mismatch").is_request_error());
+        assert!(!error(500, "LITE_SERVER_NOTREADY: cannot load block").is_request_error());
+        assert!(!error(500, "LITE_SERVER_TIMEOUT").is_request_error());
+        assert!(!error(500, "LITE_SERVER_NETWORK").is_request_error());
+        assert!(!error(500, "INTERNAL").is_request_error());
+    }
 }